@@ -11,6 +11,66 @@ KEYS_DIR = "keys"         # Where to save the audit trail
 if not os.path.exists(KEYS_DIR):
     os.makedirs(KEYS_DIR)
 
+# Encrypted pool seed saved on shutdown and mixed back in at startup (key kept in <file>.key).
+# None disables persistence.
+SEED_FILE = os.path.join(KEYS_DIR, "pool.seed")
+# Session identity signature scheme: "falcon512" or "falcon1024"
+PQC_SIGNER = "falcon512"
+# Key vault: secret keys in keys/*.json are sealed with a passphrase-derived key (Argon2id +
+# XChaCha20-Poly1305). The passphrase is read from this environment variable at startup;
+# without it minting fails until unlock_vault() is called.
+VAULT_PASSPHRASE_ENV = "CHAOS_MAGNET_VAULT_PASSPHRASE"
+# Write secret keys as plaintext hex while the vault is locked (not recommended)
+VAULT_ALLOW_PLAINTEXT = False
+# Keep the Falcon identity across restarts, sealed in the vault (None = new key every run)
+IDENTITY_FILE = None  # e.g. os.path.join(KEYS_DIR, "identity.json")
+
+# --- Extraction ---
+# Conditioner for the 200-byte extraction pool: sha256, sha512, sha3-256, blake3, shake256,
+# or hmac-sha256 (keyed by a secret derived from the main pool, per SP 800-90B vetted conditioning)
+# "vetted" is an alias for hmac-sha256; vetted conditioners also produce an SP 800-90B h_out claim
+# toeplitz is a universal-hash (information-theoretic) extractor with a pool-refreshed seed
+EXTRACTOR = "sha256"
+# Raw bytes accumulated per extraction and bytes emitted (output <= input / 2).
+# Fixed-width digests cap the output (32 bytes, SHA-512 64) and wider values are rejected;
+# shake256/toeplitz honour any width.
+EXTRACTION_INPUT_BYTES = 200
+EXTRACTION_OUTPUT_BYTES = 32
+# Size each extraction by measured source quality instead of a fixed input: a strong TRNG
+# extracts after ~2x the output, weak sources gather up to 4x EXTRACTION_INPUT_BYTES
+ADAPTIVE_EXTRACTION = True
+# Per-sample whitening ahead of pooling: XOR-fold rounds (0 = off), then SHA-256 over 64+ byte samples
+WHITEN_FOLD_ROUNDS = 0
+WHITEN_HASH = False
+# Per-source debiasing before extraction: {"SOURCE": "von_neumann" | "xor_fold" | ("xor_fold", rounds)}
+# e.g. {"MOUSE": "von_neumann", "SERIAL": ("xor_fold", 2)}
+DEBIAS_SOURCES = {}
+
+# --- Pool Routing ---
+# The fast pool reseeds the output DRBGs; the slow pool gathers 2048 assessed bits per reseed
+# and backs key minting. {"SOURCE": "fast" | "slow" | "both"}; unlisted sources feed both.
+# e.g. {"SYS": "fast", "MOUSE_MOV": "fast", "HWRNG": "slow"}
+POOL_ROUTES = {}
+
+# --- Entropy Ledger ---
+# Assessed credit in bits per raw byte, per source. Credits are further capped at half the
+# measured min-entropy; unlisted sources get 0.5. Minting and output APIs debit the ledger.
+ENTROPY_CREDITS = {"TRNG": 4.0, "AUDIO": 1.0, "VIDEO_0": 1.0, "SYS": 0.25}
+
+# --- Output Generators ---
+# Per-consumer DRBG: "ctr" (SP 800-90A CTR-DRBG, AES-256) or "hmac" (HMAC-DRBG, SHA-256).
+# NETWORK feeds uplink/P2P payloads, PYTHON serves get_random_bytes; unlisted consumers default to ctr.
+OUTPUT_GENERATORS = {"NETWORK": "ctr"}
+# Consumers that reseed from the pool before every request
+PREDICTION_RESISTANCE = []
+# Per-consumer output quotas: {"CONSUMER": (bytes_per_sec, bytes_per_pool_reseed)}, None = unlimited.
+# Keeps a greedy consumer from draining fresh pool entropy ahead of key minting.
+OUTPUT_QUOTAS = {}
+# Reseed triggers for output generators (None disables one; any enabled trigger fires)
+RESEED_MAX_AGE_SECS = 30
+RESEED_MAX_OUTPUT_BYTES = None
+RESEED_MIN_CREDIT_BITS = None
+
 # --- Harvester Polling Rates (Seconds) ---
 RATE_SYSTEM = 0.5
 RATE_TRNG   = 1.0         # Poll hardware RNG every second (don't drain it too fast)
@@ -18,11 +78,104 @@ RATE_MOUSE  = 0.0         # 0 = event driven
 RATE_AUDIO  = 0.2
 RATE_VIDEO  = 1.0         # Slow poll to save CPU/Battery
 
+# --- Audio Inputs ---
+# Device names to capture ("" = system default, reported as AUDIO); named devices
+# become AUDIO_<NAME> sources. ChaosEngine().list_audio_devices() shows the names.
+AUDIO_DEVICES = [""]
+# "normal" or "ultrasonic" (high-pass >18 kHz; needs a 44.1 kHz+ input)
+AUDIO_MODE = "normal"
+
+# --- Cameras ---
+# Indices to harvest; each becomes its own VIDEO_<index> source.
+# chaos_magnet_core.ChaosEngine().list_video_devices() shows what is attached.
+VIDEO_DEVICES = [0]
+# (x, y, width, height) to harvest only part of the frame (e.g. a lava lamp); None = whole frame
+VIDEO_ROI = None
+# Cover the lens: exposure/gain go to max and only sensor noise (frame-difference LSBs) is kept
+VIDEO_DARK_MODE = False
+
+# --- Hardware RNG Device ---
+# On-board RNG or USB TRNG dongle exposed as a character device
+HWRNG_DEVICE = "/dev/hwrng"
+
+# --- Latency Probe Targets ---
+# "host:port" pairs timed with a TCP handshake (rate limited to one probe per host every 2s)
+PING_TARGETS = ["1.1.1.1:443", "8.8.8.8:443", "9.9.9.9:443"]
+
+# --- DNS Timing ---
+# Resolvers are queried directly over UDP, rotating through the hostnames
+DNS_RESOLVERS = ["1.1.1.1:53", "8.8.8.8:53"]
+DNS_HOSTNAMES = ["example.com", "wikipedia.org", "debian.org", "rust-lang.org", "python.org"]
+
+# --- NTP Clock Drift ---
+# Queried round-robin, one query every 20s in total
+NTP_SERVERS = ["time.cloudflare.com:123", "time.google.com:123", "pool.ntp.org:123"]
+
+# --- Filesystem Churn ---
+# Directories watched recursively (build trees, /tmp, spool dirs...)
+FS_WATCH_PATHS = []
+
+# --- RTL-SDR (requires building with --features sdr) ---
+# Pick a quiet frequency so the samples are dominated by thermal/atmospheric noise
+SDR_FREQUENCY_HZ = 100_000_000
+
+# --- Serial Noise Source ---
+# e.g. an Arduino avalanche-noise circuit; None disables the harvester
+SERIAL_DEVICE = None      # "/dev/ttyACM0" or "COM3"
+SERIAL_BAUD = 115200
+
+# --- GPIO Noise Source (Raspberry Pi / ARM Linux) ---
+# sysfs GPIO number of the pin wired to an avalanche/zener noise circuit.
+# Newer Pi kernels offset sysfs numbers by the chip base (see /sys/class/gpio/gpiochip*/base).
+GPIO_PIN = None
+
+# --- I2C Environmental Sensors (Linux) ---
+# Probed for BME280/BMP280 (0x76/0x77) and BH1750 (0x23/0x5C)
+I2C_BUS = "/dev/i2c-1"
+
+# --- Mobile Companions ---
+# Phones posting to /mobile_ingest: {device_id: attestation key (hex, >= 16 bytes)}
+MOBILE_DEVICES = {}
+
 # --- Health Check Thresholds (NIST-style) ---
 # If a single byte value repeats this many times, fail.
 RCT_CUTOFF = 10
 # If one byte value appears more than this % of the time in a sample, fail.
 APT_CUTOFF = 0.40 
+# Wi-Fi RSSI drifts slowly, so its source uses looser cutoffs
+WIFI_RCT_CUTOFF = 24
+WIFI_APT_CUTOFF = 0.60
+# Per-source overrides: {"SOURCE": (rct_cutoff, apt_cutoff, apt_window)}. The mixer's APT runs
+# over consecutive windows of apt_window samples (SP 800-90B: 512 for bytes) and fails when the
+# window's first sample fills apt_cutoff of it.
+# Limits: rct_cutoff 2..32, apt_cutoff <= 0.80 (the harvester-side stuck-hardware filter).
+HEALTH_PARAMS = {
+    "MOUSE_MOV": (24, 0.60, 256),   # HID deltas repeat legitimately
+    "TRNG": (6, 0.30, 1024),        # A real TRNG should never come close
+}
+# Rolling chi-square (4096-byte window, 255 dof) alarm thresholds. Only listed sources (and P2P
+# peers, at 330.5) are tested: 330.5 is p = 0.001 for uniform bytes, which raw physical noise is
+# not, so set each threshold from the source's own measured distribution.
+CHI_SQUARE_THRESHOLDS = {}
+# Health event when total credited entropy drops below this many bits/sec (None = off)
+ENTROPY_RATE_ALARM = None
+# Failure mode: minting and output APIs raise EntropyFailureError while credited pool entropy
+# is below this many bits (0 = only when every active source is quarantined)
+ENTROPY_FLOOR_BITS = 0.0
+# Top up the kernel pool via RNDADDENTROPY, claiming this many bits per byte (None = off).
+# Linux only and needs CAP_SYS_ADMIN; useful on entropy-starved VMs.
+KERNEL_FEED_BITS_PER_BYTE = None
+# EGD (entropy gathering daemon) socket for legacy consumers such as OpenSSL's EGD support (None = off).
+# Bytes clients write to it are mixed in but earn no ledger credit unless "EGD" is listed in
+# ENTROPY_CREDITS.
+EGD_SOCKET = None
+# Local randomness socket for non-Python processes (None = off). Root and our own uid may
+# always connect; list any other uids allowed in SOCKET_ALLOWED_UIDS.
+SOCKET_SERVICE_PATH = None  # e.g. "/run/chaosmagnet.sock"
+SOCKET_ALLOWED_UIDS = []
+# Randomness beacon: signed, hash-chained 512-bit pulses (None = off) and an optional HTTP port
+BEACON_INTERVAL_SECS = None
+BEACON_HTTP_PORT = None
 
 # --- Theme: Cobra Lab Stealth ---
 COLOR_BG         = (15, 15, 20, 255)
@@ -32,4 +185,4 @@ COLOR_ACCENT     = (212, 175, 55, 255)
 COLOR_ACCENT_DIM = (150, 120, 30, 150)
 COLOR_PLOT_LINE  = (0, 255, 200, 255)
 COLOR_ERROR      = (200, 50, 50, 255)
-COLOR_WARN       = (200, 150, 50, 255)
\ No newline at end of file
+COLOR_WARN       = (200, 150, 50, 255)
@@ -7,23 +7,126 @@ import dearpygui.dearpygui as dpg
 import json
 import chaos_magnet_core 
 import config
+import os
+import platform
 import time
 
 # --- Init Rust Backend ---
 try:
-    engine = chaos_magnet_core.ChaosEngine() 
+    engine = chaos_magnet_core.ChaosEngine(config.EXTRACTION_INPUT_BYTES, config.EXTRACTION_OUTPUT_BYTES, config.SEED_FILE, config.PQC_SIGNER, config.EXTRACTOR)
     print("DEBUG: Rust Core Loaded & Running.")
 except Exception as e:
     print(f"FATAL: Could not load Rust core: {e}")
     exit(1)
 
+engine.set_adaptive_extraction(config.ADAPTIVE_EXTRACTION)
+engine.set_whitener(config.WHITEN_FOLD_ROUNDS, config.WHITEN_HASH)
+for source, mode in config.DEBIAS_SOURCES.items():
+    if isinstance(mode, tuple):
+        engine.set_source_debias(source, mode[0], mode[1])
+    else:
+        engine.set_source_debias(source, mode)
+for source, route in config.POOL_ROUTES.items():
+    engine.set_source_route(source, route)
+for source, (rct, apt, window) in config.HEALTH_PARAMS.items():
+    engine.set_health_params(source, rct, apt, window)
+for source, threshold in config.CHI_SQUARE_THRESHOLDS.items():
+    engine.set_chi_square_threshold(source, threshold)
+engine.set_entropy_rate_alarm(config.ENTROPY_RATE_ALARM)
+engine.set_entropy_floor(config.ENTROPY_FLOOR_BITS)
+vault_passphrase = os.environ.get(config.VAULT_PASSPHRASE_ENV)
+if vault_passphrase:
+    if os.path.exists(os.path.join(config.KEYS_DIR, "vault.json")):
+        engine.unlock_vault(vault_passphrase)
+    else:
+        engine.set_vault_passphrase(vault_passphrase)
+engine.set_vault_plaintext_export(config.VAULT_ALLOW_PLAINTEXT)
+if config.IDENTITY_FILE and vault_passphrase:
+    engine.enable_identity_persistence(config.IDENTITY_FILE)
+if config.KERNEL_FEED_BITS_PER_BYTE is not None:
+    engine.feed_kernel(config.KERNEL_FEED_BITS_PER_BYTE)
+if config.EGD_SOCKET:
+    engine.start_egd_server(config.EGD_SOCKET)
+if config.SOCKET_SERVICE_PATH:
+    engine.start_socket_service(config.SOCKET_SERVICE_PATH, config.SOCKET_ALLOWED_UIDS)
+if config.BEACON_INTERVAL_SECS:
+    engine.start_beacon(config.BEACON_INTERVAL_SECS, config.BEACON_HTTP_PORT)
+
+
+def on_health_event(source, test, statistic, severity):
+    if severity != "info":
+        print(f"HEALTH {severity.upper()}: {source} {test} ({statistic:.2f})")
+
+
+engine.on_health_event(on_health_event)
+for source, bits_per_byte in config.ENTROPY_CREDITS.items():
+    engine.set_entropy_credit(source, bits_per_byte)
+for consumer, generator in config.OUTPUT_GENERATORS.items():
+    engine.set_consumer_generator(consumer, generator)
+for consumer in config.PREDICTION_RESISTANCE:
+    engine.set_prediction_resistance(consumer, True)
+for consumer, (per_sec, per_reseed) in config.OUTPUT_QUOTAS.items():
+    engine.set_consumer_quota(consumer, per_sec, per_reseed)
+engine.set_reseed_policy(config.RESEED_MAX_AGE_SECS, config.RESEED_MAX_OUTPUT_BYTES, config.RESEED_MIN_CREDIT_BITS)
+engine.set_hwrng_device(config.HWRNG_DEVICE)
+engine.set_ping_targets(config.PING_TARGETS)
+engine.set_dns_config(config.DNS_RESOLVERS, config.DNS_HOSTNAMES)
+engine.set_sdr_frequency(config.SDR_FREQUENCY_HZ)
+engine.set_ntp_servers(config.NTP_SERVERS)
+engine.set_fs_watch_paths(config.FS_WATCH_PATHS)
+if config.SERIAL_DEVICE:
+    engine.set_serial_device(config.SERIAL_DEVICE, config.SERIAL_BAUD)
+if config.GPIO_PIN is not None:
+    engine.set_gpio_pin(config.GPIO_PIN)
+engine.set_i2c_bus(config.I2C_BUS)
+engine.set_audio_mode(config.AUDIO_MODE)
+for device_id, key_hex in config.MOBILE_DEVICES.items():
+    engine.register_mobile_device(device_id, key_hex)
+if config.AUDIO_DEVICES:
+    engine.set_audio_device(config.AUDIO_DEVICES[0])
+    for dev in config.AUDIO_DEVICES[1:]:
+        engine.add_audio_device(dev)
+engine.set_video_roi(config.VIDEO_ROI)
+engine.set_video_dark_mode(config.VIDEO_DARK_MODE)
+if config.VIDEO_DEVICES:
+    engine.set_video_device(config.VIDEO_DEVICES[0])
+    for cam in config.VIDEO_DEVICES[1:]:
+        engine.add_video_device(cam)
+
 # --- Harvester Availability Detection ---
+# Feature-gated harvesters are no-op stubs unless the core was built with that Cargo feature
+compiled_features = set(engine.compiled_features())
 harvester_info = {
     "System/CPU": {"rust_name": "SYSTEM", "available": True},
     "Hardware/TRNG": {"rust_name": "TRNG", "available": True},
     "Audio (Mic)": {"rust_name": "AUDIO", "available": True},
     "Video (Cam)": {"rust_name": "VIDEO", "available": True},
     "HID (Mouse)": {"rust_name": "MOUSE", "available": True},
+    "Network (Jitter)": {"rust_name": "NET_JITTER", "available": True},
+    "GPU (Telemetry)": {"rust_name": "GPU", "available": True},
+    "Thermal/Fans": {"rust_name": "THERMAL", "available": True},
+    "Bluetooth (BLE)": {"rust_name": "BT", "available": "bluetooth" in compiled_features},
+    "Wi-Fi (RSSI)": {"rust_name": "WIFI", "available": True},
+    "Hardware/HWRNG": {"rust_name": "HWRNG", "available": os.path.exists(config.HWRNG_DEVICE)},
+    "CPU (RDSEED)": {"rust_name": "CPU_RNG", "available": platform.machine().lower() in ("x86_64", "amd64")},
+    "Network (Ping)": {"rust_name": "PING", "available": True},
+    "Network (DNS)": {"rust_name": "DNS", "available": True},
+    "Kernel (IRQ)": {"rust_name": "IRQ", "available": os.path.exists("/proc/interrupts")},
+    "Battery/Power": {"rust_name": "POWER", "available": os.path.exists("/sys/class/power_supply")},
+    "Motion (IMU)": {"rust_name": "IMU", "available": os.path.exists("/sys/bus/iio/devices")},
+    "Radio (SDR)": {"rust_name": "SDR", "available": "sdr" in compiled_features},
+    "Serial (DIY TRNG)": {"rust_name": "SERIAL", "available": bool(config.SERIAL_DEVICE)},
+    "GPIO (Avalanche)": {"rust_name": "GPIO", "available": config.GPIO_PIN is not None and os.path.exists("/sys/class/gpio")},
+    "Security Key (Token)": {"rust_name": "TOKEN", "available": "token" in compiled_features},
+    "Environment (I2C)": {"rust_name": "ENV", "available": os.path.exists(config.I2C_BUS)},
+    "Scheduler Jitter": {"rust_name": "SCHED", "available": True},
+    "Memory Timing": {"rust_name": "MEM", "available": True},
+    "Screen (Capture)": {"rust_name": "SCREEN", "available": "screen" in compiled_features},
+    "Clock Drift (NTP)": {"rust_name": "CLOCK", "available": True},
+    "Filesystem Churn": {"rust_name": "FS", "available": bool(config.FS_WATCH_PATHS)},
+    "USB (Hot-plug)": {"rust_name": "USB", "available": "usb" in compiled_features},
+    "Gamepad (HID)": {"rust_name": "GAMEPAD", "available": "gamepad" in compiled_features},
+    "Mains Hum (Audio)": {"rust_name": "MAINS", "available": True},
 }
 
 # --- GUI Update Throttling ---
@@ -175,8 +278,8 @@ def update_gui():
         dpg.set_value("txt_source_breakdown", breakdown_text)
         
         # 6. Pool State Display
-        pool_hex = metrics.get('pool_hex', '')
-        dpg.set_value("txt_pool", pool_hex)
+        pool_view = metrics.get('pool_view', '')
+        dpg.set_value("txt_pool", pool_view)
         
         # 7. Update Logs (last 15 lines)
         logs = metrics.get('logs', [])
@@ -1,37 +1,338 @@
 use pyo3::prelude::*;
+use pyo3::create_exception;
 use std::sync::{Arc, atomic::{AtomicBool, AtomicUsize, Ordering}};
 use parking_lot::Mutex;
 use crossbeam_channel::{bounded, Sender, Receiver};
 use std::thread;
 use std::time::{SystemTime, UNIX_EPOCH, Duration, Instant};
 use std::fs;
-use std::collections::{VecDeque, HashMap};
-use sha2::{Sha256, Digest as Sha2Digest};
+use std::collections::{VecDeque, HashMap, HashSet};
+use sha2::{Sha256, Sha512, Digest as Sha2Digest};
 use sha3::Sha3_256;
-use pqcrypto_kyber::kyber512;
-use pqcrypto_falcon::falcon512;
+use hmac::{Hmac, Mac};
+use zeroize::Zeroize;
+use pqcrypto_kyber::{kyber512, kyber768, kyber1024};
+use pqcrypto_falcon::{falcon512, falcon1024};
+use pqcrypto_dilithium::{dilithium2, dilithium3, dilithium5};
+use pqcrypto_sphincsplus::{sphincssha2128ssimple, sphincssha2192ssimple, sphincssha2256ssimple};
 use pqcrypto_traits::sign::{PublicKey as SignPublicKey, SecretKey as SignSecretKey, DetachedSignature};
-use pqcrypto_traits::kem::{PublicKey as KemPublicKey, SecretKey as KemSecretKey};
+use pqcrypto_traits::kem::{PublicKey as KemPublicKey, SecretKey as KemSecretKey, Ciphertext as KemCiphertext, SharedSecret as KemSharedSecret};
+use x25519_dalek::{StaticSecret as X25519Secret, PublicKey as X25519Public};
+use ed25519_dalek::SigningKey as Ed25519SigningKey;
+use p256::SecretKey as P256SecretKey;
 use rand::prelude::*;
 
 // ═══════════════════════════════════════════════════════════════════════════
 // CONFIGURATION
 // ═══════════════════════════════════════════════════════════════════════════
 
-const EXTRACTION_POOL_SIZE: usize = 200;  // Raw bytes before extraction
+const EXTRACTION_POOL_SIZE: usize = 200;  // Default raw bytes before extraction
+const EXTRACTION_OUTPUT_SIZE: usize = 32;  // Default bytes per extraction
+const ADAPTIVE_MAX_INPUT_FACTOR: usize = 4;  // Adaptive pools may take up to 4x the configured input
+const ADAPTIVE_SECURITY_MARGIN_BITS: f64 = 64.0;  // SP 800-90B full entropy: h_in >= n_out + 64
+const FORTUNA_POOLS: usize = 32;
+const SLOW_POOL_RESEED_BITS: f64 = 2048.0;  // Assessed input the slow pool gathers per reseed (8x its state)
+const FORTUNA_RESEED_INTERVAL_MS: u64 = 100;  // Minimum spacing between reseeds
+const FORTUNA_MIN_POOL_BYTES: usize = 64;     // Conditioned bytes in pool 0 before a reseed
+const CTR_DRBG_SEED_LEN: usize = 48;          // AES-256 key + one block
+const DRBG_MAX_REQUEST: usize = 1 << 16;      // 800-90A max_number_of_bits_per_request = 2^19
+const DRBG_RESEED_INTERVAL: u64 = 1 << 20;    // Generate calls before a reseed is mandatory
+const DRBG_RESEED_SECS: u64 = 30;             // Default reseed policy: periodic reseed from the main pool
 const POOL_SIZE: usize = 1024;
 const HISTORY_LEN: usize = 300;
-const RCT_CUTOFF: usize = 10;
+const RCT_CUTOFF: usize = 10;     // Default per-source cutoffs, applied in the mixer
 const APT_CUTOFF: f64 = 0.40;
+const APT_WINDOW: usize = 512;    // SP 800-90B APT window for non-binary sources
+const CHI_SQUARE_WINDOW: usize = 4096;       // 16 expected hits per byte value
+const CHI_SQUARE_THRESHOLD: f64 = 330.5;     // 255 degrees of freedom, p = 0.001; uniform input only
+const BATTERY_HISTORY_BYTES: usize = 64 * 1024;  // Raw bytes kept per source for run_health_battery
+const FIPS_BLOCK_BYTES: usize = 2500;            // FIPS 140-2 tests are defined over 20,000 bits
+const ESTIMATOR_WINDOW: usize = 4096;   // Non-IID estimators run over this much recent history
+const ESTIMATOR_INTERVAL: usize = 4096; // ...once per this many new bytes
+const LAG_PREDICTOR_DEPTH: usize = 128; // SP 800-90B 6.3.8: D = 128
+const IID_PERMUTATIONS: usize = 10_000;        // SP 800-90B 5.1
+const IID_REJECT_RANK: usize = 5;               // Reject when the original ranks in the outer 0.05%
+const COMPRESSION_INTERVAL_SECS: u64 = 60;     // Background compression estimate cadence
+const COMPRESSION_MIN_BYTES: usize = 8 * 1024; // Dictionary warm-up needs well over 1000 blocks
+const COMPRESSION_BLOCK_BITS: usize = 6;        // SP 800-90B 6.3.4: b = 6, d = 1000
+const COMPRESSION_DICTIONARY_BLOCKS: usize = 1000;
+const GROSS_RCT_CUTOFF: usize = 32;   // Harvester-side filter for stuck hardware
+const GROSS_APT_CUTOFF: f64 = 0.80;
 const AUTO_MINT_THRESHOLD: f64 = 6.5;  // Min-entropy threshold
+const WIFI_RCT_CUTOFF: usize = 24;     // RSSI legitimately repeats between polls
+const WIFI_APT_CUTOFF: f64 = 0.60;
+const STARTUP_TEST_SAMPLES: usize = 1024;  // SP 800-90B 4.3: startup tests cover at least 1024 samples
+const STARTUP_MAX_FAILURES: u32 = 5;       // Startup attempts before the source is disabled
+const HEALTH_SUSPECT_AFTER: u32 = 3;       // Consecutive continuous-test failures
+const HEALTH_DISABLE_AFTER: u32 = 16;
+const HEALTH_RECOVER_AFTER: u32 = 64;      // Consecutive passes to clear SUSPECT
+const RETEST_BASE_SECS: u64 = 30;          // First retest of a DISABLED source; doubles per attempt
+const RETEST_MAX_SECS: u64 = 3600;
+const HEALTH_EVENT_QUEUE: usize = 256;     // Undelivered events kept for callbacks
+const ENTROPY_RATE_WINDOW_SECS: u64 = 10;  // Credited-bits rate measured over this window
+const ACTIVE_SOURCE_SECS: u64 = 30;        // A source counts as enabled if it reported this recently
+const MEM_CHASE_ENTRIES: usize = 4 * 1024 * 1024;  // 32 MB of indices, well past LLC
+const ULTRASONIC_CUTOFF_HZ: f32 = 18_000.0;
+const MAINS_BLOCK_MS: u32 = 100;           // Phase measured once per block
+const MAINS_MIN_AMPLITUDE: f64 = 1e-4;     // Below this there is no hum to lock onto
+const PING_MIN_INTERVAL_MS: u64 = 250;      // Global probe rate limit
+const PING_PER_HOST_INTERVAL_MS: u64 = 2000; // Never probe one host faster than this
+const NTP_QUERY_INTERVAL_SECS: u64 = 20;      // Round-robin, so each server sees far less
+const NTP_UNIX_OFFSET_SECS: u64 = 2_208_988_800;
+const SELFTEST_AVALANCHE_TRIALS: usize = 256;
+const SELFTEST_AVALANCHE_TOLERANCE: f64 = 0.05;  // Mean flip fraction must land in 0.45..=0.55
+const SEED_FILE_MAGIC: &[u8; 8] = b"CMSEED01";
+const SEED_FILE_LEN: usize = 8 + 16 + 32 + 32;  // magic | nonce | encrypted seed | HMAC tag
+const ENTROPY_BLOCK_MAGIC: &[u8; 8] = b"CMBLOCK1";
+const ENTROPY_BLOCK_HEADER_LEN: usize = 8 + 8 + 8 + 4;  // magic | sequence | timestamp | length
+const VAULT_META_PATH: &str = "keys/vault.json";
+const VAULT_CHECK: &[u8] = b"CHAOS_MAGNET_VAULT_CHECK";
+const VAULT_ARGON2_M_COST_KIB: u32 = 64 * 1024;
+const VAULT_ARGON2_T_COST: u32 = 3;
+const VAULT_ARGON2_P_COST: u32 = 1;
+const IDENTITY_ROTATION_LOG: &str = "keys/identity_rotations.jsonl";
+const VAULT_DIR: &str = "keys";
+const VAULT_BUNDLE_TYPES: &[&str] = &["COBRA_PQC_BUNDLE", "COBRA_PQC_SIG_BUNDLE", "COBRA_HYBRID_BUNDLE", "COBRA_CLASSICAL_BUNDLE"];
+const VAULT_SECRET_FIELDS: &[&str] = &["kyber_sk", "secret_key", "x25519_sk", "ed25519_sk", "signer_sk"];
+const MOBILE_MAX_FAILURES: u32 = 5;  // Consecutive health failures before a phone is quarantined
+const P2P_MAX_FAILURES: u32 = 3;     // Consecutive health failures before a peer is banned
+const P2P_RCT_CUTOFF: usize = 4;     // SP 800-90B cutoffs for a full-entropy claim (H = 8, alpha = 2^-20)
+const P2P_APT_CUTOFF: f64 = 0.025;   // 13 of 512
+const P2P_DEFAULT_BITS_PER_BYTE: f64 = 0.1;  // Remote bytes can't be verified; credit them sparingly
+const LEDGER_CAPACITY_BITS: f64 = 256.0;       // The 32-byte pool can't hold more than this
+const LEDGER_DEFAULT_BITS_PER_BYTE: f64 = 0.5; // Credit for sources without a configured rate
+const EGD_DEFAULT_BITS_PER_BYTE: f64 = 0.0;    // Any local client can write to the EGD socket; mixed, not credited
+const LEDGER_ASSESSMENT_FRACTION: f64 = 0.5;   // Credit never exceeds this share of measured min-entropy
+const LEDGER_MINT_DEBIT_BITS: f64 = 256.0;     // A mint consumes the whole pool state
+const KERNEL_FEED_INTERVAL_SECS: u64 = 5;
+const KERNEL_FEED_BYTES: usize = 64;           // Per RNDADDENTROPY write
+const EGD_MAX_CLIENTS: usize = 64;             // Disconnected clients are pruned past this
+const FIFO_CHUNK_BYTES: usize = 4096;          // Largest single write to a FIFO
+const SOCKET_MAX_REQUEST: u32 = 1 << 20;       // Bytes per socket service request
+const FILE_CHUNK_BYTES: usize = 1 << 20;       // File output is generated and written per MiB
+const BEACON_MAX_PULSES: usize = 1440;         // A day of pulses at the default 60 s interval
+const SHUFFLE_GIL_RELEASE_LEN: usize = 4096;   // Larger shuffles/samples draw with the GIL released
+
+type HmacSha256 = Hmac<Sha256>;
 
 // ═══════════════════════════════════════════════════════════════════════════
 // DATA STRUCTURES
 // ═══════════════════════════════════════════════════════════════════════════
 
+// Optional per-source pre-stage for biased bit-level sources
+#[derive(Clone, Copy, PartialEq)]
+enum Debias {
+    VonNeumann,
+    XorFold(u8),  // Rounds
+}
+
+impl Debias {
+    fn apply(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Debias::VonNeumann => von_neumann_debias(data),
+            Debias::XorFold(rounds) => xor_fold(data, *rounds),
+        }
+    }
+}
+
+// Conditioning function applied when the extraction pool fills
+#[derive(Clone, Copy, PartialEq)]
+enum Extractor {
+    Sha256,
+    Sha512,
+    Sha3_256,
+    Blake3,
+    Shake256,
+    HmacSha256,  // Keyed by a secret derived from the main pool (SP 800-90B vetted conditioning)
+    Toeplitz,    // Information-theoretic; seed expanded from the pool-derived key
+}
+
+impl Extractor {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().replace(['-', '_'], "").as_str() {
+            "sha256" => Some(Extractor::Sha256),
+            "sha512" => Some(Extractor::Sha512),
+            "sha3256" | "sha3" => Some(Extractor::Sha3_256),
+            "sp80090b" | "vetted" => Some(Extractor::HmacSha256),
+            "blake3" => Some(Extractor::Blake3),
+            "shake256" | "shake" => Some(Extractor::Shake256),
+            "hmacsha256" | "hmac" => Some(Extractor::HmacSha256),
+            "toeplitz" => Some(Extractor::Toeplitz),
+            _ => None,
+        }
+    }
+    
+    fn name(&self) -> &'static str {
+        match self {
+            Extractor::Sha256 => "SHA-256",
+            Extractor::Sha512 => "SHA-512",
+            Extractor::Sha3_256 => "SHA3-256",
+            Extractor::Blake3 => "BLAKE3",
+            Extractor::Shake256 => "SHAKE256",
+            Extractor::HmacSha256 => "HMAC-SHA256 (keyed)",
+            Extractor::Toeplitz => "TOEPLITZ",
+        }
+    }
+    
+    /// Narrowest internal width (bits) for SP 800-90B vetted components; None when not vetted
+    fn vetted_width(&self) -> Option<f64> {
+        match self {
+            Extractor::Sha256 | Extractor::Sha3_256 | Extractor::HmacSha256 => Some(256.0),
+            Extractor::Sha512 => Some(512.0),
+            Extractor::Blake3 | Extractor::Shake256 | Extractor::Toeplitz => None,
+        }
+    }
+    
+    /// Digest width in bytes; None for XOF-style extractors that produce any requested length
+    fn output_width(&self) -> Option<usize> {
+        match self {
+            Extractor::Sha256 | Extractor::Sha3_256 | Extractor::Blake3 | Extractor::HmacSha256 => Some(32),
+            Extractor::Sha512 => Some(64),
+            Extractor::Shake256 | Extractor::Toeplitz => None,
+        }
+    }
+    
+    /// `key` is only used by keyed conditioners; fixed-width digests ignore `out_len`
+    fn condense(&self, input: &[u8], counter: u64, key: &[u8; 32], out_len: usize) -> Vec<u8> {
+        match self {
+            Extractor::Sha256 => {
+                let mut hasher = Sha256::new();
+                hasher.update(input);
+                hasher.update(counter.to_le_bytes());
+                hasher.finalize().to_vec()
+            }
+            Extractor::Sha512 => {
+                let mut hasher = Sha512::new();
+                hasher.update(input);
+                hasher.update(counter.to_le_bytes());
+                hasher.finalize().to_vec()
+            }
+            Extractor::Sha3_256 => {
+                let mut hasher = Sha3_256::new();
+                hasher.update(input);
+                hasher.update(counter.to_le_bytes());
+                hasher.finalize().to_vec()
+            }
+            Extractor::Blake3 => {
+                let mut hasher = blake3::Hasher::new();
+                hasher.update(input);
+                hasher.update(&counter.to_le_bytes());
+                hasher.finalize().as_bytes().to_vec()
+            }
+            Extractor::Shake256 => {
+                use sha3::digest::{ExtendableOutput, Update, XofReader};
+                let mut hasher = sha3::Shake256::default();
+                hasher.update(input);
+                hasher.update(&counter.to_le_bytes());
+                let mut out = vec![0u8; out_len];
+                hasher.finalize_xof().read(&mut out);
+                out
+            }
+            Extractor::HmacSha256 => {
+                let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts any key length");
+                mac.update(input);
+                mac.update(&counter.to_le_bytes());
+                mac.finalize().into_bytes().to_vec()
+            }
+            Extractor::Toeplitz => {
+                use sha3::digest::{ExtendableOutput, Update, XofReader};
+                let mut xof = sha3::Shake256::default();
+                xof.update(b"CHAOS_MAGNET_TOEPLITZ_SEED");
+                xof.update(key);
+                let mut seed = vec![0u8; input.len() + out_len];
+                xof.finalize_xof().read(&mut seed);
+                toeplitz_hash(input, &seed, out_len)
+            }
+        }
+    }
+}
+
+// Active conditioning function and its parameters, shared by every pool
+#[derive(Clone)]
+struct Conditioner {
+    extractor: Extractor,
+    key: [u8; 32],  // HMAC key / Toeplitz seed, re-derived from the main pool after every mix
+    input_bytes: usize,   // Raw bytes accumulated per extraction
+    output_bytes: usize,  // Fixed-width digests are truncated to this, never extended
+    adaptive: bool,       // Extract on assessed entropy rather than a fixed byte count
+}
+
+impl Conditioner {
+    fn new(input_bytes: usize, output_bytes: usize) -> Self {
+        Self {
+            extractor: Extractor::Sha256,
+            key: [0u8; 32],
+            input_bytes,
+            output_bytes,
+            adaptive: false,
+        }
+    }
+    
+    /// Output must stay at most half the input so every extraction still compresses, and within
+    /// the extractor's digest width since truncation never extends a fixed-width digest
+    fn validate_params(extractor: Extractor, input_bytes: usize, output_bytes: usize) -> Result<(), String> {
+        if !(32..=65536).contains(&input_bytes) {
+            return Err(format!("Extraction input must be 32..=65536 bytes, got {}", input_bytes));
+        }
+        if !(8..=input_bytes / 2).contains(&output_bytes) {
+            return Err(format!("Extraction output must be 8..={} bytes, got {}", input_bytes / 2, output_bytes));
+        }
+        if let Some(width) = extractor.output_width() {
+            if output_bytes > width {
+                return Err(format!("{} produces at most {} bytes per extraction, got {}", extractor.name(), width, output_bytes));
+            }
+        }
+        Ok(())
+    }
+    
+    /// Largest raw input a pool may gather before it must extract
+    fn max_input(&self) -> usize {
+        if self.adaptive {
+            (self.input_bytes * ADAPTIVE_MAX_INPUT_FACTOR).min(65536)
+        } else {
+            self.input_bytes
+        }
+    }
+    
+    /// Fixed mode extracts at `input_bytes`. Adaptive mode extracts as soon as the buffered
+    /// assessment covers the output plus the 90B margin, so a strong TRNG needs little raw
+    /// input while weak sources accumulate up to `max_input`.
+    fn ready(&self, buffered: usize, h_in: f64) -> bool {
+        if !self.adaptive {
+            return buffered >= self.input_bytes;
+        }
+        let target_bits = self.output_bytes as f64 * 8.0 + ADAPTIVE_SECURITY_MARGIN_BITS;
+        buffered >= self.max_input() || (buffered >= 2 * self.output_bytes && h_in >= target_bits)
+    }
+    
+    fn condense(&self, input: &[u8], counter: u64) -> Vec<u8> {
+        let mut out = self.extractor.condense(input, counter, &self.key, self.output_bytes);
+        out.truncate(self.output_bytes);
+        out
+    }
+    
+    /// Claimed h_out for `out_len` bytes conditioned from `in_len` bytes carrying `h_in` bits
+    fn claim(&self, in_len: usize, h_in: f64, out_len: usize) -> Option<f64> {
+        let nw = self.extractor.vetted_width()?;
+        Some(output_entropy(in_len as f64 * 8.0, out_len as f64 * 8.0, nw, h_in))
+    }
+    
+    fn ratio_label(&self) -> String {
+        if self.adaptive {
+            format!("{}..{}→{}", 2 * self.output_bytes, self.max_input(), self.output_bytes)
+        } else {
+            format!("{}→{}", self.input_bytes, self.output_bytes)
+        }
+    }
+}
+
 #[derive(Clone)]
 struct EntropyExtractionPool {
     buffer: Vec<u8>,
+    pending_entropy_bits: f64,     // Assessed h_in of what's buffered
+    last_claim_bits: Option<f64>,  // h_out of the last extraction (vetted conditioners only)
+    raw_since_extract: usize,      // Raw bytes behind the current buffer (it may have been condensed)
+    last_input_len: usize,         // Raw bytes behind the last extraction
     extractions_count: u64,
     last_extraction: f64,
     total_raw_consumed: usize,      // NEW: Track total raw bytes
@@ -41,7 +342,11 @@ struct EntropyExtractionPool {
 impl EntropyExtractionPool {
     fn new() -> Self {
         Self {
-            buffer: Vec::with_capacity(EXTRACTION_POOL_SIZE),
+            buffer: Vec::new(),
+            pending_entropy_bits: 0.0,
+            last_claim_bits: None,
+            raw_since_extract: 0,
+            last_input_len: 0,
             extractions_count: 0,
             last_extraction: 0.0,
             total_raw_consumed: 0,
@@ -49,35 +354,52 @@ impl EntropyExtractionPool {
         }
     }
     
-    fn add_raw_bytes(&mut self, raw_data: &[u8]) -> Option<Vec<u8>> {
+    /// Buffers without extracting; past 2x the pool size the buffer is condensed in place
+    /// (digest carried forward) so rarely drained pools stay bounded
+    fn absorb(&mut self, raw_data: &[u8], entropy_bits: f64, conditioner: &Conditioner) {
         self.buffer.extend_from_slice(raw_data);
+        self.pending_entropy_bits += entropy_bits;
+        self.total_raw_consumed += raw_data.len();
+        self.raw_since_extract += raw_data.len();
+        
+        if self.buffer.len() >= 2 * conditioner.max_input() {
+            let in_len = self.buffer.len();
+            self.buffer = conditioner.condense(&self.buffer, self.extractions_count);
+            self.pending_entropy_bits = conditioner
+                .claim(in_len, self.pending_entropy_bits, self.buffer.len())
+                .unwrap_or(0.0);
+        }
+    }
+    
+    fn add_raw_bytes(&mut self, raw_data: &[u8], entropy_bits: f64, conditioner: &Conditioner) -> Option<Vec<u8>> {
+        self.absorb(raw_data, entropy_bits, conditioner);
         
-        if self.buffer.len() >= EXTRACTION_POOL_SIZE {
-            Some(self.extract())
+        if conditioner.ready(self.buffer.len(), self.pending_entropy_bits) {
+            Some(self.extract(conditioner))
         } else {
             None
         }
     }
     
-    fn extract(&mut self) -> Vec<u8> {
-        let mut hasher = Sha256::new();
-        hasher.update(&self.buffer);
-        hasher.update(&self.extractions_count.to_le_bytes());
-        let result = hasher.finalize();
+    fn extract(&mut self, conditioner: &Conditioner) -> Vec<u8> {
+        let result = conditioner.condense(&self.buffer, self.extractions_count);
+        self.last_claim_bits = conditioner.claim(self.buffer.len(), self.pending_entropy_bits, result.len());
         
-        // NEW: Track raw vs extracted
-        self.total_raw_consumed += self.buffer.len();
-        self.total_extracted_bytes += 32;  // SHA-256 always outputs 32 bytes
+        // NEW: Track extracted (raw is counted as it arrives)
+        self.total_extracted_bytes += result.len();
         
+        self.last_input_len = self.raw_since_extract;
+        self.raw_since_extract = 0;
         self.buffer.clear();
+        self.pending_entropy_bits = 0.0;
         self.extractions_count += 1;
         self.last_extraction = get_timestamp() as f64;
         
-        result.to_vec()
+        result
     }
     
-    fn fill_percentage(&self) -> f64 {
-        (self.buffer.len() as f64 / EXTRACTION_POOL_SIZE as f64) * 100.0
+    fn fill_percentage(&self, capacity: usize) -> f64 {
+        (self.buffer.len() as f64 / capacity.max(1) as f64) * 100.0
     }
     
     fn accumulated_bytes(&self) -> usize {
@@ -85,855 +407,9381 @@ impl EntropyExtractionPool {
     }
 }
 
-#[derive(Clone, Default)]
-struct SourceMetrics {
-    raw_shannon: f64,
-    min_entropy: f64,
-    samples: u64,
-    avg_raw_entropy: f64,
-    total_bits_contributed: f64,
-}
-
-// NEW: P2P Configuration
-#[derive(Clone)]
-struct P2PConfig {
-    active: bool,
-    listen_port: u16,
-    peers: Vec<String>,  // List of "IP:PORT" strings
-    received_count: u64,
+// Two stages. Each source first fills its own extraction pool, so a flood of low-entropy
+// samples from one source can't crowd out another's bytes before conditioning. Extracted
+// outputs then rotate through 32 Fortuna pools, and reseed r drains pool i only when 2^i
+// divides r. Deep pools gather entropy for exponentially longer, so even if an attacker
+// learns the state, some later reseed outpaces what they can track.
+struct FortunaAccumulator {
+    conditioner: Conditioner,
+    source_pools: HashMap<String, EntropyExtractionPool>,
+    pools: Vec<EntropyExtractionPool>,
+    next_pool: HashMap<String, usize>,  // Per-source round-robin position
+    reseed_count: u64,
+    last_reseed: Instant,
+    last_claim_bits: Option<f64>,  // SP 800-90B h_out of the last reseed, if every stage was vetted
+    total_claimed_bits: f64,
+    xof_extractions: u64,
+    xof_extracted_bytes: usize,
+    raw_since_reseed: usize,        // Raw input behind the source extractions feeding this reseed
+    extracted_since_reseed: usize,
+    last_effective_ratio: f64,      // Raw:conditioned bytes over the last reseed interval
 }
 
-impl Default for P2PConfig {
-    fn default() -> Self {
+impl FortunaAccumulator {
+    fn new(conditioner: Conditioner) -> Self {
         Self {
-            active: false,
-            listen_port: 9000,
-            peers: Vec::new(),
-            received_count: 0,
+            conditioner,
+            source_pools: HashMap::new(),
+            pools: (0..FORTUNA_POOLS).map(|_| EntropyExtractionPool::new()).collect(),
+            next_pool: HashMap::new(),
+            reseed_count: 0,
+            last_reseed: Instant::now(),
+            last_claim_bits: None,
+            total_claimed_bits: 0.0,
+            xof_extractions: 0,
+            xof_extracted_bytes: 0,
+            raw_since_reseed: 0,
+            extracted_since_reseed: 0,
+            last_effective_ratio: 0.0,
         }
     }
+    
+    /// `entropy_bits` is the assessed h_in of `raw_data`. Returns reseed output once pool 0
+    /// holds enough conditioned input.
+    fn add_raw_bytes(&mut self, source: &str, raw_data: &[u8], entropy_bits: f64) -> Option<Vec<u8>> {
+        let source_pool = self.source_pools.entry(source.to_string())
+            .or_insert_with(EntropyExtractionPool::new);
+        let extracted = source_pool.add_raw_bytes(raw_data, entropy_bits, &self.conditioner)?;
+        let credit = source_pool.last_claim_bits.unwrap_or(0.0);
+        self.raw_since_reseed += source_pool.last_input_len;
+        self.extracted_since_reseed += extracted.len();
+        
+        let slot = self.next_pool.entry(source.to_string()).or_insert(0);
+        let index = *slot;
+        *slot = (index + 1) % FORTUNA_POOLS;
+        self.pools[index].absorb(&extracted, credit, &self.conditioner);
+        
+        if self.pools[0].accumulated_bytes() >= self.reseed_threshold()
+            && self.last_reseed.elapsed() >= Duration::from_millis(FORTUNA_RESEED_INTERVAL_MS)
+        {
+            Some(self.reseed())
+        } else {
+            None
+        }
+    }
+    
+    fn reseed(&mut self) -> Vec<u8> {
+        self.reseed_count += 1;
+        self.last_reseed = Instant::now();
+        self.last_effective_ratio = self.raw_since_reseed as f64 / self.extracted_since_reseed.max(1) as f64;
+        self.raw_since_reseed = 0;
+        self.extracted_since_reseed = 0;
+        
+        let mut output = Vec::new();
+        let mut claim = Some(0.0);
+        for (i, pool) in self.pools.iter_mut().enumerate() {
+            if self.reseed_count % (1u64 << i) != 0 { break; }
+            output.extend(pool.extract(&self.conditioner));
+            claim = claim.zip(pool.last_claim_bits).map(|(total, bits)| total + bits);
+        }
+        
+        self.last_claim_bits = claim;
+        self.total_claimed_bits += claim.unwrap_or(0.0);
+        output
+    }
+    
+    /// One SHAKE256 extraction of `out_len` bytes over every source pool's pending raw input,
+    /// without waiting for the pools' own thresholds. Returns the output and the assessed input
+    /// entropy it was drawn from; the output is reseed material, never handed out directly.
+    fn extract_xof(&mut self, out_len: usize) -> Result<(Vec<u8>, f64), String> {
+        use sha3::digest::{ExtendableOutput, Update, XofReader};
+        
+        let buffered: usize = self.source_pools.values().map(|p| p.accumulated_bytes()).sum();
+        if buffered < 2 * out_len {
+            return Err(format!("{} raw bytes buffered, need {} for a {}-byte extraction", buffered, 2 * out_len, out_len));
+        }
+        
+        let mut xof = sha3::Shake256::default();
+        xof.update(b"CHAOS_MAGNET_XOF_EXTRACT");
+        xof.update(&self.conditioner.key);
+        xof.update(&self.xof_extractions.to_le_bytes());
+        
+        // Sorted so the absorb order doesn't depend on HashMap iteration
+        let mut names: Vec<String> = self.source_pools.keys().cloned().collect();
+        names.sort();
+        let mut h_in = 0.0;
+        for name in names {
+            let pool = self.source_pools.get_mut(&name).expect("key from source_pools");
+            if pool.buffer.is_empty() { continue; }
+            xof.update(name.as_bytes());
+            xof.update(&(pool.buffer.len() as u64).to_le_bytes());
+            xof.update(&pool.buffer);
+            h_in += pool.pending_entropy_bits;
+            pool.buffer.clear();
+            pool.pending_entropy_bits = 0.0;
+            pool.raw_since_extract = 0;
+            pool.last_extraction = get_timestamp() as f64;
+        }
+        
+        let mut out = vec![0u8; out_len];
+        xof.finalize_xof().read(&mut out);
+        self.xof_extractions += 1;
+        self.xof_extracted_bytes += out_len;
+        Ok((out, h_in))
+    }
+    
+    /// Pool 0 must at least double the output width so reseeds still compress
+    fn reseed_threshold(&self) -> usize {
+        FORTUNA_MIN_POOL_BYTES.max(2 * self.conditioner.output_bytes)
+    }
+    
+    /// Number of pools the most recent reseed drew from
+    fn last_reseed_depth(&self) -> u32 {
+        (self.reseed_count.trailing_zeros() + 1).min(FORTUNA_POOLS as u32)
+    }
+    
+    fn total_raw_consumed(&self) -> usize {
+        self.source_pools.values().map(|p| p.total_raw_consumed).sum()
+    }
+    
+    fn total_extracted_bytes(&self) -> usize {
+        self.source_pools.values().map(|p| p.total_extracted_bytes).sum::<usize>() + self.xof_extracted_bytes
+    }
+    
+    fn accumulated_bytes(&self) -> usize {
+        self.source_pools.values().chain(self.pools.iter()).map(|p| p.accumulated_bytes()).sum()
+    }
+    
+    fn pool_fill(&self, index: usize) -> f64 {
+        self.pools[index].fill_percentage(self.reseed_threshold())
+    }
+    
+    fn source_pool_fill(&self) -> HashMap<String, f64> {
+        self.source_pools.iter()
+            .map(|(name, p)| (name.clone(), p.fill_percentage(self.conditioner.max_input())))
+            .collect()
+    }
 }
 
-struct SharedState {
-    extraction_pool: EntropyExtractionPool,
-    pool: [u8; 32],
-    display_pool: VecDeque<u8>,
-    history_raw_entropy: VecDeque<f64>,
-    history_whitened_entropy: VecDeque<f64>,
-    source_metrics: HashMap<String, SourceMetrics>,
-    estimated_true_entropy_bits: f64,
-    logs: VecDeque<String>,
-    total_bytes: usize,
-    sequence_id: u64,
-    net_mode: bool,
-    uplink_url: String,
-    falcon_pk: Vec<u8>,
-    falcon_sk: Vec<u8>,
-    pqc_active: bool,
-    harvester_states: HarvesterStates,
-    p2p_config: P2PConfig,  // NEW
-}
-
-#[derive(Clone)]
-struct HarvesterStates {
-    trng: bool,
-    audio: bool,
-    system: bool,
-    mouse: bool,
-    video: bool,
+// Stage 1 of the pipeline: per-sample whitening ahead of pooling. An optional XOR-fold, then
+// SHA-256 over the sample once it is at least twice the digest width. Stateless across
+// samples, so any sample can be whitened in isolation.
+struct Whitener {
+    fold_rounds: u8,  // 0 = no folding
+    hash: bool,
+    samples: u64,
+    hashed_samples: u64,
+    bytes_in: u64,
+    bytes_out: u64,
 }
 
-impl Default for HarvesterStates {
-    fn default() -> Self {
-        Self {
-            trng: false,
-            audio: false,
-            system: false,
-            mouse: false,
-            video: false,
+impl Whitener {
+    fn new() -> Self {
+        Self { fold_rounds: 0, hash: false, samples: 0, hashed_samples: 0, bytes_in: 0, bytes_out: 0 }
+    }
+    
+    /// Whitened sample plus the assessed entropy it can still carry
+    fn whiten(&mut self, source: &str, sample: &[u8], entropy_bits: f64) -> (Vec<u8>, f64) {
+        self.samples += 1;
+        self.bytes_in += sample.len() as u64;
+        
+        let mut out = sample.to_vec();
+        for _ in 0..self.fold_rounds {
+            if out.len() < 2 { break; }
+            out = xor_fold(&out, 1);
+        }
+        let mut bits = entropy_bits.min(out.len() as f64 * 8.0);
+        
+        if self.hash && out.len() >= 64 {
+            let mut hasher = Sha256::new();
+            hasher.update(source.as_bytes());
+            hasher.update(&out);
+            let in_len = out.len();
+            out = hasher.finalize().to_vec();
+            bits = output_entropy(in_len as f64 * 8.0, 256.0, 256.0, bits);
+            self.hashed_samples += 1;
         }
+        
+        self.bytes_out += out.len() as u64;
+        (out, bits)
     }
 }
 
-#[pyclass]
-struct ChaosEngine {
-    state: Arc<Mutex<SharedState>>,
-    running: Arc<AtomicBool>,
-    tx_entropy: Sender<(String, Vec<u8>)>,
+// Stage 3: the 32-byte main pool. Conditioned reseed output is hashed in, output generators
+// draw from it, and it ratchets forward after every extraction and output.
+struct Accumulator {
+    pool: [u8; 32],
+    mixes: u64,
+    bytes_mixed: u64,
+    ratchets: u64,
 }
 
-// ═══════════════════════════════════════════════════════════════════════════
-// HEALTH CHECKS
-// ═══════════════════════════════════════════════════════════════════════════
-
-fn check_health_rct(data: &[u8], cutoff: usize) -> bool {
-    if data.is_empty() { return true; }
-    let mut max_repeats = 0usize;
-    let mut current_repeats = 1usize;
-    let mut last_val = data[0];
-    for &byte in &data[1..] {
-        if byte == last_val {
-            current_repeats += 1;
-        } else {
-            max_repeats = max_repeats.max(current_repeats);
-            current_repeats = 1;
-            last_val = byte;
+impl Accumulator {
+    fn new() -> Self {
+        Self { pool: [0u8; 32], mixes: 0, bytes_mixed: 0, ratchets: 0 }
+    }
+    
+    /// pool' = SHA3-256(pool || parts...)
+    fn mix(&mut self, parts: &[&[u8]]) {
+        let mut hasher = Sha3_256::new();
+        hasher.update(self.pool);
+        for part in parts {
+            hasher.update(part);
+            self.bytes_mixed += part.len() as u64;
         }
+        self.pool = hasher.finalize().into();
+        self.mixes += 1;
     }
-    max_repeats = max_repeats.max(current_repeats);
-    max_repeats < cutoff
-}
-
-fn check_health_apt(data: &[u8], cutoff: f64) -> bool {
-    if data.len() < 10 { return false; }
-    let mut counts = [0usize; 256];
-    let mut max_count = 0usize;
-    for &b in data {
-        let c = counts[b as usize] + 1;
-        counts[b as usize] = c;
-        if c > max_count { max_count = c; }
+    
+    /// Domain-separated so the extractor key is never the pool itself
+    fn extractor_key(&self) -> [u8; 32] {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"CHAOS_MAGNET_EXTRACTOR_KEY");
+        hasher.update(self.pool);
+        hasher.finalize().into()
+    }
+    
+    /// What metrics show of the pool: a truncated one-way view under its own domain tag. The
+    /// pool feeds the DRBGs, the extractor key and the seed file, so it is never published.
+    fn view(&self) -> [u8; 16] {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"CHAOS_MAGNET_POOL_VIEW");
+        hasher.update(self.pool);
+        let mut view = [0u8; 16];
+        view.copy_from_slice(&hasher.finalize()[..16]);
+        view
+    }
+    
+    /// Forward security: the pool steps through a one-way function after every extraction and
+    /// every output drawn from it, and the previous value is overwritten. Whoever captures the
+    /// current pool can't run it backwards to anything already emitted.
+    fn ratchet(&mut self) {
+        ratchet_state(&mut self.pool);
+        self.ratchets += 1;
     }
-    let ratio = max_count as f64 / data.len() as f64;
-    ratio < cutoff
 }
 
-fn passes_health_checks(data: &[u8]) -> bool {
-    check_health_rct(data, RCT_CUTOFF) && check_health_apt(data, APT_CUTOFF)
+/// One-way step shared by the main and slow pools: the state becomes
+/// SHA3-256("CHAOS_MAGNET_RATCHET" || state) and the old value is wiped
+fn ratchet_state(state: &mut [u8; 32]) {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"CHAOS_MAGNET_RATCHET");
+    hasher.update(*state);
+    let mut next: [u8; 32] = hasher.finalize().into();
+    state.zeroize();
+    *state = next;
+    next.zeroize();
 }
 
-// ═══════════════════════════════════════════════════════════════════════════
-// ENTROPY CALCULATIONS
-// ═══════════════════════════════════════════════════════════════════════════
+// Which accumulator a source feeds. The fast path (Fortuna + main pool) keeps the output
+// DRBGs lively; the slow pool only reseeds after a large assessed amount and backs key minting.
+#[derive(Clone, Copy, PartialEq)]
+enum PoolRoute {
+    Fast,
+    Slow,
+    Both,
+}
 
-fn get_timestamp() -> u64 {
-    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+impl PoolRoute {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "fast" => Some(PoolRoute::Fast),
+            "slow" => Some(PoolRoute::Slow),
+            "both" => Some(PoolRoute::Both),
+            _ => None,
+        }
+    }
+    
+    fn name(&self) -> &'static str {
+        match self {
+            PoolRoute::Fast => "fast",
+            PoolRoute::Slow => "slow",
+            PoolRoute::Both => "both",
+        }
+    }
+    
+    fn feeds_fast(&self) -> bool {
+        matches!(self, PoolRoute::Fast | PoolRoute::Both)
+    }
+    
+    fn feeds_slow(&self) -> bool {
+        matches!(self, PoolRoute::Slow | PoolRoute::Both)
+    }
 }
 
-fn get_timestamp_nanos() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_nanos() as u64
+// High-assurance accumulator for key minting. Raw samples stream into a SHA-512 state
+// (nothing is buffered) and the 32-byte slow state only changes once SLOW_POOL_RESEED_BITS
+// of assessed input have arrived, so a mint never rests on the latest few hundred bytes.
+struct SlowPool {
+    hasher: Sha512,
+    pending_bits: f64,
+    pending_bytes: usize,
+    contributors: HashSet<String>,  // Sources behind the pending input
+    state: [u8; 32],
+    reseeds: u64,
+    last_reseed_bits: f64,
+    last_contributors: usize,
 }
 
-fn shannon_entropy(data: &[u8]) -> f64 {
-    if data.is_empty() { return 0.0; }
-    let mut entropy = 0.0;
-    let mut counts = [0usize; 256];
-    for &b in data { counts[b as usize] += 1; }
-    let len = data.len() as f64;
-    for &count in &counts {
-        if count > 0 {
-            let p = count as f64 / len;
-            entropy -= p * p.log2();
+impl SlowPool {
+    fn new() -> Self {
+        Self {
+            hasher: Sha512::new(),
+            pending_bits: 0.0,
+            pending_bytes: 0,
+            contributors: HashSet::new(),
+            state: [0u8; 32],
+            reseeds: 0,
+            last_reseed_bits: 0.0,
+            last_contributors: 0,
         }
     }
-    entropy
+    
+    /// Returns true when this sample completed a reseed
+    fn add_raw_bytes(&mut self, source: &str, raw_data: &[u8], entropy_bits: f64) -> bool {
+        self.hasher.update(source.as_bytes());
+        self.hasher.update((raw_data.len() as u64).to_le_bytes());
+        self.hasher.update(raw_data);
+        self.pending_bits += entropy_bits;
+        self.pending_bytes += raw_data.len();
+        self.contributors.insert(source.to_string());
+        
+        if self.pending_bits < SLOW_POOL_RESEED_BITS {
+            return false;
+        }
+        
+        let mut digest = self.hasher.finalize_reset();
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"CHAOS_MAGNET_SLOW_RESEED");
+        hasher.update(self.state);
+        hasher.update(&digest);
+        self.state = hasher.finalize().into();
+        digest.as_mut_slice().zeroize();
+        
+        self.reseeds += 1;
+        self.last_reseed_bits = self.pending_bits;
+        self.last_contributors = self.contributors.len();
+        self.pending_bits = 0.0;
+        self.pending_bytes = 0;
+        self.contributors.clear();
+        true
+    }
+    
+    /// Same one-way step as the main pool, taken after every mint
+    fn ratchet(&mut self) {
+        ratchet_state(&mut self.state);
+    }
+    
+    fn fill_percentage(&self) -> f64 {
+        (self.pending_bits / SLOW_POOL_RESEED_BITS * 100.0).min(100.0)
+    }
 }
 
-fn min_entropy(data: &[u8]) -> f64 {
-    if data.is_empty() { return 0.0; }
-    let mut counts = [0usize; 256];
-    for &b in data { counts[b as usize] += 1; }
-    let max_count = counts.iter().max().copied().unwrap_or(0);
-    let max_prob = max_count as f64 / data.len() as f64;
-    if max_prob <= 0.0 || max_prob >= 1.0 { return 0.0; }
-    -max_prob.log2()
+// KEM parameter set for minted bundles (Kyber = ML-KEM, FIPS 203)
+#[derive(Clone, Copy, PartialEq)]
+enum KemKind {
+    Kyber512,
+    Kyber768,
+    Kyber1024,
 }
 
-// ═══════════════════════════════════════════════════════════════════════════
-// HARVESTERS (WITH THROTTLING)
-// ═══════════════════════════════════════════════════════════════════════════
-
-fn start_trng_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
-    thread::spawn(move || {
-        let mut rng = rand::rngs::OsRng;
-        while running.load(Ordering::Relaxed) {
-            let enabled = state.lock().harvester_states.trng;
-            if enabled {
-                let mut buf = [0u8; 1024];
-                rng.fill_bytes(&mut buf);
-                if passes_health_checks(&buf) {
-                    let _ = tx.try_send(("TRNG".to_string(), buf.to_vec()));
-                }
+impl KemKind {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().replace(['-', '_'], "").as_str() {
+            "kyber512" | "mlkem512" | "512" => Some(KemKind::Kyber512),
+            "kyber768" | "mlkem768" | "768" => Some(KemKind::Kyber768),
+            "kyber1024" | "mlkem1024" | "1024" => Some(KemKind::Kyber1024),
+            _ => None,
+        }
+    }
+    
+    fn name(&self) -> &'static str {
+        match self {
+            KemKind::Kyber512 => "kyber512",
+            KemKind::Kyber768 => "kyber768",
+            KemKind::Kyber1024 => "kyber1024",
+        }
+    }
+    
+    /// (public key, secret key)
+    fn keypair(&self) -> (Vec<u8>, Vec<u8>) {
+        match self {
+            KemKind::Kyber512 => {
+                let (pk, sk) = kyber512::keypair();
+                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
+            }
+            KemKind::Kyber768 => {
+                let (pk, sk) = kyber768::keypair();
+                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
+            }
+            KemKind::Kyber1024 => {
+                let (pk, sk) = kyber1024::keypair();
+                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
             }
-            thread::sleep(Duration::from_secs(1));
         }
-    });
+    }
+    
+    fn from_public_key(pk: &[u8]) -> Option<Self> {
+        [KemKind::Kyber512, KemKind::Kyber768, KemKind::Kyber1024].into_iter().find(|kind| pk.len() == match kind {
+            KemKind::Kyber512 => kyber512::public_key_bytes(),
+            KemKind::Kyber768 => kyber768::public_key_bytes(),
+            KemKind::Kyber1024 => kyber1024::public_key_bytes(),
+        })
+    }
+    
+    /// For bundles minted before the parameter set was recorded
+    fn from_secret_key(sk: &[u8]) -> Option<Self> {
+        [KemKind::Kyber512, KemKind::Kyber768, KemKind::Kyber1024].into_iter().find(|kind| sk.len() == match kind {
+            KemKind::Kyber512 => kyber512::secret_key_bytes(),
+            KemKind::Kyber768 => kyber768::secret_key_bytes(),
+            KemKind::Kyber1024 => kyber1024::secret_key_bytes(),
+        })
+    }
+    
+    /// (shared secret, ciphertext) for the holder of `pk`
+    fn encapsulate(&self, pk: &[u8]) -> Result<(Vec<u8>, Vec<u8>), String> {
+        let bad_key = |_| format!("Invalid {} public key", self.name());
+        match self {
+            KemKind::Kyber512 => {
+                let (ss, ct) = kyber512::encapsulate(&kyber512::PublicKey::from_bytes(pk).map_err(bad_key)?);
+                Ok((ss.as_bytes().to_vec(), ct.as_bytes().to_vec()))
+            }
+            KemKind::Kyber768 => {
+                let (ss, ct) = kyber768::encapsulate(&kyber768::PublicKey::from_bytes(pk).map_err(bad_key)?);
+                Ok((ss.as_bytes().to_vec(), ct.as_bytes().to_vec()))
+            }
+            KemKind::Kyber1024 => {
+                let (ss, ct) = kyber1024::encapsulate(&kyber1024::PublicKey::from_bytes(pk).map_err(bad_key)?);
+                Ok((ss.as_bytes().to_vec(), ct.as_bytes().to_vec()))
+            }
+        }
+    }
+    
+    /// Shared secret from `ciphertext`; Kyber decapsulation never fails on a well-formed but
+    /// wrong ciphertext, it yields an unrelated secret (implicit rejection)
+    fn decapsulate(&self, sk: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, String> {
+        let bad_key = |_| format!("Invalid {} secret key", self.name());
+        let bad_ct = |_| format!("Ciphertext is not a {} ciphertext", self.name());
+        match self {
+            KemKind::Kyber512 => {
+                let sk = kyber512::SecretKey::from_bytes(sk).map_err(bad_key)?;
+                let ct = kyber512::Ciphertext::from_bytes(ciphertext).map_err(bad_ct)?;
+                Ok(kyber512::decapsulate(&ct, &sk).as_bytes().to_vec())
+            }
+            KemKind::Kyber768 => {
+                let sk = kyber768::SecretKey::from_bytes(sk).map_err(bad_key)?;
+                let ct = kyber768::Ciphertext::from_bytes(ciphertext).map_err(bad_ct)?;
+                Ok(kyber768::decapsulate(&ct, &sk).as_bytes().to_vec())
+            }
+            KemKind::Kyber1024 => {
+                let sk = kyber1024::SecretKey::from_bytes(sk).map_err(bad_key)?;
+                let ct = kyber1024::Ciphertext::from_bytes(ciphertext).map_err(bad_ct)?;
+                Ok(kyber1024::decapsulate(&ct, &sk).as_bytes().to_vec())
+            }
+        }
+    }
 }
 
-fn start_audio_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
-    thread::spawn(move || {
-        use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-        
-        let host = cpal::default_host();
-        let device = match host.default_input_device() {
-            Some(d) => d,
-            None => return,
-        };
-        
-        let config = match device.default_input_config() {
-            Ok(c) => c,
-            Err(_) => return,
-        };
-        
-        let tx_clone = tx.clone();
-        let running_stream = running.clone();
-        let state_clone = state.clone();
-        
-        // THROTTLE: Track last send time
-        let last_send = Arc::new(Mutex::new(Instant::now()));
-        let last_send_clone = last_send.clone();
-
-        let stream = device.build_input_stream(
-            &config.into(),
-            move |data: &[f32], _: &_| {
-                if !running_stream.load(Ordering::Relaxed) { return; }
-                
-                let enabled = state_clone.lock().harvester_states.audio;
-                if !enabled { return; }
-                
-                // THROTTLE: Max 5 sends/second (200ms minimum interval)
-                let mut last = last_send_clone.lock();
-                if last.elapsed() < Duration::from_millis(200) {
-                    return;  // Skip this callback
-                }
-                *last = Instant::now();
-                drop(last);
-                
-                // LIMIT: Only take first 256 samples to avoid flooding
-                let sample_limit = data.len().min(256);
-                let mut bytes = Vec::with_capacity(sample_limit * 4);
-                
-                for &sample in data.iter().take(sample_limit).step_by(4) {
-                    let bits = sample.to_bits();
-                    bytes.extend_from_slice(&bits.to_le_bytes());
-                }
-                
-                let nanos = get_timestamp_nanos();
-                bytes.extend_from_slice(&nanos.to_le_bytes());
-                
-                if passes_health_checks(&bytes) {
-                    let _ = tx_clone.try_send(("AUDIO".to_string(), bytes));
-                }
-            },
-            |_| {}, None
-        );
+// Parameter set of the Falcon session identity, chosen at engine construction
+#[derive(Clone, Copy, PartialEq)]
+enum FalconKind {
+    Falcon512,
+    Falcon1024,
+}
 
-        if let Ok(s) = stream {
-            let _ = s.play();
-            while running.load(Ordering::Relaxed) { 
-                thread::sleep(Duration::from_secs(1)); 
+impl FalconKind {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().replace(['-', '_'], "").as_str() {
+            "falcon512" | "512" => Some(FalconKind::Falcon512),
+            "falcon1024" | "1024" => Some(FalconKind::Falcon1024),
+            _ => None,
+        }
+    }
+    
+    /// The parameter set a public key belongs to, by its length
+    fn from_public_key(pk: &[u8]) -> Option<Self> {
+        if pk.len() == falcon512::public_key_bytes() {
+            Some(FalconKind::Falcon512)
+        } else if pk.len() == falcon1024::public_key_bytes() {
+            Some(FalconKind::Falcon1024)
+        } else {
+            None
+        }
+    }
+    
+    fn name(&self) -> &'static str {
+        match self {
+            FalconKind::Falcon512 => "falcon512",
+            FalconKind::Falcon1024 => "falcon1024",
+        }
+    }
+    
+    /// (public key, secret key)
+    fn keypair(&self) -> (Vec<u8>, Vec<u8>) {
+        match self {
+            FalconKind::Falcon512 => {
+                let (pk, sk) = falcon512::keypair();
+                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
+            }
+            FalconKind::Falcon1024 => {
+                let (pk, sk) = falcon1024::keypair();
+                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
             }
         }
-    });
-}
-
-fn start_system_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
-    thread::spawn(move || {
-        use sysinfo::System;
-        let mut sys = System::new_all();
-        
-        while running.load(Ordering::Relaxed) {
-            let enabled = state.lock().harvester_states.system;
-            if enabled {
-                sys.refresh_all();
-                let mut raw_bytes = Vec::with_capacity(128);
-                
-                for cpu in sys.cpus() {
-                    let usage_bits = cpu.cpu_usage().to_bits();
-                    let freq = cpu.frequency();
-                    raw_bytes.extend_from_slice(&usage_bits.to_le_bytes());
-                    raw_bytes.extend_from_slice(&freq.to_le_bytes());
-                }
-                
-                let nanos = get_timestamp_nanos();
-                raw_bytes.extend_from_slice(&nanos.to_le_bytes());
-                let mem = sys.used_memory();
-                raw_bytes.extend_from_slice(&mem.to_le_bytes());
-                let avail = sys.available_memory();
-                raw_bytes.extend_from_slice(&avail.to_le_bytes());
-                
-                if !raw_bytes.is_empty() && passes_health_checks(&raw_bytes) {
-                    let _ = tx.try_send(("SYS".to_string(), raw_bytes));
-                }
+    }
+    
+    /// Upper bound on a detached signature's length
+    fn signature_bytes(&self) -> usize {
+        match self {
+            FalconKind::Falcon512 => falcon512::signature_bytes(),
+            FalconKind::Falcon1024 => falcon1024::signature_bytes(),
+        }
+    }
+    
+    /// Detached signature over `message` with secret key `sk`
+    fn sign(&self, sk: &[u8], message: &[u8]) -> Result<Vec<u8>, String> {
+        match self {
+            FalconKind::Falcon512 => {
+                let sk = falcon512::SecretKey::from_bytes(sk).map_err(|e| e.to_string())?;
+                Ok(falcon512::detached_sign(message, &sk).as_bytes().to_vec())
+            }
+            FalconKind::Falcon1024 => {
+                let sk = falcon1024::SecretKey::from_bytes(sk).map_err(|e| e.to_string())?;
+                Ok(falcon1024::detached_sign(message, &sk).as_bytes().to_vec())
             }
-            thread::sleep(Duration::from_millis(500));
         }
-    });
+    }
+    
+    fn verify(&self, pk: &[u8], message: &[u8], signature: &[u8]) -> bool {
+        match self {
+            FalconKind::Falcon512 => {
+                let (Ok(pk), Ok(sig)) = (falcon512::PublicKey::from_bytes(pk), falcon512::DetachedSignature::from_bytes(signature)) else {
+                    return false;
+                };
+                falcon512::verify_detached_signature(&sig, message, &pk).is_ok()
+            }
+            FalconKind::Falcon1024 => {
+                let (Ok(pk), Ok(sig)) = (falcon1024::PublicKey::from_bytes(pk), falcon1024::DetachedSignature::from_bytes(signature)) else {
+                    return false;
+                };
+                falcon1024::verify_detached_signature(&sig, message, &pk).is_ok()
+            }
+        }
+    }
 }
 
-fn start_mouse_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
-    thread::spawn(move || {
-        use rdev::{listen, EventType};
-        
-        let counter = Arc::new(AtomicUsize::new(0));
-        let counter_clone = counter.clone();
-        let last_instant = Arc::new(Mutex::new(Instant::now()));
-        let last_instant_clone = last_instant.clone();
-        
-        let callback = move |event: rdev::Event| {
-            if !running.load(Ordering::Relaxed) { return; }
-            
-            let enabled = state.lock().harvester_states.mouse;
-            if !enabled { return; }
-            
-            match event.event_type {
-                EventType::MouseMove { x, y } => {
-                    let count = counter_clone.fetch_add(1, Ordering::Relaxed);
-                    if count % 20 != 0 { return; }
-                    
-                    let now = Instant::now();
-                    let mut last = last_instant_clone.lock();
-                    let delta_nanos = now.duration_since(*last).as_nanos() as u64;
-                    *last = now;
-                    drop(last);
-                    
-                    let mut payload = Vec::with_capacity(24);
-                    payload.extend_from_slice(&(x as f64).to_bits().to_le_bytes());
-                    payload.extend_from_slice(&(y as f64).to_bits().to_le_bytes());
-                    payload.extend_from_slice(&delta_nanos.to_le_bytes());
-                    
-                    let _ = tx.try_send(("MOUSE_MOV".to_string(), payload));
-                },
-                EventType::ButtonPress(btn) => {
-                    let now = Instant::now();
-                    let mut last = last_instant_clone.lock();
-                    let delta_nanos = now.duration_since(*last).as_nanos() as u64;
-                    *last = now;
-                    drop(last);
-                    
-                    let mut payload = Vec::with_capacity(24);
-                    let btn_bytes = format!("{:?}", btn).into_bytes();
-                    payload.extend_from_slice(&btn_bytes);
-                    payload.extend_from_slice(&delta_nanos.to_le_bytes());
-                    payload.extend_from_slice(&get_timestamp_nanos().to_le_bytes());
-                    
-                    let _ = tx.try_send(("MOUSE_CLK".to_string(), payload));
-                }
-                _ => {}
-            }
-        };
-        
-        let _ = listen(callback);
-    });
+// Signature keypairs mintable into the vault (Dilithium = ML-DSA, FIPS 204;
+// SPHINCS+ = SLH-DSA, FIPS 205, the hash-based non-lattice fallback)
+#[derive(Clone, Copy, PartialEq)]
+enum SignatureKeyKind {
+    Dilithium2,
+    Dilithium3,
+    Dilithium5,
+    Falcon(FalconKind),
+    Sphincs128s,
+    Sphincs192s,
+    Sphincs256s,
 }
 
-fn start_video_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
-    thread::spawn(move || {
-        use nokhwa::pixel_format::RgbFormat;
-        use nokhwa::utils::{CameraIndex, RequestedFormat, RequestedFormatType};
-        use nokhwa::Camera;
-        
-        let index = CameraIndex::Index(0);
-        let format = RequestedFormat::new::<RgbFormat>(RequestedFormatType::AbsoluteHighestFrameRate);
-        
-        if let Ok(mut camera) = Camera::new(index, format) {
-            if camera.open_stream().is_ok() {
-                let mut last_frame_hash: Option<[u8; 32]> = None;
-                
-                while running.load(Ordering::Relaxed) {
-                    let enabled = state.lock().harvester_states.video;
-                    if enabled {
-                        if let Ok(frame) = camera.frame() {
-                            let buffer = frame.buffer();
-                            let mut noise: Vec<u8> = buffer.iter()
-                                .step_by(7)
-                                .map(|&b| b & 0x0F)
-                                .collect();
-                            
-                            let nanos = get_timestamp_nanos();
-                            noise.extend_from_slice(&nanos.to_le_bytes());
-                            
-                            if let Some(ref prev_hash) = last_frame_hash {
-                                for (i, b) in noise.iter_mut().enumerate().take(32) {
-                                    *b ^= prev_hash[i % 32];
-                                }
-                            }
-                            
-                            let mut hasher = Sha3_256::new();
-                            hasher.update(&noise);
-                            last_frame_hash = Some(hasher.finalize().into());
-                            
-                            if passes_health_checks(&noise) {
-                                let _ = tx.try_send(("VIDEO".to_string(), noise));
-                            }
-                        }
-                    }
-                    thread::sleep(Duration::from_secs(1));
-                }
+impl SignatureKeyKind {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().replace(['-', '_'], "").as_str() {
+            "dilithium2" | "mldsa44" => Some(SignatureKeyKind::Dilithium2),
+            "dilithium3" | "mldsa65" => Some(SignatureKeyKind::Dilithium3),
+            "dilithium5" | "mldsa87" => Some(SignatureKeyKind::Dilithium5),
+            "sphincs128s" | "sphincssha2128s" | "slhdsa128s" | "slhdsasha2128s" => Some(SignatureKeyKind::Sphincs128s),
+            "sphincs192s" | "sphincssha2192s" | "slhdsa192s" | "slhdsasha2192s" => Some(SignatureKeyKind::Sphincs192s),
+            "sphincs256s" | "sphincssha2256s" | "slhdsa256s" | "slhdsasha2256s" => Some(SignatureKeyKind::Sphincs256s),
+            _ => FalconKind::from_name(name).map(SignatureKeyKind::Falcon),
+        }
+    }
+    
+    fn name(&self) -> &'static str {
+        match self {
+            SignatureKeyKind::Dilithium2 => "dilithium2",
+            SignatureKeyKind::Dilithium3 => "dilithium3",
+            SignatureKeyKind::Dilithium5 => "dilithium5",
+            SignatureKeyKind::Falcon(kind) => kind.name(),
+            SignatureKeyKind::Sphincs128s => "sphincs-sha2-128s",
+            SignatureKeyKind::Sphincs192s => "sphincs-sha2-192s",
+            SignatureKeyKind::Sphincs256s => "sphincs-sha2-256s",
+        }
+    }
+    
+    /// (public key, secret key)
+    fn keypair(&self) -> (Vec<u8>, Vec<u8>) {
+        match self {
+            SignatureKeyKind::Dilithium2 => {
+                let (pk, sk) = dilithium2::keypair();
+                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
+            }
+            SignatureKeyKind::Dilithium3 => {
+                let (pk, sk) = dilithium3::keypair();
+                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
+            }
+            SignatureKeyKind::Dilithium5 => {
+                let (pk, sk) = dilithium5::keypair();
+                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
+            }
+            SignatureKeyKind::Falcon(kind) => kind.keypair(),
+            SignatureKeyKind::Sphincs128s => {
+                let (pk, sk) = sphincssha2128ssimple::keypair();
+                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
+            }
+            SignatureKeyKind::Sphincs192s => {
+                let (pk, sk) = sphincssha2192ssimple::keypair();
+                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
+            }
+            SignatureKeyKind::Sphincs256s => {
+                let (pk, sk) = sphincssha2256ssimple::keypair();
+                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
             }
         }
-    });
+    }
 }
 
-// ═══════════════════════════════════════════════════════════════════════════
-// P2P SERVER (NEW)
-// ═══════════════════════════════════════════════════════════════════════════
+// Conservative entropy accounting for the main pool. Credits use a configured per-source
+// rate, capped at a fraction of the measured min-entropy; every byte that leaves through
+// minting or an output API is debited at full value.
+struct EntropyLedger {
+    credit_rates: HashMap<String, f64>,  // Assessed bits per byte, per source
+    credited_by_source: HashMap<String, f64>,
+    available_bits: f64,
+    total_credited_bits: f64,
+    total_debited_bits: f64,
+}
 
-fn start_p2p_server(
-    tx: Sender<(String, Vec<u8>)>,
-    state: Arc<Mutex<SharedState>>,
-    running: Arc<AtomicBool>
-) {
-    thread::spawn(move || {
-        use std::net::TcpListener;
-        use std::io::{Read, Write};
-        
-        let port = state.lock().p2p_config.listen_port;
-        let addr = format!("0.0.0.0:{}", port);
+impl EntropyLedger {
+    fn new() -> Self {
+        Self {
+            credit_rates: HashMap::new(),
+            credited_by_source: HashMap::new(),
+            available_bits: 0.0,
+            total_credited_bits: 0.0,
+            total_debited_bits: 0.0,
+        }
+    }
+    
+    /// `min_entropy` is the measured per-byte min-entropy of the sample
+    fn credit(&mut self, source: &str, len: usize, min_entropy: f64) -> f64 {
+        let default_rate = match source {
+            "EGD" => EGD_DEFAULT_BITS_PER_BYTE,
+            s if s.starts_with("P2P_") => P2P_DEFAULT_BITS_PER_BYTE,
+            _ => LEDGER_DEFAULT_BITS_PER_BYTE,
+        };
+        let rate = self.credit_rates.get(source).copied().unwrap_or(default_rate);
+        let per_byte = rate.min(min_entropy * LEDGER_ASSESSMENT_FRACTION).max(0.0);
+        let bits = per_byte * len as f64;
         
-        let listener = match TcpListener::bind(&addr) {
+        self.available_bits = (self.available_bits + bits).min(LEDGER_CAPACITY_BITS);
+        self.total_credited_bits += bits;
+        *self.credited_by_source.entry(source.to_string()).or_insert(0.0) += bits;
+        bits
+    }
+    
+    /// Explicit operator claim, not subject to the per-source rate or min-entropy cap
+    fn credit_claimed(&mut self, source: &str, bits: f64) {
+        self.available_bits = (self.available_bits + bits).min(LEDGER_CAPACITY_BITS);
+        self.total_credited_bits += bits;
+        *self.credited_by_source.entry(source.to_string()).or_insert(0.0) += bits;
+    }
+    
+    fn debit(&mut self, bits: f64) {
+        self.available_bits = (self.available_bits - bits).max(0.0);
+        self.total_debited_bits += bits;
+    }
+}
+
+#[derive(Clone, Default)]
+struct SourceMetrics {
+    raw_shannon: f64,
+    min_entropy: f64,
+    samples: u64,
+    avg_raw_entropy: f64,
+    total_bits_contributed: f64,
+    chi_square: f64,         // Latest rolling statistic (0 until the window fills)
+    chi_square_alarm: bool,
+    lag_min_entropy: Option<f64>,  // SP 800-90B lag predictor over recent history
+    lag_period: usize,             // Best-predicting lag, in samples
+    markov_min_entropy: Option<f64>,
+    collision_min_entropy: Option<f64>,
+    compression_min_entropy: Option<f64>,  // Background thread, over BATTERY_HISTORY_BYTES
+    mcv_min_entropy: Option<f64>,  // Over the same window as the non-IID estimators
+    iid: Option<bool>,  // Last run_iid_test verdict
+    tainted: bool,         // Latest sample failed health checks (research mode only)
+    tainted_samples: u64,
+}
+
+impl SourceMetrics {
+    /// Most conservative of the windowed estimates; None until the first window has run.
+    /// A source that passed IID permutation testing is on the IID track (MCV only).
+    fn assessed_min_entropy(&self) -> Option<f64> {
+        if self.iid == Some(true) && self.mcv_min_entropy.is_some() {
+            return self.mcv_min_entropy;
+        }
+        [
+            self.mcv_min_entropy,
+            self.collision_min_entropy,
+            self.markov_min_entropy,
+            self.compression_min_entropy,
+            self.lag_min_entropy,
+        ]
+            .into_iter()
+            .flatten()
+            .reduce(f64::min)
+    }
+}
+
+// RCT/APT tuning for one source: HID sources need looser cutoffs than a TRNG
+#[derive(Clone, Copy)]
+struct HealthParams {
+    rct_cutoff: usize,
+    apt_cutoff: f64,  // Share of the window; the count cutoff is C = ceil(apt_cutoff * window)
+    window: usize,  // APT window, in samples (bytes)
+    chi_square_threshold: Option<f64>,  // Alarm above this; None skips the test (raw bytes aren't uniform)
+}
+
+impl Default for HealthParams {
+    fn default() -> Self {
+        Self {
+            rct_cutoff: RCT_CUTOFF,
+            apt_cutoff: APT_CUTOFF,
+            window: APT_WINDOW,
+            chi_square_threshold: None,
+        }
+    }
+}
+
+impl HealthParams {
+    fn apt_cutoff_count(&self) -> usize {
+        ((self.apt_cutoff * self.window as f64).ceil() as usize).max(2)
+    }
+    
+    /// Built-in defaults for sources that haven't been configured
+    fn default_for(source: &str) -> Self {
+        if source == "WIFI" {
+            // Slow RSSI drift would trip the global RCT
+            Self { rct_cutoff: WIFI_RCT_CUTOFF, apt_cutoff: WIFI_APT_CUTOFF, ..Self::default() }
+        } else if source.starts_with("P2P_") {
+            // Peers send DRBG output, so anything short of uniform is suspect
+            Self {
+                rct_cutoff: P2P_RCT_CUTOFF,
+                apt_cutoff: P2P_APT_CUTOFF,
+                chi_square_threshold: Some(CHI_SQUARE_THRESHOLD),
+                ..Self::default()
+            }
+        } else {
+            Self::default()
+        }
+    }
+}
+
+// Per-source health state. STARTUP until the startup tests pass; HEALTHY sources are mixed and
+// credited; SUSPECT sources are still mixed but earn no entropy credit; DISABLED sources are
+// quarantined and nothing from them reaches the pool.
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+enum HealthState {
+    #[default]
+    Startup,
+    Healthy,
+    Suspect,
+    Disabled,
+}
+
+impl HealthState {
+    fn name(self) -> &'static str {
+        match self {
+            HealthState::Startup => "STARTUP",
+            HealthState::Healthy => "HEALTHY",
+            HealthState::Suspect => "SUSPECT",
+            HealthState::Disabled => "DISABLED",
+        }
+    }
+}
+
+// One health alarm, queued under the state lock and delivered to Python callbacks from the
+// mixer once the lock is released
+struct HealthEvent {
+    source: String,
+    test: &'static str,
+    statistic: f64,
+    severity: &'static str,  // "info", "warning" or "critical"
+}
+
+fn push_health_event(state: &mut SharedState, source: &str, test: &'static str, statistic: f64, severity: &'static str) {
+    if state.health_callbacks.is_empty() {
+        return;
+    }
+    if state.health_events.len() >= HEALTH_EVENT_QUEUE {
+        state.health_events.pop_front();
+    }
+    state.health_events.push_back(HealthEvent { source: source.to_string(), test, statistic, severity });
+}
+
+// SP 800-90B 4.4.2 adaptive proportion test over consecutive, non-overlapping windows of W
+// samples, carried across submissions: the first sample of each window is the reference, and
+// the test fails if it occurs C or more times in that window
+#[derive(Clone, Default)]
+struct AdaptiveProportion {
+    reference: u8,
+    count: usize,
+    seen: usize,  // Samples into the current window
+}
+
+impl AdaptiveProportion {
+    /// Feeds samples; returns the highest reference count reached along the way and whether a
+    /// window hit `cutoff`. A failing window is reported once and then closed, so its count
+    /// never carries into later submissions.
+    fn feed(&mut self, data: &[u8], window: usize, cutoff: usize) -> (usize, bool) {
+        let mut peak = 0;
+        let mut failed = false;
+        for &b in data {
+            if self.seen == 0 {
+                self.reference = b;
+                self.count = 1;
+            } else if b == self.reference {
+                self.count += 1;
+            }
+            peak = peak.max(self.count);
+            self.seen += 1;
+            if self.count >= cutoff {
+                failed = true;
+                self.seen = 0;
+            } else if self.seen >= window {
+                self.seen = 0;
+            }
+        }
+        (peak, failed)
+    }
+}
+
+// Mixer-side health tracking per source. SP 800-90B startup testing: a source's first
+// STARTUP_TEST_SAMPLES samples are held back and run through RCT and APT, and nothing from
+// it reaches the pool until they pass. The startup samples themselves are discarded.
+// After that, RCT runs on every sample and APT over consecutive windows spanning submissions.
+#[derive(Clone, Default)]
+struct SourceHealth {
+    startup_buffer: Vec<u8>,
+    startup_passed: bool,
+    startup_failures: u32,
+    apt: AdaptiveProportion,
+    continuous_failures: u64,
+    chi_window: VecDeque<u8>,
+    chi_counts: Vec<u32>,  // Byte-value histogram of chi_window
+    history: VecDeque<u8>, // Last BATTERY_HISTORY_BYTES of debiased raw data
+    estimator_pending: usize,  // Bytes recorded since the last estimator run
+    last_seen: Option<Instant>,
+    rct_last_run: usize,        // Continuous-test statistics, for get_metrics
+    rct_max_run: usize,
+    apt_last_proportion: f64,
+    apt_max_proportion: f64,
+    checks_passed: u64,
+    bytes_rejected: u64,  // Dropped by continuous failures or quarantine
+    state: HealthState,
+    consecutive_failures: u32,
+    consecutive_passes: u32,
+    retest_attempts: u32,  // Since the source was last stable; drives the backoff
+    next_retest: Option<Instant>,
+}
+
+impl SourceHealth {
+    fn record_history(&mut self, data: &[u8]) {
+        self.history.extend(data);
+        self.estimator_pending += data.len();
+        self.last_seen = Some(Instant::now());
+        let excess = self.history.len().saturating_sub(BATTERY_HISTORY_BYTES);
+        self.history.drain(..excess);
+    }
+    
+    /// The last ESTIMATOR_WINDOW bytes, once ESTIMATOR_INTERVAL new bytes have arrived
+    fn take_estimator_window(&mut self) -> Option<Vec<u8>> {
+        if self.estimator_pending < ESTIMATOR_INTERVAL || self.history.len() < ESTIMATOR_WINDOW {
+            return None;
+        }
+        self.estimator_pending = 0;
+        Some(self.history.iter().skip(self.history.len() - ESTIMATOR_WINDOW).copied().collect())
+    }
+    
+    /// Buffers `data`; once enough samples are in, runs the startup tests and returns the verdict
+    fn run_startup(&mut self, data: &[u8], params: &HealthParams) -> Option<bool> {
+        self.startup_buffer.extend_from_slice(data);
+        if self.startup_buffer.len() < STARTUP_TEST_SAMPLES {
+            return None;
+        }
+        
+        let passed = check_health_rct(&self.startup_buffer, params.rct_cutoff)
+            && !AdaptiveProportion::default().feed(&self.startup_buffer, params.window, params.apt_cutoff_count()).1;
+        self.startup_buffer.clear();
+        if passed {
+            self.startup_passed = true;
+            self.state = HealthState::Healthy;
+        } else {
+            self.startup_failures += 1;
+            // A retest gets one attempt before the source goes back to quarantine
+            if self.startup_failures >= STARTUP_MAX_FAILURES || self.retest_attempts > 0 {
+                self.disable();
+            }
+        }
+        Some(passed)
+    }
+    
+    /// RCT on the sample, APT continuing the current window; returns the failing test and its
+    /// statistic (longest run, or reference count as a share of the window)
+    fn run_continuous(&mut self, data: &[u8], params: &HealthParams) -> Option<(&'static str, f64)> {
+        let run = rct_max_run(data);
+        let (apt_peak, apt_failed) = self.apt.feed(data, params.window, params.apt_cutoff_count());
+        let proportion = apt_peak as f64 / params.window as f64;
+        self.rct_last_run = run;
+        self.rct_max_run = self.rct_max_run.max(run);
+        self.apt_last_proportion = proportion;
+        self.apt_max_proportion = self.apt_max_proportion.max(proportion);
+        
+        let failure = if run >= params.rct_cutoff {
+            Some(("RCT", run as f64))
+        } else if apt_failed {
+            Some(("APT", proportion))
+        } else {
+            None
+        };
+        if failure.is_some() {
+            self.continuous_failures += 1;
+            self.bytes_rejected += data.len() as u64;
+        } else {
+            self.checks_passed += 1;
+        }
+        failure
+    }
+    
+    /// Advances the state machine on a continuous-test result; returns (old, new) on a transition
+    fn record_result(&mut self, passed: bool) -> Option<(HealthState, HealthState)> {
+        let old = self.state;
+        if passed {
+            self.consecutive_failures = 0;
+            self.consecutive_passes += 1;
+            if self.consecutive_passes >= HEALTH_RECOVER_AFTER {
+                if old == HealthState::Suspect {
+                    self.state = HealthState::Healthy;
+                }
+                self.retest_attempts = 0;
+            }
+        } else {
+            self.consecutive_passes = 0;
+            self.consecutive_failures += 1;
+            if self.consecutive_failures >= HEALTH_DISABLE_AFTER {
+                self.disable();
+            } else if self.consecutive_failures >= HEALTH_SUSPECT_AFTER && old == HealthState::Healthy {
+                self.state = HealthState::Suspect;
+            }
+        }
+        (self.state != old).then_some((old, self.state))
+    }
+    
+    /// Quarantines the source and schedules its next retest, backing off exponentially
+    fn disable(&mut self) {
+        let delay = RETEST_BASE_SECS.saturating_mul(1 << self.retest_attempts.min(16)).min(RETEST_MAX_SECS);
+        self.state = HealthState::Disabled;
+        self.next_retest = Some(Instant::now() + Duration::from_secs(delay));
+    }
+    
+    /// Once the backoff has elapsed, puts a DISABLED source back through the startup tests on
+    /// fresh data; returns true when a retest begins
+    fn begin_retest(&mut self) -> bool {
+        if self.state != HealthState::Disabled || self.next_retest.is_some_and(|at| Instant::now() < at) {
+            return false;
+        }
+        self.retest_attempts += 1;
+        self.next_retest = None;
+        self.state = HealthState::Startup;
+        self.startup_passed = false;
+        self.startup_buffer.clear();
+        self.startup_failures = 0;
+        self.apt = AdaptiveProportion::default();
+        self.consecutive_failures = 0;
+        self.consecutive_passes = 0;
+        true
+    }
+    
+    /// Chi-square goodness-of-fit against uniform bytes over the last CHI_SQUARE_WINDOW
+    /// samples; None until the window has filled
+    fn update_chi_square(&mut self, data: &[u8]) -> Option<f64> {
+        if self.chi_counts.is_empty() {
+            self.chi_counts = vec![0; 256];
+        }
+        for &b in data {
+            self.chi_window.push_back(b);
+            self.chi_counts[b as usize] += 1;
+            if self.chi_window.len() > CHI_SQUARE_WINDOW {
+                if let Some(old) = self.chi_window.pop_front() {
+                    self.chi_counts[old as usize] -= 1;
+                }
+            }
+        }
+        if self.chi_window.len() < CHI_SQUARE_WINDOW {
+            return None;
+        }
+        
+        let expected = CHI_SQUARE_WINDOW as f64 / 256.0;
+        Some(self.chi_counts.iter()
+            .map(|&observed| (observed as f64 - expected).powi(2) / expected)
+            .sum())
+    }
+    
+    fn startup_status(&self) -> &'static str {
+        if self.startup_passed {
+            "PASSED"
+        } else if self.startup_failures > 0 {
+            "RETESTING"
+        } else {
+            "RUNNING"
+        }
+    }
+}
+
+// Raw samples for offline SP 800-90B assessment: one byte per sample, no header, as NIST's
+// ea_iid / ea_non_iid tools read with 8 bits per symbol
+struct RawCapture {
+    writer: std::io::BufWriter<fs::File>,
+    path: String,
+    requested: usize,
+    written: usize,
+}
+
+impl RawCapture {
+    /// Appends up to the remaining sample count; returns true once the capture is complete
+    fn write(&mut self, data: &[u8]) -> std::io::Result<bool> {
+        use std::io::Write;
+        let take = data.len().min(self.requested - self.written);
+        self.writer.write_all(&data[..take])?;
+        self.written += take;
+        if self.written < self.requested {
+            return Ok(false);
+        }
+        self.writer.flush()?;
+        Ok(true)
+    }
+}
+
+// NEW: P2P Configuration
+#[derive(Clone)]
+struct P2PConfig {
+    active: bool,
+    listen_port: u16,
+    peers: Vec<String>,  // List of "IP:PORT" strings
+    received_count: u64,
+    peer_health: HashMap<String, PeerHealth>,  // By sender IP
+}
+
+// Submission history for one sending peer; banned peers are refused at the listener
+#[derive(Clone, Default)]
+struct PeerHealth {
+    accepted: u64,
+    rejected: u64,
+    consecutive_failures: u32,
+    banned: bool,
+}
+
+impl Default for P2PConfig {
+    fn default() -> Self {
+        Self {
+            active: false,
+            listen_port: 9000,
+            peers: Vec::new(),
+            received_count: 0,
+            peer_health: HashMap::new(),
+        }
+    }
+}
+
+// Userspace entropy daemon mode: KERNEL consumer output written to /dev/random with RNDADDENTROPY
+struct KernelFeed {
+    bits_per_byte: f64,  // Credit claimed to the kernel, further capped by the ledger
+    bytes_fed: u64,
+    bits_credited: u64,
+    last_error: Option<String>,
+}
+
+// Per-client EGD accounting, keyed by peer credentials where the platform provides them
+#[derive(Default)]
+struct EgdClient {
+    connected: u32,  // Open connections
+    connections: u64,
+    requests: u64,
+    bytes_served: u64,
+    bytes_received: u64,  // Written with command 0x03 and mixed in as source EGD
+}
+
+struct EgdServer {
+    path: String,
+    clients: HashMap<String, EgdClient>,
+    stop: Arc<AtomicBool>,  // Ends the listener and its connections
+}
+
+// Socket service accounting per peer uid
+#[derive(Default)]
+struct SocketClient {
+    requests: u64,
+    bytes_served: u64,
+    denied: u64,  // Connections refused by the uid allow-list
+}
+
+// Local randomness service; peers are admitted by SO_PEERCRED uid
+struct SocketService {
+    path: String,
+    allowed_uids: Vec<u32>,  // Root and our own uid are always allowed
+    clients: HashMap<u32, SocketClient>,
+    stop: Arc<AtomicBool>,
+}
+
+// Continuous output to disk, rotated logrotate-style: `path` -> `path.1` -> ... -> `path.<keep_files>`
+struct FileSink {
+    path: String,
+    rotate_at_bytes: u64,
+    keep_files: u32,
+    rate_limit: Option<u64>,  // Bytes per second
+    bytes_written: u64,
+    rotations: u64,
+    stop: Arc<AtomicBool>,
+}
+
+// One randomness beacon pulse. The signature covers "COBRA_BEACON_PULSE" || index u64 BE ||
+// timestamp u64 BE || local random value || previous output value, and the output value is
+// SHA-512 over that message and the signature, so each pulse chains its predecessor.
+struct BeaconPulse {
+    index: u64,
+    timestamp: u64,
+    local_random: [u8; 64],
+    previous_output: [u8; 64],
+    signature: Vec<u8>,
+    signer: FalconKind,  // Identity at emission; a later rotation must not re-attribute old pulses
+    signer_pk: Vec<u8>,
+    output: [u8; 64],
+}
+
+impl BeaconPulse {
+    fn signed_message(index: u64, timestamp: u64, local_random: &[u8; 64], previous_output: &[u8; 64]) -> Vec<u8> {
+        let mut message = Vec::with_capacity(18 + 16 + 128);
+        message.extend_from_slice(b"COBRA_BEACON_PULSE");
+        message.extend_from_slice(&index.to_be_bytes());
+        message.extend_from_slice(&timestamp.to_be_bytes());
+        message.extend_from_slice(local_random);
+        message.extend_from_slice(previous_output);
+        message
+    }
+    
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "index": self.index,
+            "timestamp": self.timestamp,
+            "local_random_value": hex::encode(self.local_random),
+            "previous_output_value": hex::encode(self.previous_output),
+            "signature": hex::encode(&self.signature),
+            "signer": self.signer.name(),
+            "signer_pk": hex::encode(&self.signer_pk),
+            "output_value": hex::encode(self.output),
+        })
+    }
+}
+
+// NIST-beacon-style pulse chain, the last BEACON_MAX_PULSES kept for retrieval
+struct Beacon {
+    interval_secs: u64,
+    http_port: Option<u16>,
+    pulses: VecDeque<BeaconPulse>,
+    next_index: u64,
+    last_output: [u8; 64],  // Chains across pulses that have aged out
+    stop: Arc<AtomicBool>,
+    threads: Vec<thread::JoinHandle<()>>,  // Pulse thread and HTTP listener, joined on restart/stop
+}
+
+impl Beacon {
+    fn pulse(&self, index: u64) -> Option<&BeaconPulse> {
+        let first = self.pulses.front()?.index;
+        self.pulses.get(index.checked_sub(first)? as usize)
+    }
+    
+    /// Latest pulse emitted at or before `timestamp`
+    fn pulse_at(&self, timestamp: u64) -> Option<&BeaconPulse> {
+        self.pulses.iter().rev().find(|p| p.timestamp <= timestamp)
+    }
+}
+
+// Continuous output into a named pipe, read like a device file
+struct FifoOutput {
+    rate_limit: Option<u64>,  // Bytes per second
+    bytes_written: u64,
+    readers: u64,  // Times a reader opened the pipe
+    stop: Arc<AtomicBool>,
+}
+
+// Phone companion registered for /mobile_ingest
+struct MobileDevice {
+    key: Vec<u8>,           // Pre-shared HMAC-SHA256 attestation key
+    last_counter: u64,      // Highest accepted counter (replay protection)
+    accepted: u64,
+    rejected: u64,
+    consecutive_failures: u32,
+    quarantined: bool,
+}
+
+struct SharedState {
+    whitener: Whitener,
+    fortuna: FortunaAccumulator,
+    accumulator: Accumulator,
+    slow_pool: SlowPool,
+    pool_routes: HashMap<String, PoolRoute>,  // Unlisted sources feed both pools
+    display_pool: VecDeque<u8>,
+    history_raw_entropy: VecDeque<f64>,
+    history_whitened_entropy: VecDeque<f64>,
+    source_metrics: HashMap<String, SourceMetrics>,
+    source_health: HashMap<String, SourceHealth>,
+    health_params: HashMap<String, HealthParams>,  // Overrides; see HealthParams::default_for
+    research_mode: bool,  // Failing local samples are mixed in tainted and uncredited instead of dropped
+    ledger: EntropyLedger,
+    logs: VecDeque<String>,
+    total_bytes: usize,
+    sequence_id: u64,
+    net_mode: bool,
+    uplink_url: String,
+    falcon_kind: FalconKind,
+    falcon_pk: Vec<u8>,
+    falcon_sk: Vec<u8>,
+    pqc_active: bool,
+    harvester_states: HarvesterStates,
+    p2p_config: P2PConfig,  // NEW
+    hwrng_path: String,
+    ping_targets: Vec<String>,  // "host:port" probed with TCP connect
+    dns_resolvers: Vec<String>, // "ip:port" queried directly over UDP
+    dns_hostnames: Vec<String>,
+    #[cfg_attr(not(feature = "sdr"), allow(dead_code))]
+    sdr_frequency_hz: u32,
+    serial_device: Option<(String, u32)>,  // (path, baud)
+    gpio_pin: Option<u32>,  // sysfs GPIO number wired to the noise circuit
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    i2c_bus: String,
+    video_devices: Vec<u32>,     // Camera indices that should be harvested
+    video_threads: HashSet<u32>, // Camera indices with a live harvester thread
+    audio_devices: Vec<String>,     // Input device names ("" = host default)
+    audio_threads: HashSet<String>,
+    ntp_servers: Vec<String>,
+    fs_watch_paths: Vec<String>,
+    audio_mode: AudioMode,
+    mobile_devices: HashMap<String, MobileDevice>,
+    video_roi: Option<(u32, u32, u32, u32)>,  // (x, y, width, height) in pixels
+    video_dark_mode: bool,  // Max exposure/gain, extract sensor noise from frame differences
+    custom_sources: HashSet<String>,  // Registered from Python via register_source
+    debias_sources: HashMap<String, Debias>,  // Applied in the mixer before metrics/extraction
+    output_consumers: HashMap<String, OutputConsumer>,  // Per-consumer DRBGs, seeded from the pool
+    reseed_policy: ReseedPolicy,
+    seed_file: Option<String>,  // Saved on shutdown, restored on startup
+    streams_opened: u64,  // Numbers ChaosStream names
+    health_callbacks: Vec<Py<PyAny>>,  // Registered via on_health_event
+    health_events: VecDeque<HealthEvent>,  // Pending delivery
+    entropy_rate_alarm: Option<f64>,  // Credited bits/sec below which an event fires
+    entropy_floor_bits: f64,  // Failure mode below this much credited pool entropy
+    raw_captures: HashMap<String, RawCapture>,  // Per source, until sample_count is reached
+    failure_mode: Option<String>,  // Reason, as last seen by the mixer
+    kernel_feed: Option<KernelFeed>,
+    egd_server: Option<EgdServer>,
+    fifo_outputs: HashMap<String, FifoOutput>,  // By path
+    socket_service: Option<SocketService>,
+    file_sink: Option<FileSink>,
+    beacon: Option<Beacon>,
+    entropy_tokens_issued: u64,  // Sequence number of the last signed entropy token
+    entropy_blocks_exported: u64,
+    blocks_imported: HashMap<String, u64>,  // Highest imported block sequence per signer (hex pk)
+    uuid7_last: (u64, u16),  // Unix ms and 12-bit counter of the last UUIDv7, for monotonic ordering
+    vault: Vault,
+    identity_path: Option<String>,  // Sealed copy of the Falcon identity, when persistence is on
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum AudioMode {
+    Normal,
+    Ultrasonic,  // High-pass >18 kHz before extraction
+}
+
+#[derive(Clone)]
+struct HarvesterStates {
+    trng: bool,
+    audio: bool,
+    system: bool,
+    mouse: bool,
+    video: bool,
+    net: bool,
+    gpu: bool,
+    thermal: bool,
+    #[cfg_attr(not(feature = "bluetooth"), allow(dead_code))]
+    bt: bool,
+    wifi: bool,
+    hwrng: bool,
+    cpu_rng: bool,
+    ping: bool,
+    dns: bool,
+    irq: bool,
+    power: bool,
+    imu: bool,
+    #[cfg_attr(not(feature = "sdr"), allow(dead_code))]
+    sdr: bool,
+    serial: bool,
+    gpio: bool,
+    #[cfg_attr(not(feature = "token"), allow(dead_code))]
+    token: bool,
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    env: bool,
+    sched: bool,
+    mem: bool,
+    #[cfg_attr(not(feature = "screen"), allow(dead_code))]
+    screen: bool,
+    clock: bool,
+    fs: bool,
+    #[cfg_attr(not(feature = "usb"), allow(dead_code))]
+    usb: bool,
+    #[cfg_attr(not(feature = "gamepad"), allow(dead_code))]
+    gamepad: bool,
+    mains: bool,
+}
+
+impl Default for HarvesterStates {
+    fn default() -> Self {
+        Self {
+            trng: false,
+            audio: false,
+            system: false,
+            mouse: false,
+            video: false,
+            net: false,
+            gpu: false,
+            thermal: false,
+            bt: false,
+            wifi: false,
+            hwrng: false,
+            cpu_rng: false,
+            ping: false,
+            dns: false,
+            irq: false,
+            power: false,
+            imu: false,
+            sdr: false,
+            serial: false,
+            gpio: false,
+            token: false,
+            env: false,
+            sched: false,
+            mem: false,
+            screen: false,
+            clock: false,
+            fs: false,
+            usb: false,
+            gamepad: false,
+            mains: false,
+        }
+    }
+}
+
+// Raised by output and minting APIs while the engine is in failure mode
+create_exception!(chaos_magnet_core, EntropyFailureError, pyo3::exceptions::PyRuntimeError);
+
+#[pyclass]
+struct ChaosEngine {
+    state: Arc<Mutex<SharedState>>,
+    running: Arc<AtomicBool>,
+    tx_entropy: Sender<(String, Vec<u8>)>,
+}
+
+// Output stream from ChaosEngine.get_stream(): its own output consumer (STREAM_<n>), so it
+// gets the same reseeds, quotas and ledger debits as any other, generated with the GIL released
+#[pyclass]
+struct ChaosStream {
+    state: Arc<Mutex<SharedState>>,
+    name: String,  // Consumer name, also the DRBG personalization string
+    chunk_size: usize,  // Bytes per iteration
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// HEALTH CHECKS
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn check_health_rct(data: &[u8], cutoff: usize) -> bool {
+    rct_max_run(data) < cutoff
+}
+
+/// Longest run of identical bytes
+fn rct_max_run(data: &[u8]) -> usize {
+    if data.is_empty() { return 0; }
+    let mut max_repeats = 0usize;
+    let mut current_repeats = 1usize;
+    let mut last_val = data[0];
+    for &byte in &data[1..] {
+        if byte == last_val {
+            current_repeats += 1;
+        } else {
+            max_repeats = max_repeats.max(current_repeats);
+            current_repeats = 1;
+            last_val = byte;
+        }
+    }
+    max_repeats.max(current_repeats)
+}
+
+fn check_health_apt(data: &[u8], cutoff: f64) -> bool {
+    data.len() >= 10 && apt_max_fraction(data) < cutoff
+}
+
+/// Share of the most common byte value
+fn apt_max_fraction(data: &[u8]) -> f64 {
+    if data.is_empty() { return 0.0; }
+    let mut counts = [0usize; 256];
+    let mut max_count = 0usize;
+    for &b in data {
+        let c = counts[b as usize] + 1;
+        counts[b as usize] = c;
+        if c > max_count { max_count = c; }
+    }
+    max_count as f64 / data.len() as f64
+}
+
+/// Filter for grossly failed hardware (stuck or saturated output). The per-source tuned
+/// RCT/APT run in the mixer, so these limits must stay looser than any of them. Network input
+/// (P2P, mobile) always goes through this unconditionally.
+fn passes_health_checks(data: &[u8]) -> bool {
+    check_health_rct(data, GROSS_RCT_CUTOFF) && check_health_apt(data, GROSS_APT_CUTOFF)
+}
+
+/// Harvester-side gate for local sources: the gross filter, except that in research mode
+/// everything passes here and the mixer taints what its own tests reject
+fn admits_sample(state: &Mutex<SharedState>, data: &[u8]) -> bool {
+    passes_health_checks(data) || state.lock().research_mode
+}
+
+/// Known answers for the extractor: a published vector for the underlying primitive, then the
+/// conditioner itself over "abc" with counter 1 and key 0x0b*32 (catches framing mistakes).
+/// Toeplitz has no published vectors, so small matrices pin the bit ordering instead.
+fn extractor_known_answers(extractor: Extractor) -> Vec<(&'static str, bool)> {
+    let key = [0x0bu8; 32];
+    // Toeplitz is O(input x output) bits, so its vector is only 8 bytes wide
+    let out_len = if extractor == Extractor::Toeplitz { 8 } else { 32 };
+    let conditioned = hex::encode(extractor.condense(b"abc", 1, &key, out_len));
+    let primitive = match extractor {
+        Extractor::Sha256 => (
+            "SHA-256(\"abc\")",
+            hex::encode(Sha256::digest(b"abc")) == "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        ),
+        Extractor::Sha512 => (
+            "SHA-512(\"abc\")",
+            hex::encode(Sha512::digest(b"abc")) == "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+                                                   2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+        ),
+        Extractor::Sha3_256 => (
+            "SHA3-256(\"abc\")",
+            hex::encode(Sha3_256::digest(b"abc")) == "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532",
+        ),
+        Extractor::Blake3 => (
+            "BLAKE3(\"\")",
+            blake3::hash(b"").to_hex().as_str() == "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+        ),
+        Extractor::Shake256 => {
+            use sha3::digest::{ExtendableOutput, Update, XofReader};
+            let mut xof = sha3::Shake256::default();
+            xof.update(b"abc");
+            let mut out = [0u8; 32];
+            xof.finalize_xof().read(&mut out);
+            ("SHAKE256(\"abc\", 32)", hex::encode(out) == "483366601360a8771c6863080cc4114d8db44530f8f1e1ee4f94ea37e78b5739")
+        }
+        Extractor::HmacSha256 => {
+            let mut mac = HmacSha256::new_from_slice(b"Jefe").expect("HMAC accepts any key length");
+            mac.update(b"what do ya want for nothing?");
+            (
+                "HMAC-SHA256 RFC 4231 case 2",
+                hex::encode(mac.finalize().into_bytes()) == "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            )
+        }
+        Extractor::Toeplitz => (
+            "Toeplitz 8x8 / 16x16",
+            toeplitz_hash(&[0xA5], &[0x3C, 0x96], 1) == [0x17]
+                && toeplitz_hash(&[0xA5, 0x0F], &[0x3C, 0x96, 0x5A, 0xE1], 2) == [0x7A, 0x4C],
+        ),
+    };
+    
+    let expected = match extractor {
+        Extractor::Sha256 => "b02182b7d683e5925f77054403e9338d22ec1d0ff031867c01953fed4d89a596".to_string(),
+        Extractor::Sha512 => "7e8b447e4a017b914321529b0744aba20706d8f60605b7304d8247b2c7a63bb3\
+                              36bfa4bc6d8b968636a205bbe26d4c7c450aa3b94587c964c6d0720754737112".to_string(),
+        Extractor::Sha3_256 => "7dfec3e19a4c5ed104447e545deaf5ba77c9164e12d14ec110aeadd25840ec2c".to_string(),
+        Extractor::Shake256 => "06a0344331974b9e1a33d4ae9720e2ab3e730f9c7ea20fbf96a58200bc93fe22".to_string(),
+        Extractor::HmacSha256 => "68a5502c4b678d51b5b2125ba54d3d8cc6172c970036c6d37482654d6503924e".to_string(),
+        // The reference primitive passed above, so checking the framing against it is enough
+        Extractor::Blake3 => blake3::hash(&[b"abc".as_slice(), &1u64.to_le_bytes()].concat()).to_hex().to_string(),
+        Extractor::Toeplitz => "4cbc8ce3289a3f2c".to_string(),
+    };
+    
+    vec![primitive, ("Conditioner(\"abc\", counter 1)", conditioned == expected)]
+}
+
+/// Mean fraction of output bits that change when one input bit flips; ~0.5 for a sound extractor
+fn extractor_avalanche(conditioner: &Conditioner, trials: usize) -> (f64, f64, f64) {
+    let mut rng = rand::thread_rng();
+    let mut key = [0u8; 32];
+    rng.fill_bytes(&mut key);
+    let input_len = conditioner.input_bytes.min(64);
+    
+    let (mut sum, mut lo, mut hi) = (0.0, 1.0f64, 0.0f64);
+    for counter in 0..trials as u64 {
+        let mut input = vec![0u8; input_len];
+        rng.fill_bytes(&mut input);
+        let base = conditioner.extractor.condense(&input, counter, &key, conditioner.output_bytes);
+        
+        let bit = rng.gen_range(0..input_len * 8);
+        input[bit / 8] ^= 1 << (bit % 8);
+        let flipped = conditioner.extractor.condense(&input, counter, &key, conditioner.output_bytes);
+        
+        let changed: u32 = base.iter().zip(&flipped).map(|(a, b)| (a ^ b).count_ones()).sum();
+        let fraction = changed as f64 / (base.len() * 8) as f64;
+        sum += fraction;
+        lo = lo.min(fraction);
+        hi = hi.max(fraction);
+    }
+    (sum / trials as f64, lo, hi)
+}
+
+// FIPS 140-2 (4.9.1) power-up statistics over one 20,000-bit block, bits taken MSB first
+struct FipsBlockResult {
+    ones: u32,
+    poker: f64,
+    runs: [[u32; 6]; 2],  // [bit value][run length 1..=6+]
+    longest_run: u32,
+}
+
+const FIPS_MONOBIT_BOUNDS: (u32, u32) = (9725, 10275);
+const FIPS_POKER_BOUNDS: (f64, f64) = (2.16, 46.17);
+const FIPS_RUNS_BOUNDS: [(u32, u32); 6] = [
+    (2343, 2657), (1135, 1365), (542, 708), (251, 373), (111, 201), (111, 201),
+];
+const FIPS_LONG_RUN: u32 = 26;
+
+impl FipsBlockResult {
+    fn monobit_passed(&self) -> bool {
+        self.ones > FIPS_MONOBIT_BOUNDS.0 && self.ones < FIPS_MONOBIT_BOUNDS.1
+    }
+    
+    fn poker_passed(&self) -> bool {
+        self.poker > FIPS_POKER_BOUNDS.0 && self.poker < FIPS_POKER_BOUNDS.1
+    }
+    
+    fn runs_passed(&self) -> bool {
+        self.runs.iter().all(|counts| {
+            counts.iter().zip(FIPS_RUNS_BOUNDS.iter()).all(|(&n, &(lo, hi))| n >= lo && n <= hi)
+        })
+    }
+    
+    fn long_run_passed(&self) -> bool {
+        self.longest_run < FIPS_LONG_RUN
+    }
+}
+
+fn fips_block(block: &[u8]) -> FipsBlockResult {
+    let ones = block.iter().map(|b| b.count_ones()).sum();
+    
+    let mut nibbles = [0u32; 16];
+    for &b in block {
+        nibbles[(b >> 4) as usize] += 1;
+        nibbles[(b & 0x0F) as usize] += 1;
+    }
+    let segments = (block.len() * 2) as f64;
+    let poker = 16.0 / segments * nibbles.iter().map(|&f| (f as f64).powi(2)).sum::<f64>() - segments;
+    
+    let mut runs = [[0u32; 6]; 2];
+    let mut longest_run = 0;
+    let mut current: Option<(u8, u32)> = None;
+    let bits = block.iter().flat_map(|&b| (0..8).rev().map(move |i| (b >> i) & 1));
+    for bit in bits.map(Some).chain(std::iter::once(None)) {
+        match (current, bit) {
+            (Some((value, len)), Some(b)) if value == b => current = Some((value, len + 1)),
+            (prev, next) => {
+                if let Some((value, len)) = prev {
+                    runs[value as usize][(len.min(6) - 1) as usize] += 1;
+                    longest_run = longest_run.max(len);
+                }
+                current = next.map(|b| (b, 1));
+            }
+        }
+    }
+    
+    FipsBlockResult { ones, poker, runs, longest_run }
+}
+
+// SP 800-90B 5.1 permutation-testing statistics, in IID_STATISTIC_NAMES order
+const IID_STATISTIC_NAMES: [&str; 19] = [
+    "excursion", "directional_runs", "longest_directional_run", "increases_decreases",
+    "median_runs", "longest_median_run", "average_collision", "maximum_collision",
+    "periodicity_1", "periodicity_2", "periodicity_8", "periodicity_16", "periodicity_32",
+    "covariance_1", "covariance_2", "covariance_8", "covariance_16", "covariance_32",
+    "compression",
+];
+const IID_LAGS: [usize; 5] = [1, 2, 8, 16, 32];
+
+fn iid_statistics(data: &[u8], median: f64) -> Vec<f64> {
+    let n = data.len();
+    let mean = data.iter().map(|&x| x as f64).sum::<f64>() / n as f64;
+    let mut stats = Vec::with_capacity(IID_STATISTIC_NAMES.len());
+    
+    let mut cumulative = 0.0f64;
+    let mut excursion = 0.0f64;
+    for &x in data {
+        cumulative += x as f64 - mean;
+        excursion = excursion.max(cumulative.abs());
+    }
+    stats.push(excursion);
+    
+    // Runs of a +1/-1 sequence: (number of runs, longest run, count of +1)
+    let runs = |signs: &mut dyn Iterator<Item = bool>| {
+        let (mut count, mut longest, mut current, mut ups) = (0usize, 0usize, 0usize, 0usize);
+        let mut last = None;
+        for sign in signs {
+            if sign { ups += 1; }
+            if Some(sign) == last {
+                current += 1;
+            } else {
+                count += 1;
+                current = 1;
+                last = Some(sign);
+            }
+            longest = longest.max(current);
+        }
+        (count, longest, ups)
+    };
+    
+    let (count, longest, ups) = runs(&mut data.windows(2).map(|w| w[0] <= w[1]));
+    stats.push(count as f64);
+    stats.push(longest as f64);
+    stats.push(ups.max(n - 1 - ups) as f64);
+    
+    let (count, longest, _) = runs(&mut data.iter().map(|&x| x as f64 >= median));
+    stats.push(count as f64);
+    stats.push(longest as f64);
+    
+    let mut collisions = Vec::new();
+    let mut start = 0;
+    while start < n {
+        let mut seen = [false; 256];
+        let Some(offset) = data[start..].iter().position(|&x| std::mem::replace(&mut seen[x as usize], true)) else {
+            break;
+        };
+        collisions.push(offset + 1);
+        start += offset + 1;
+    }
+    let collision_mean = if collisions.is_empty() { 0.0 } else {
+        collisions.iter().sum::<usize>() as f64 / collisions.len() as f64
+    };
+    stats.push(collision_mean);
+    stats.push(collisions.iter().max().copied().unwrap_or(0) as f64);
+    
+    for lag in IID_LAGS {
+        stats.push(data.iter().zip(data.iter().skip(lag)).filter(|(a, b)| a == b).count() as f64);
+    }
+    for lag in IID_LAGS {
+        stats.push(data.iter().zip(data.iter().skip(lag)).map(|(&a, &b)| a as f64 * b as f64).sum());
+    }
+    
+    {
+        use std::io::Write;
+        let text = data.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(" ");
+        let mut encoder = bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::best());
+        let compressed = encoder.write_all(text.as_bytes()).and_then(|_| encoder.finish());
+        stats.push(compressed.map_or(0, |c| c.len()) as f64);
+    }
+    
+    stats
+}
+
+/// SP 800-90B 5.1 permutation testing: each statistic of the original sequence is ranked
+/// against IID_PERMUTATIONS shuffles of it. Returns (name, original, greater, equal, passed).
+fn iid_permutation_test(data: &[u8]) -> Vec<(&'static str, f64, usize, usize, bool)> {
+    let mut counts = [0usize; 256];
+    for &x in data { counts[x as usize] += 1; }
+    let mut seen = 0;
+    let mut median = 0.0;
+    for (value, &count) in counts.iter().enumerate() {
+        seen += count;
+        if seen * 2 >= data.len() {
+            median = value as f64;
+            break;
+        }
+    }
+    
+    let original = iid_statistics(data, median);
+    let mut greater = vec![0usize; original.len()];
+    let mut equal = vec![0usize; original.len()];
+    let mut shuffled = data.to_vec();
+    let mut rng = rand::thread_rng();
+    
+    for done in 1..=IID_PERMUTATIONS {
+        shuffled.shuffle(&mut rng);
+        for (i, stat) in iid_statistics(&shuffled, median).into_iter().enumerate() {
+            if stat > original[i] {
+                greater[i] += 1;
+            } else if stat == original[i] {
+                equal[i] += 1;
+            }
+        }
+        // Once every statistic has enough permutations on both sides it can't be rejected
+        let settled = (0..original.len())
+            .all(|i| greater[i] + equal[i] > IID_REJECT_RANK && done - greater[i] > IID_REJECT_RANK);
+        if settled {
+            break;
+        }
+    }
+    
+    IID_STATISTIC_NAMES.iter().enumerate()
+        .map(|(i, &name)| {
+            let passed = greater[i] + equal[i] > IID_REJECT_RANK
+                && greater[i] < IID_PERMUTATIONS - IID_REJECT_RANK;
+            (name, original[i], greater[i], equal[i], passed)
+        })
+        .collect()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// ENTROPY CALCULATIONS
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn get_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+fn get_timestamp_nanos() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos() as u64
+}
+
+fn shannon_entropy(data: &[u8]) -> f64 {
+    if data.is_empty() { return 0.0; }
+    let mut entropy = 0.0;
+    let mut counts = [0usize; 256];
+    for &b in data { counts[b as usize] += 1; }
+    let len = data.len() as f64;
+    for &count in &counts {
+        if count > 0 {
+            let p = count as f64 / len;
+            entropy -= p * p.log2();
+        }
+    }
+    entropy
+}
+
+fn min_entropy(data: &[u8]) -> f64 {
+    if data.is_empty() { return 0.0; }
+    let mut counts = [0usize; 256];
+    for &b in data { counts[b as usize] += 1; }
+    let max_count = counts.iter().max().copied().unwrap_or(0);
+    let max_prob = max_count as f64 / data.len() as f64;
+    if max_prob <= 0.0 || max_prob >= 1.0 { return 0.0; }
+    -max_prob.log2()
+}
+
+/// SP 800-90B 6.3.1 most-common-value estimate per byte, using the 99% upper bound on p
+fn mcv_min_entropy(data: &[u8]) -> f64 {
+    if data.len() < 2 { return 0.0; }
+    let mut counts = [0usize; 256];
+    for &b in data { counts[b as usize] += 1; }
+    let n = data.len() as f64;
+    let p_hat = counts.iter().max().copied().unwrap_or(0) as f64 / n;
+    let p_upper = (p_hat + 2.576 * (p_hat * (1.0 - p_hat) / (n - 1.0)).sqrt()).min(1.0);
+    -p_upper.log2()
+}
+
+/// SP 800-90B 6.3.2 collision estimate, per byte, over the bitstring (MSB first). A binary
+/// source's collision times are 2 or 3 with mean 2 + 2p(1 - p), so the lower bound on the mean
+/// gives p directly.
+fn collision_min_entropy(data: &[u8]) -> f64 {
+    let bits: Vec<u8> = data.iter().flat_map(|&b| (0..8).rev().map(move |i| (b >> i) & 1)).collect();
+    let mut times = Vec::with_capacity(bits.len() / 2);
+    let mut i = 0;
+    while i + 2 < bits.len() {
+        let t = if bits[i] == bits[i + 1] { 2 } else { 3 };
+        times.push(t as f64);
+        i += t;
+    }
+    if times.len() < 2 { return 0.0; }
+    
+    let v = times.len() as f64;
+    let mean = times.iter().sum::<f64>() / v;
+    let sigma = (times.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / (v - 1.0)).sqrt();
+    let mean_lower = mean - 2.576 * sigma / v.sqrt();
+    
+    let pq = ((mean_lower - 2.0) / 2.0).clamp(0.0, 0.25);
+    let p = 0.5 + (0.25 - pq).sqrt();
+    (-p.log2()).min(1.0) * 8.0
+}
+
+/// SP 800-90B 6.3.3 Markov estimate, per byte: a first-order model over the bitstring (MSB
+/// first), scored by its most likely 128-bit sequence
+fn markov_min_entropy(data: &[u8]) -> f64 {
+    if data.len() < 2 { return 0.0; }
+    let bits: Vec<usize> = data.iter().flat_map(|&b| (0..8).rev().map(move |i| ((b >> i) & 1) as usize)).collect();
+    let ones = bits.iter().filter(|&&b| b == 1).count();
+    let p1 = ones as f64 / bits.len() as f64;
+    let p0 = 1.0 - p1;
+    
+    let mut transitions = [[0usize; 2]; 2];
+    for pair in bits.windows(2) {
+        transitions[pair[0]][pair[1]] += 1;
+    }
+    let row = |from: usize, to: usize| {
+        let total = transitions[from][0] + transitions[from][1];
+        if total == 0 { 0.0 } else { transitions[from][to] as f64 / total as f64 }
+    };
+    let (p00, p01, p10, p11) = (row(0, 0), row(0, 1), row(1, 0), row(1, 1));
+    
+    let p_max = [
+        p0 * p00.powi(127),
+        p0 * p01.powi(64) * p10.powi(63),
+        p0 * p01 * p11.powi(126),
+        p1 * p10 * p00.powi(126),
+        p1 * p10.powi(64) * p01.powi(63),
+        p1 * p11.powi(127),
+    ].into_iter().fold(0.0, f64::max);
+    
+    if p_max <= 0.0 { return 8.0; }
+    (-p_max.log2() / 128.0).min(1.0) * 8.0
+}
+
+/// SP 800-90B 6.3.4 compression (Maurer universal statistic) estimate, per byte. Distances
+/// between repeats of 6-bit blocks see multi-byte structure the byte histograms are blind to.
+fn compression_min_entropy(data: &[u8]) -> f64 {
+    let b = COMPRESSION_BLOCK_BITS;
+    let d = COMPRESSION_DICTIONARY_BLOCKS;
+    let bits: Vec<u8> = data.iter().flat_map(|&byte| (0..8).rev().map(move |i| (byte >> i) & 1)).collect();
+    let blocks: Vec<usize> = bits.chunks_exact(b)
+        .map(|chunk| chunk.iter().fold(0usize, |acc, &bit| (acc << 1) | bit as usize))
+        .collect();
+    if blocks.len() <= d + 1 { return 0.0; }
+    
+    let mut last_seen = vec![0usize; 1 << b];
+    for (i, &block) in blocks[..d].iter().enumerate() {
+        last_seen[block] = i + 1;
+    }
+    let mut log_distances = Vec::with_capacity(blocks.len() - d);
+    for (i, &block) in blocks.iter().enumerate().skip(d) {
+        let position = i + 1;
+        let distance = if last_seen[block] != 0 { position - last_seen[block] } else { position };
+        log_distances.push((distance as f64).log2());
+        last_seen[block] = position;
+    }
+    
+    let v = log_distances.len() as f64;
+    let mean = log_distances.iter().sum::<f64>() / v;
+    let variance = log_distances.iter().map(|x| x * x).sum::<f64>() / (v - 1.0) - mean * mean;
+    let sigma = 0.5907 * variance.max(0.0).sqrt();
+    let mean_lower = mean - 2.576 * sigma / v.sqrt();
+    
+    // Expected statistic when one block value has probability z: G(z) summed over the test
+    // blocks, with the inner sum over distances carried forward instead of recomputed
+    let total = blocks.len();
+    let g = |z: f64| {
+        if z <= 0.0 { return 0.0; }
+        let (mut inner, mut sum) = (0.0f64, 0.0f64);
+        let mut decay = 1.0f64;  // (1 - z)^(u - 1)
+        for t in 1..=total {
+            if t > d {
+                sum += inner + (t as f64).log2() * z * decay;
+            }
+            inner += (t as f64).log2() * z * z * decay;
+            decay *= 1.0 - z;
+        }
+        sum / v
+    };
+    let n = (1usize << b) as f64;
+    let expected = |p: f64| g(p) + (n - 1.0) * g((1.0 - p) / (n - 1.0));
+    
+    let (mut lo, mut hi) = (1.0 / n, 1.0f64);
+    if mean_lower >= expected(lo) { return 8.0; }
+    for _ in 0..40 {
+        let mid = (lo + hi) / 2.0;
+        if expected(mid) > mean_lower { lo = mid; } else { hi = mid; }
+    }
+    (-hi.log2() / b as f64).min(1.0) * 8.0
+}
+
+/// SP 800-90B 6.3.8 lag prediction estimate per byte: predicts each sample from the sample
+/// `d` back, for the lag that has been right most often so far. Returns the min-entropy and
+/// that winning lag, which points at periodic structure (e.g. a harvester's polling cadence).
+fn lag_prediction_min_entropy(data: &[u8]) -> (f64, usize) {
+    if data.len() < LAG_PREDICTOR_DEPTH + 2 { return (8.0, 0); }
+    let mut scoreboard = [0u32; LAG_PREDICTOR_DEPTH];
+    let mut winner = 0usize;  // Index d - 1
+    let (mut correct, mut run, mut longest_run) = (0usize, 0usize, 0usize);
+    
+    for i in 1..data.len() {
+        if i > winner && data[i - winner - 1] == data[i] {
+            correct += 1;
+            run += 1;
+            longest_run = longest_run.max(run);
+        } else {
+            run = 0;
+        }
+        for d in 0..LAG_PREDICTOR_DEPTH.min(i) {
+            if data[i - d - 1] == data[i] {
+                scoreboard[d] += 1;
+                if scoreboard[d] >= scoreboard[winner] {
+                    winner = d;
+                }
+            }
+        }
+    }
+    
+    (predictor_min_entropy(data.len() - 1, correct, longest_run), winner + 1)
+}
+
+/// SP 800-90B 6.3.7-6.3.10 min-entropy from a predictor's results: the larger of the 99% upper
+/// bound on its global accuracy and the accuracy implied by its longest run of correct guesses
+fn predictor_min_entropy(predictions: usize, correct: usize, longest_run: usize) -> f64 {
+    let n = predictions as f64;
+    let p_global = correct as f64 / n;
+    let p_global_upper = if correct == 0 {
+        1.0 - 0.01f64.powf(1.0 / n)
+    } else {
+        (p_global + 2.576 * (p_global * (1.0 - p_global) / (n - 1.0)).sqrt()).min(1.0)
+    };
+    
+    // P_local: the p at which a longest run of r - 1 has probability 0.99
+    let r = (longest_run + 1) as f64;
+    let no_run_probability = |p: f64| {
+        let q = 1.0 - p;
+        let mut x = 1.0f64;
+        for _ in 0..10 {
+            x = 1.0 + q * p.powf(r) * x.powf(r + 1.0);
+        }
+        (1.0 - p * x) / ((r + 1.0 - r * x) * q) / x.powf(n + 1.0)
+    };
+    let (mut lo, mut hi) = (0.0f64, 1.0f64);
+    for _ in 0..64 {
+        let mid = (lo + hi) / 2.0;
+        if no_run_probability(mid) > 0.99 { lo = mid; } else { hi = mid; }
+    }
+    
+    let p = p_global_upper.max(hi).max(1.0 / 256.0);
+    (-p.log2()).clamp(0.0, 8.0)
+}
+
+/// log2(2^a + 2^b) without leaving the log domain
+fn log2_add(a: f64, b: f64) -> f64 {
+    let (hi, lo) = if a > b { (a, b) } else { (b, a) };
+    if lo.is_infinite() && lo < 0.0 { return hi; }
+    hi + (1.0 + (lo - hi).exp2()).log2()
+}
+
+/// SP 800-90B 3.1.5.1.2 Output_Entropy(n_in, n_out, nw, h_in) for a vetted conditioning
+/// component. Evaluated in log2 so n_in of thousands of bits doesn't overflow.
+fn output_entropy(n_in: f64, n_out: f64, nw: f64, h_in: f64) -> f64 {
+    let h_in = h_in.min(n_in);
+    if h_in <= 0.0 { return 0.0; }
+    let n = n_out.min(nw);
+    
+    // P_high = 2^-h_in, P_low = (1 - P_high) / (2^n_in - 1)
+    let log_p_high = -h_in;
+    let log_denom = if n_in > 64.0 { n_in } else { (n_in.exp2() - 1.0).log2() };
+    let log_p_low = (1.0 - log_p_high.exp2()).log2() - log_denom;
+    
+    // psi = 2^(n_in - n) P_low + P_high;  U = 2^(n_in - n) + sqrt(2 n 2^(n_in - n) ln 2);  omega = U P_low
+    let a = n_in - n;
+    let log_psi = log2_add(a + log_p_low, log_p_high);
+    let log_u = a + (1.0 + (2.0 * n * std::f64::consts::LN_2).sqrt() * (-a / 2.0).exp2()).log2();
+    let log_omega = log_u + log_p_low;
+    
+    (-log_psi.max(log_omega)).clamp(0.0, n_out)
+}
+
+/// Von Neumann debiasing over the bit stream (MSB first): 01 -> 0, 10 -> 1, 00/11 dropped
+fn von_neumann_debias(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() / 4);
+    let mut acc = 0u8;
+    let mut nbits = 0u8;
+    for &byte in data {
+        for pair in 0..4 {
+            let a = (byte >> (7 - pair * 2)) & 1;
+            let b = (byte >> (6 - pair * 2)) & 1;
+            if a != b {
+                acc = (acc << 1) | a;
+                nbits += 1;
+                if nbits == 8 {
+                    out.push(acc);
+                    acc = 0;
+                    nbits = 0;
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Iterated XOR-fold: each round XORs adjacent byte pairs, so a per-bit bias e becomes 2e^2
+fn xor_fold(data: &[u8], rounds: u8) -> Vec<u8> {
+    let mut out = data.to_vec();
+    for _ in 0..rounds {
+        out = out.chunks_exact(2).map(|p| p[0] ^ p[1]).collect();
+    }
+    out
+}
+
+/// Toeplitz-matrix universal hash: output bit i = XOR_j T[i][j] & x[j], where the
+/// matrix is constant along diagonals, T[i][j] = seed[i - j + n - 1]. Needs n + m - 1 seed bits.
+fn toeplitz_hash(input: &[u8], seed: &[u8], out_len: usize) -> Vec<u8> {
+    let n = input.len() * 8;
+    let m = out_len * 8;
+    let bit = |data: &[u8], k: usize| (data[k / 8] >> (7 - k % 8)) & 1;
+    
+    let mut out = vec![0u8; out_len];
+    for i in 0..m {
+        let mut acc = 0u8;
+        for j in 0..n {
+            acc ^= bit(seed, i + n - 1 - j) & bit(input, j);
+        }
+        out[i / 8] |= acc << (7 - i % 8);
+    }
+    out
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// OUTPUT GENERATOR (DRBG)
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Squeezes arbitrary input to exactly seedlen bytes: hashed if longer, zero-padded if shorter
+fn fit_seed_len(data: &[u8]) -> [u8; CTR_DRBG_SEED_LEN] {
+    let mut out = [0u8; CTR_DRBG_SEED_LEN];
+    if data.len() > CTR_DRBG_SEED_LEN {
+        out.copy_from_slice(&Sha512::digest(data)[..CTR_DRBG_SEED_LEN]);
+    } else {
+        out[..data.len()].copy_from_slice(data);
+    }
+    out
+}
+
+/// 800-90A no-df seed material: entropy XOR extra (personalization or additional input)
+fn ctr_drbg_seed_material(entropy: &[u8], extra: &[u8]) -> [u8; CTR_DRBG_SEED_LEN] {
+    let mut seed = fit_seed_len(entropy);
+    for (s, e) in seed.iter_mut().zip(fit_seed_len(extra)) { *s ^= e; }
+    seed
+}
+
+/// Pool-derived DRBG entropy input; domain-separated so outputs never expose pool bytes
+fn drbg_entropy_from_pool(pool: &[u8; 32], sequence_id: u64) -> [u8; CTR_DRBG_SEED_LEN] {
+    let mut hasher = Sha512::new();
+    hasher.update(b"CHAOS_MAGNET_DRBG_SEED");
+    hasher.update(pool);
+    hasher.update(sequence_id.to_le_bytes());
+    hasher.update(get_timestamp_nanos().to_le_bytes());
+    fit_seed_len(&hasher.finalize())
+}
+
+/// SP 800-90A deterministic random bit generator
+trait Drbg: Send {
+    fn name(&self) -> &'static str;
+    fn reseed(&mut self, entropy: &[u8], additional: &[u8]);
+    /// Fails once the reseed interval is exhausted; the caller must reseed first
+    fn generate(&mut self, out: &mut [u8], additional: &[u8]) -> Result<(), &'static str>;
+    fn reseed_counter(&self) -> u64;
+    fn reseeds(&self) -> u64;
+    fn last_reseed(&self) -> Instant;
+    
+    fn needs_reseed(&self) -> bool {
+        self.reseed_counter() > DRBG_RESEED_INTERVAL
+    }
+}
+
+/// SP 800-90A CTR_DRBG with AES-256 and no derivation function; the seed material is
+/// already conditioned by the pool, so the df would add nothing
+struct CtrDrbg {
+    key: [u8; 32],
+    v: [u8; 16],
+    reseed_counter: u64,
+    reseeds: u64,
+    last_reseed: Instant,
+}
+
+impl CtrDrbg {
+    fn instantiate(entropy: &[u8], personalization: &[u8]) -> Self {
+        let mut drbg = Self {
+            key: [0u8; 32],
+            v: [0u8; 16],
+            reseed_counter: 1,
+            reseeds: 0,
+            last_reseed: Instant::now(),
+        };
+        drbg.update(&ctr_drbg_seed_material(entropy, personalization));
+        drbg
+    }
+    
+    fn increment_v(&mut self) {
+        self.v = u128::from_be_bytes(self.v).wrapping_add(1).to_be_bytes();
+    }
+    
+    /// Keystream blocks AES_K(V+1), AES_K(V+2), ... into `out`
+    fn fill_blocks(&mut self, out: &mut [u8]) {
+        use aes::cipher::{BlockEncrypt, KeyInit};
+        let cipher = aes::Aes256::new(&self.key.into());
+        for chunk in out.chunks_mut(16) {
+            self.increment_v();
+            let mut block = aes::Block::from(self.v);
+            cipher.encrypt_block(&mut block);
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+    }
+    
+    fn update(&mut self, provided: &[u8; CTR_DRBG_SEED_LEN]) {
+        let mut temp = [0u8; CTR_DRBG_SEED_LEN];
+        self.fill_blocks(&mut temp);
+        for (t, p) in temp.iter_mut().zip(provided) { *t ^= p; }
+        self.key.copy_from_slice(&temp[..32]);
+        self.v.copy_from_slice(&temp[32..]);
+    }
+}
+
+impl Drbg for CtrDrbg {
+    fn name(&self) -> &'static str { "CTR-DRBG (AES-256)" }
+    
+    fn reseed(&mut self, entropy: &[u8], additional: &[u8]) {
+        self.update(&ctr_drbg_seed_material(entropy, additional));
+        self.reseed_counter = 1;
+        self.reseeds += 1;
+        self.last_reseed = Instant::now();
+    }
+    
+    /// Requests larger than the 800-90A limit are split into several generate calls
+    fn generate(&mut self, out: &mut [u8], additional: &[u8]) -> Result<(), &'static str> {
+        for request in out.chunks_mut(DRBG_MAX_REQUEST) {
+            if self.needs_reseed() {
+                return Err("CTR-DRBG reseed required");
+            }
+            let additional = fit_seed_len(additional);
+            if additional != [0u8; CTR_DRBG_SEED_LEN] {
+                self.update(&additional);
+            }
+            self.fill_blocks(request);
+            // Backtracking resistance: the state that produced this output is gone
+            self.update(&additional);
+            self.reseed_counter += 1;
+        }
+        Ok(())
+    }
+    
+    fn reseed_counter(&self) -> u64 { self.reseed_counter }
+    fn reseeds(&self) -> u64 { self.reseeds }
+    fn last_reseed(&self) -> Instant { self.last_reseed }
+}
+
+/// SP 800-90A HMAC_DRBG with SHA-256
+struct HmacDrbg {
+    key: [u8; 32],
+    v: [u8; 32],
+    reseed_counter: u64,
+    reseeds: u64,
+    last_reseed: Instant,
+}
+
+impl HmacDrbg {
+    fn instantiate(entropy: &[u8], personalization: &[u8]) -> Self {
+        let mut drbg = Self {
+            key: [0x00; 32],
+            v: [0x01; 32],
+            reseed_counter: 1,
+            reseeds: 0,
+            last_reseed: Instant::now(),
+        };
+        drbg.update(&[entropy, personalization]);
+        drbg
+    }
+    
+    fn hmac(key: &[u8; 32], parts: &[&[u8]]) -> [u8; 32] {
+        let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts any key length");
+        for part in parts { mac.update(part); }
+        mac.finalize().into_bytes().into()
+    }
+    
+    /// HMAC_DRBG_Update; `provided` is concatenated, empty means no provided data
+    fn update(&mut self, provided: &[&[u8]]) {
+        let has_data = provided.iter().any(|p| !p.is_empty());
+        for round in [0x00u8, 0x01] {
+            if round == 0x01 && !has_data { break; }
+            let mut parts: Vec<&[u8]> = vec![&self.v, &[round]];
+            parts.extend_from_slice(provided);
+            self.key = Self::hmac(&self.key, &parts);
+            self.v = Self::hmac(&self.key, &[&self.v]);
+        }
+    }
+}
+
+impl Drbg for HmacDrbg {
+    fn name(&self) -> &'static str { "HMAC-DRBG (SHA-256)" }
+    
+    fn reseed(&mut self, entropy: &[u8], additional: &[u8]) {
+        self.update(&[entropy, additional]);
+        self.reseed_counter = 1;
+        self.reseeds += 1;
+        self.last_reseed = Instant::now();
+    }
+    
+    fn generate(&mut self, out: &mut [u8], additional: &[u8]) -> Result<(), &'static str> {
+        for request in out.chunks_mut(DRBG_MAX_REQUEST) {
+            if self.needs_reseed() {
+                return Err("HMAC-DRBG reseed required");
+            }
+            if !additional.is_empty() {
+                self.update(&[additional]);
+            }
+            for chunk in request.chunks_mut(32) {
+                self.v = Self::hmac(&self.key, &[&self.v]);
+                chunk.copy_from_slice(&self.v[..chunk.len()]);
+            }
+            self.update(&[additional]);
+            self.reseed_counter += 1;
+        }
+        Ok(())
+    }
+    
+    fn reseed_counter(&self) -> u64 { self.reseed_counter }
+    fn reseeds(&self) -> u64 { self.reseeds }
+    fn last_reseed(&self) -> Instant { self.last_reseed }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum DrbgKind {
+    Ctr,
+    Hmac,
+}
+
+impl DrbgKind {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().replace(['-', '_'], "").as_str() {
+            "ctr" | "ctrdrbg" | "aes" => Some(DrbgKind::Ctr),
+            "hmac" | "hmacdrbg" | "sha256" => Some(DrbgKind::Hmac),
+            _ => None,
+        }
+    }
+    
+    fn instantiate(&self, entropy: &[u8], personalization: &[u8]) -> Box<dyn Drbg> {
+        match self {
+            DrbgKind::Ctr => Box::new(CtrDrbg::instantiate(entropy, personalization)),
+            DrbgKind::Hmac => Box::new(HmacDrbg::instantiate(entropy, personalization)),
+        }
+    }
+}
+
+// When output generators go back to the pool. Any enabled trigger is enough; the DRBG's own
+// reseed interval always applies on top.
+#[derive(Clone)]
+struct ReseedPolicy {
+    max_age_secs: Option<u64>,      // Time since the last reseed
+    max_output_bytes: Option<u64>,  // Bytes generated since the last reseed
+    min_credit_bits: Option<f64>,   // Ledger credits gathered since the last reseed
+}
+
+impl Default for ReseedPolicy {
+    fn default() -> Self {
+        Self {
+            max_age_secs: Some(DRBG_RESEED_SECS),
+            max_output_bytes: None,
+            min_credit_bits: None,
+        }
+    }
+}
+
+impl ReseedPolicy {
+    /// Reason the consumer is due for a reseed, if it is
+    fn due(&self, consumer: &OutputConsumer, credited_bits: f64) -> Option<&'static str> {
+        let drbg = consumer.drbg.as_ref()?;
+        if drbg.needs_reseed() {
+            return Some("interval");
+        }
+        if self.max_age_secs.is_some_and(|secs| drbg.last_reseed().elapsed() >= Duration::from_secs(secs)) {
+            return Some("time");
+        }
+        if self.max_output_bytes.is_some_and(|bytes| consumer.bytes_since_reseed >= bytes) {
+            return Some("bytes");
+        }
+        if self.min_credit_bits.is_some_and(|bits| credited_bits - consumer.credit_mark >= bits) {
+            return Some("credits");
+        }
+        None
+    }
+}
+
+// Per-consumer output limits, charged before every request so one greedy consumer can't drain
+// the pool ahead of key minting. The per-reseed budget refills each time the pool reseeds,
+// i.e. whenever fresh entropy arrives.
+#[derive(Default)]
+struct OutputQuota {
+    bytes_per_sec: Option<u64>,
+    bytes_per_reseed: Option<u64>,
+    window_start: Option<Instant>,  // Current one-second rate window
+    window_bytes: u64,
+    reseed_mark: u64,  // Pool reseed count the per-reseed budget belongs to
+    reseed_bytes: u64,
+    denied_requests: u64,
+}
+
+impl OutputQuota {
+    /// Charges `n` bytes, or says which limit the request would break
+    fn charge(&mut self, n: usize, pool_reseeds: u64) -> Result<(), String> {
+        if !self.window_start.is_some_and(|t| t.elapsed() < Duration::from_secs(1)) {
+            self.window_start = Some(Instant::now());
+            self.window_bytes = 0;
+        }
+        if self.reseed_mark != pool_reseeds {
+            self.reseed_mark = pool_reseeds;
+            self.reseed_bytes = 0;
+        }
+        
+        let n = n as u64;
+        if let Some(limit) = self.bytes_per_sec.filter(|&l| self.window_bytes + n > l) {
+            self.denied_requests += 1;
+            return Err(format!("Output quota exceeded ({} bytes/s)", limit));
+        }
+        if let Some(limit) = self.bytes_per_reseed.filter(|&l| self.reseed_bytes + n > l) {
+            self.denied_requests += 1;
+            return Err(format!("Output quota exceeded ({} bytes per pool reseed)", limit));
+        }
+        self.window_bytes += n;
+        self.reseed_bytes += n;
+        Ok(())
+    }
+}
+
+/// A named output consumer with its own generator and reseed schedule
+struct OutputConsumer {
+    kind: DrbgKind,
+    drbg: Option<Box<dyn Drbg>>,  // None until the pool has seen its first reseed
+    prediction_resistance: bool,  // Reseed from the pool before every request
+    quota: OutputQuota,
+    bytes_generated: u64,
+    bytes_since_reseed: u64,
+    credit_mark: f64,  // Ledger total_credited_bits at the last reseed
+    last_reseed_reason: &'static str,
+    last_reseed_at: u64,
+}
+
+impl OutputConsumer {
+    fn new(kind: DrbgKind) -> Self {
+        Self {
+            kind,
+            drbg: None,
+            prediction_resistance: false,
+            quota: OutputQuota::default(),
+            bytes_generated: 0,
+            bytes_since_reseed: 0,
+            credit_mark: 0.0,
+            last_reseed_reason: "none",
+            last_reseed_at: 0,
+        }
+    }
+    
+    /// Instantiates or reseeds from `entropy` and records why
+    fn seed(&mut self, entropy: &[u8], personalization: &[u8], reason: &'static str, credited_bits: f64) {
+        match self.drbg.as_mut() {
+            Some(drbg) => drbg.reseed(entropy, personalization),
+            None => self.drbg = Some(self.kind.instantiate(entropy, personalization)),
+        }
+        self.bytes_since_reseed = 0;
+        self.credit_mark = credited_bits;
+        self.last_reseed_reason = reason;
+        self.last_reseed_at = get_timestamp();
+    }
+}
+
+/// Instantiates pending generators and reseeds any the policy says are due, from the current pool
+fn refresh_output_consumers(state: &mut SharedState) {
+    if state.fortuna.reseed_count == 0 { return; }
+    
+    let credited = state.ledger.total_credited_bits;
+    let mut instantiated = Vec::new();
+    let mut drew_from_pool = false;
+    for (name, consumer) in state.output_consumers.iter_mut() {
+        let reason = if consumer.drbg.is_none() {
+            instantiated.push(name.clone());
+            "instantiate"
+        } else {
+            match state.reseed_policy.due(consumer, credited) {
+                Some(reason) => reason,
+                None => continue,
+            }
+        };
+        let entropy = drbg_entropy_from_pool(&state.accumulator.pool, state.sequence_id);
+        consumer.seed(&entropy, name.as_bytes(), reason, credited);
+        drew_from_pool = true;
+    }
+    if drew_from_pool {
+        state.accumulator.ratchet();
+    }
+    
+    for name in instantiated {
+        let kind = state.output_consumers[&name].drbg.as_ref().map(|d| d.name()).unwrap_or("?");
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] DRBG: {} instantiated for {}", ts, kind, name);
+        if state.logs.len() >= 20 { state.logs.pop_front(); }
+        state.logs.push_back(msg);
+    }
+}
+
+/// `n` bytes from the PYTHON consumer, refused in failure mode
+fn python_output(state: &Arc<Mutex<SharedState>>, n: usize) -> PyResult<Vec<u8>> {
+    let mut lock = state.lock();
+    check_output_allowed(&lock)?;
+    consumer_generate(&mut lock, "PYTHON", n).map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+/// One uniform u64 from the PYTHON consumer
+fn python_u64(state: &Arc<Mutex<SharedState>>) -> PyResult<u64> {
+    let mut bytes = python_output(state, 8)?;
+    let value = u64::from_le_bytes(bytes[..8].try_into().unwrap_or([0u8; 8]));
+    bytes.zeroize();
+    Ok(value)
+}
+
+/// Uniform in 0..span by rejection: draws at or above the largest multiple of `span` that fits
+/// in a u64 are redrawn, so there's no modulo bias
+fn python_below(state: &Arc<Mutex<SharedState>>, span: u64) -> PyResult<u64> {
+    let zone = (u64::MAX / span) * span;
+    loop {
+        let x = python_u64(state)?;
+        if x < zone {
+            return Ok(x % span);
+        }
+    }
+}
+
+/// Named character sets for generate_password; anything else is taken as the literal charset
+fn password_charset(name: &str) -> Vec<char> {
+    let set: String = match name {
+        "digits" => ('0'..='9').collect(),
+        "hex" => ('0'..='9').chain('a'..='f').collect(),
+        "alpha" => ('a'..='z').chain('A'..='Z').collect(),
+        "alnum" => ('a'..='z').chain('A'..='Z').chain('0'..='9').collect(),
+        "printable" => ('!'..='~').collect(),
+        literal => literal.to_string(),
+    };
+    let mut chars: Vec<char> = set.chars().collect();
+    chars.sort_unstable();
+    chars.dedup();
+    chars
+}
+
+/// EFF's published lists are compiled in; any other `wordlist` is a path to a user file
+fn wordlist_text(wordlist: &str) -> std::io::Result<std::borrow::Cow<'static, str>> {
+    Ok(match wordlist {
+        "eff_large" => include_str!("../wordlists/eff_large_wordlist.txt").into(),
+        "eff_short" => include_str!("../wordlists/eff_short_wordlist_1.txt").into(),
+        "eff_short2" => include_str!("../wordlists/eff_short_wordlist_2_0.txt").into(),
+        path => fs::read_to_string(path)?.into(),
+    })
+}
+
+/// Words from an EFF-style list ("11111<TAB>abacus") or one word per line, deduplicated so the
+/// reported entropy holds. `wordlist` is an EFF list name ("eff_large", "eff_short",
+/// "eff_short2") or a path.
+fn load_wordlist(wordlist: &str) -> std::io::Result<Vec<String>> {
+    let mut words: Vec<String> = wordlist_text(wordlist)?
+        .lines()
+        .filter_map(|line| line.split_whitespace().last())
+        .map(str::to_string)
+        .collect();
+    words.sort_unstable();
+    words.dedup();
+    Ok(words)
+}
+
+/// Fisher-Yates over the PYTHON consumer: swap targets for positions n-1, n-2, ... (`k` of
+/// them), each uniform in 0..=i by rejection. Draws are buffered so a large shuffle doesn't take
+/// the engine lock per element.
+fn fisher_yates_targets(state: &Arc<Mutex<SharedState>>, n: usize, k: usize) -> PyResult<Vec<usize>> {
+    let mut targets = Vec::with_capacity(k);
+    let mut draws: Vec<u8> = Vec::new();
+    let mut pos = 0;
+    for step in 0..k {
+        let span = (n - step) as u64;
+        let zone = (u64::MAX / span) * span;
+        loop {
+            if pos + 8 > draws.len() {
+                draws.zeroize();
+                draws = python_output(state, ((k - step) * 8).clamp(64, FILE_CHUNK_BYTES))?;
+                pos = 0;
+            }
+            let x = u64::from_le_bytes(draws[pos..pos + 8].try_into().unwrap_or([0u8; 8]));
+            pos += 8;
+            if x < zone {
+                targets.push((x % span) as usize);
+                break;
+            }
+        }
+    }
+    draws.zeroize();
+    Ok(targets)
+}
+
+/// RFC 5869 HKDF-SHA256; `len` must be at most 255 * 32
+fn hkdf_sha256(salt: &[u8], ikm: &[u8], info: &[u8], len: usize) -> Vec<u8> {
+    let mut extract = HmacSha256::new_from_slice(salt).expect("HMAC accepts any key length");
+    extract.update(ikm);
+    let mut prk: [u8; 32] = extract.finalize().into_bytes().into();
+    
+    let mut okm = Vec::with_capacity(len);
+    let mut block: Vec<u8> = Vec::new();
+    for counter in 1..=len.div_ceil(32) as u8 {
+        let mut expand = HmacSha256::new_from_slice(&prk).expect("HMAC accepts any key length");
+        expand.update(&block);
+        expand.update(info);
+        expand.update(&[counter]);
+        block.zeroize();
+        block = expand.finalize().into_bytes().to_vec();
+        okm.extend_from_slice(&block);
+    }
+    block.zeroize();
+    prk.zeroize();
+    okm.truncate(len);
+    okm
+}
+
+/// Parses and checks an entropy block file (magic | sequence u64 BE | timestamp u64 BE | length
+/// u32 BE | data | Falcon signature over everything before it) against `signer_pk`;
+/// returns (data, sequence, timestamp)
+fn read_entropy_block(path: &str, signer_pk: &[u8]) -> Result<(Vec<u8>, u64, u64), String> {
+    let file = fs::read(path).map_err(|e| e.to_string())?;
+    if file.len() < ENTROPY_BLOCK_HEADER_LEN || &file[..8] != ENTROPY_BLOCK_MAGIC {
+        return Err("not an entropy block".to_string());
+    }
+    let field = |at: usize| u64::from_be_bytes(file[at..at + 8].try_into().unwrap_or([0u8; 8]));
+    let (sequence, timestamp) = (field(8), field(16));
+    let len = u32::from_be_bytes(file[24..28].try_into().unwrap_or([0u8; 4])) as usize;
+    let signed_len = ENTROPY_BLOCK_HEADER_LEN + len;
+    if file.len() <= signed_len {
+        return Err("truncated entropy block".to_string());
+    }
+    
+    let kind = FalconKind::from_public_key(signer_pk).ok_or("signer_pk is not a Falcon-512/1024 key")?;
+    if !kind.verify(signer_pk, &file[..signed_len], &file[signed_len..]) {
+        return Err("signature does not verify".to_string());
+    }
+    Ok((file[ENTROPY_BLOCK_HEADER_LEN..signed_len].to_vec(), sequence, timestamp))
+}
+
+/// What an entropy token's Falcon signature covers: "COBRA_ENTROPY_TOKEN" || sequence u64 BE
+/// || timestamp u64 BE || pool commitment || bytes
+fn entropy_token_message(sequence: u64, timestamp: u64, commitment: &[u8], bytes: &[u8]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(19 + 16 + commitment.len() + bytes.len());
+    message.extend_from_slice(b"COBRA_ENTROPY_TOKEN");
+    message.extend_from_slice(&sequence.to_be_bytes());
+    message.extend_from_slice(&timestamp.to_be_bytes());
+    message.extend_from_slice(commitment);
+    message.extend_from_slice(bytes);
+    message
+}
+
+/// Emits the next pulse from 64 bytes of BEACON-consumer output, signed with the engine's
+/// Falcon identity. None in failure mode or before the generator is seeded.
+fn emit_beacon_pulse(state: &mut SharedState) -> Option<u64> {
+    if state.beacon.is_none() || state.failure_mode.is_some() || !state.pqc_active { return None; }
+    let mut random = consumer_generate(state, "BEACON", 64).ok()?;
+    let mut local_random = [0u8; 64];
+    local_random.copy_from_slice(&random);
+    random.zeroize();
+    
+    let (index, previous_output) = state.beacon.as_ref().map(|b| (b.next_index, b.last_output))?;
+    let timestamp = get_timestamp();
+    let message = BeaconPulse::signed_message(index, timestamp, &local_random, &previous_output);
+    let signature = state.falcon_kind.sign(&state.falcon_sk, &message).ok()?;
+    let (signer, signer_pk) = (state.falcon_kind, state.falcon_pk.clone());
+    let beacon = state.beacon.as_mut()?;
+    
+    let mut hasher = Sha512::new();
+    hasher.update(&message);
+    hasher.update(&signature);
+    let mut output = [0u8; 64];
+    output.copy_from_slice(&hasher.finalize());
+    
+    if beacon.pulses.len() >= BEACON_MAX_PULSES { beacon.pulses.pop_front(); }
+    beacon.pulses.push_back(BeaconPulse {
+        index,
+        timestamp,
+        local_random,
+        previous_output: beacon.last_output,
+        signature,
+        signer,
+        signer_pk,
+        output,
+    });
+    beacon.last_output = output;
+    beacon.next_index += 1;
+    Some(index)
+}
+
+/// Pulses every `interval_secs` until the beacon is stopped
+fn start_beacon_thread(interval_secs: u64, stop: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>, running: Arc<AtomicBool>) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let mut last_pulse: Option<Instant> = None;
+        while running.load(Ordering::Relaxed) && !stop.load(Ordering::Relaxed) {
+            if !last_pulse.is_some_and(|t| t.elapsed() < Duration::from_secs(interval_secs)) {
+                let mut lock = state.lock();
+                if emit_beacon_pulse(&mut lock).is_some() {
+                    last_pulse = Some(Instant::now());
+                }
+            }
+            thread::sleep(Duration::from_millis(250));
+        }
+    })
+}
+
+/// Read-only HTTP view of the beacon: GET /beacon/last, /beacon/pulse/<index>, /beacon/time/<unix secs>
+fn start_beacon_http(port: u16, stop: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>, running: Arc<AtomicBool>) -> std::io::Result<thread::JoinHandle<()>> {
+    use std::net::TcpListener;
+    let listener = TcpListener::bind(("0.0.0.0", port))?;
+    listener.set_nonblocking(true)?;
+    
+    Ok(thread::spawn(move || {
+        use std::io::{Read, Write};
+        while running.load(Ordering::Relaxed) && !stop.load(Ordering::Relaxed) {
+            let Ok((mut stream, _)) = listener.accept() else {
+                thread::sleep(Duration::from_millis(100));
+                continue;
+            };
+            let _ = stream.set_nonblocking(false);
+            let _ = stream.set_read_timeout(Some(Duration::from_secs(2)));
+            let mut request = [0u8; 1024];
+            let Ok(len) = stream.read(&mut request) else { continue };
+            let request = String::from_utf8_lossy(&request[..len]);
+            let path = request.strip_prefix("GET ").and_then(|r| r.split_whitespace().next()).unwrap_or("");
+            
+            let body = {
+                let lock = state.lock();
+                lock.beacon.as_ref().and_then(|beacon| {
+                    let pulse = if path == "/beacon/last" {
+                        beacon.pulses.back()
+                    } else if let Some(index) = path.strip_prefix("/beacon/pulse/") {
+                        index.parse().ok().and_then(|i| beacon.pulse(i))
+                    } else if let Some(time) = path.strip_prefix("/beacon/time/") {
+                        time.parse().ok().and_then(|t| beacon.pulse_at(t))
+                    } else {
+                        None
+                    };
+                    pulse.map(|p| p.to_json().to_string())
+                })
+            };
+            let response = match body {
+                Some(body) => format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                    body.len(), body
+                ),
+                None => "HTTP/1.1 404 Not Found\r\nContent-Length: 9\r\n\r\nNOT FOUND".to_string(),
+            };
+            let _ = stream.write_all(response.as_bytes());
+        }
+    }))
+}
+
+/// `count` rolls of a `sides`-sided die plus the transcript of every DRBG draw behind them.
+/// Each roll is 1 + (u64 LE % sides) over 8-byte draws, redrawing any at or above the largest
+/// multiple of `sides`; the commitment is SHA-256("CHAOS_MAGNET_DICE" || sides u32 LE ||
+/// count u32 LE || transcript), so anyone holding the transcript can re-derive and check the rolls.
+fn dice_rolls(state: &Arc<Mutex<SharedState>>, sides: u32, count: usize) -> PyResult<(Vec<u32>, [u8; 32], Vec<u8>)> {
+    let zone = (u64::MAX / sides as u64) * sides as u64;
+    let mut rolls = Vec::with_capacity(count);
+    let mut transcript = Vec::with_capacity(count * 8);
+    while rolls.len() < count {
+        let draws = python_output(state, (count - rolls.len()) * 8)?;
+        for chunk in draws.chunks_exact(8) {
+            let x = u64::from_le_bytes(chunk.try_into().unwrap_or([0u8; 8]));
+            if x < zone && rolls.len() < count {
+                rolls.push(1 + (x % sides as u64) as u32);
+            }
+        }
+        transcript.extend_from_slice(&draws);
+    }
+    
+    let mut hasher = Sha256::new();
+    hasher.update(b"CHAOS_MAGNET_DICE");
+    hasher.update(sides.to_le_bytes());
+    hasher.update((count as u32).to_le_bytes());
+    hasher.update(&transcript);
+    Ok((rolls, hasher.finalize().into(), transcript))
+}
+
+/// Dice code -> word for a diceware list ("11111<TAB>abacus"), and the dice per word. Every
+/// code of the 6^k must be present, or the picks wouldn't be uniform.
+fn load_diceware_list(wordlist: &str) -> Result<(HashMap<String, String>, usize), String> {
+    let text = wordlist_text(wordlist).map_err(|e| e.to_string())?;
+    let mut codes = HashMap::new();
+    for line in text.lines() {
+        let mut fields = line.split_whitespace();
+        if let (Some(code), Some(word)) = (fields.next(), fields.next()) {
+            codes.insert(code.to_string(), word.to_string());
+        }
+    }
+    
+    let dice = codes.keys().next().map_or(0, |c| c.len());
+    let valid = (1..=6).contains(&dice)
+        && codes.len() == 6usize.pow(dice as u32)
+        && codes.keys().all(|c| c.len() == dice && c.bytes().all(|b| (b'1'..=b'6').contains(&b)));
+    if !valid {
+        return Err("not a diceware list (needs every code of 1-6 dice digits)".to_string());
+    }
+    Ok((codes, dice))
+}
+
+/// `count` RFC 9562 UUIDs (version 4 or 7) from the PYTHON consumer. UUIDv7 puts a 12-bit
+/// counter in rand_a, so identifiers minted in the same millisecond still sort in order.
+fn python_uuids(state: &Arc<Mutex<SharedState>>, version: u8, count: usize) -> PyResult<Vec<String>> {
+    let mut lock = state.lock();
+    check_output_allowed(&lock)?;
+    let mut bytes = consumer_generate(&mut lock, "PYTHON", count * 16)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+    
+    let mut uuids = Vec::with_capacity(count);
+    for b in bytes.chunks_exact_mut(16) {
+        if version == 7 {
+            let now_ms = get_timestamp_nanos() / 1_000_000;
+            let (last_ms, counter) = lock.uuid7_last;
+            // A fresh millisecond starts the counter at a random point in its lower half
+            let fresh = u16::from_be_bytes([b[6], b[7]]) & 0x07ff;
+            let (ms, counter) = if now_ms > last_ms {
+                (now_ms, fresh)
+            } else if counter < 0x0fff {
+                (last_ms, counter + 1)
+            } else {
+                (last_ms + 1, fresh)
+            };
+            lock.uuid7_last = (ms, counter);
+            b[..6].copy_from_slice(&ms.to_be_bytes()[2..]);
+            b[6] = 0x70 | (counter >> 8) as u8;
+            b[7] = counter as u8;
+        } else {
+            b[6] = (b[6] & 0x0f) | 0x40;
+        }
+        b[8] = (b[8] & 0x3f) | 0x80;
+        uuids.push(format!("{}-{}-{}-{}-{}",
+            hex::encode(&b[..4]), hex::encode(&b[4..6]), hex::encode(&b[6..8]),
+            hex::encode(&b[8..10]), hex::encode(&b[10..])));
+    }
+    bytes.zeroize();
+    Ok(uuids)
+}
+
+/// Reseeds one consumer (or every seeded consumer) right now; returns how many were reseeded
+fn force_reseed_consumers(state: &mut SharedState, consumer: Option<&str>, reason: &'static str) -> usize {
+    let credited = state.ledger.total_credited_bits;
+    let mut count = 0;
+    for (name, entry) in state.output_consumers.iter_mut() {
+        if entry.drbg.is_none() || consumer.is_some_and(|c| c != name) { continue; }
+        let entropy = drbg_entropy_from_pool(&state.accumulator.pool, state.sequence_id);
+        entry.seed(&entropy, name.as_bytes(), reason, credited);
+        count += 1;
+    }
+    if count > 0 {
+        state.accumulator.ratchet();
+    }
+    count
+}
+
+/// `n` bytes for `consumer` (created with a CTR-DRBG on first use)
+fn consumer_generate(state: &mut SharedState, consumer: &str, n: usize) -> Result<Vec<u8>, String> {
+    let mut out = vec![0u8; n];
+    consumer_fill(state, consumer, &mut out)?;
+    Ok(out)
+}
+
+/// Fills `out` in place for `consumer`, debiting its length from the ledger
+fn consumer_fill(state: &mut SharedState, consumer: &str, out: &mut [u8]) -> Result<(), String> {
+    if !state.output_consumers.contains_key(consumer) {
+        state.output_consumers.insert(consumer.to_string(), OutputConsumer::new(DrbgKind::Ctr));
+    }
+    refresh_output_consumers(state);
+    
+    let credited = state.ledger.total_credited_bits;
+    let pool_reseeds = state.fortuna.reseed_count;
+    let entropy = drbg_entropy_from_pool(&state.accumulator.pool, state.sequence_id);
+    let entry = state.output_consumers.get_mut(consumer).ok_or("Unknown consumer")?;
+    if entry.drbg.is_none() {
+        return Err("Output generator not seeded yet (waiting for first pool reseed)".to_string());
+    }
+    entry.quota.charge(out.len(), pool_reseeds)?;
+    
+    // Only as fresh as the pool's latest mix, but never reuses a seed thanks to the timestamp
+    let reason = if entry.prediction_resistance {
+        Some("prediction_resistance")
+    } else {
+        state.reseed_policy.due(entry, credited)
+    };
+    if let Some(reason) = reason {
+        entry.seed(&entropy, consumer.as_bytes(), reason, credited);
+    }
+    
+    let n = out.len();
+    entry.drbg.as_mut().ok_or("Output generator missing")?.generate(out, consumer.as_bytes())?;
+    entry.bytes_generated += n as u64;
+    entry.bytes_since_reseed += n as u64;
+    state.ledger.debit(n as f64 * 8.0);
+    if reason.is_some() {
+        state.accumulator.ratchet();
+    }
+    Ok(())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// SEED FILE
+// ═══════════════════════════════════════════════════════════════════════════
+
+// Like an OS RNG's boot seed: on shutdown a one-way digest of the pool is written encrypted
+// and authenticated under a per-installation key, and on startup it is mixed back in before
+// any harvester data arrives. It gets no entropy credit; it only stops every run starting
+// from an all-zero pool.
+
+/// Per-installation key kept beside the seed file (`<path>.key`); created owner-only on first
+/// use. A key file of the wrong size is an error rather than replaced, since replacing it would
+/// silently orphan the seed file it protects.
+fn seed_file_key(path: &str) -> std::io::Result<[u8; 32]> {
+    use std::io::Write;
+    let key_path = format!("{}.key", path);
+    match fs::read(&key_path) {
+        Ok(bytes) => {
+            return <[u8; 32]>::try_from(bytes.as_slice()).map_err(|_| std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{} is not a 32-byte seed key; move it aside to start over", key_path),
+            ));
+        }
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+        Err(_) => {}
+    }
+    
+    let mut key = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut key);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(&key_path)?.write_all(&key)?;
+    Ok(key)
+}
+
+fn seed_file_keystream(key: &[u8; 32], nonce: &[u8]) -> [u8; 32] {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(b"CHAOS_MAGNET_SEED_ENC");
+    mac.update(nonce);
+    mac.finalize().into_bytes().into()
+}
+
+fn seed_file_tag(key: &[u8; 32], nonce: &[u8], ciphertext: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(b"CHAOS_MAGNET_SEED_MAC");
+    mac.update(nonce);
+    mac.update(ciphertext);
+    mac
+}
+
+/// Writes a seed derived from (but not revealing) `pool`; written then renamed so a crash
+/// mid-write never leaves a truncated file
+fn write_seed_file(path: &str, pool: &[u8; 32]) -> std::io::Result<()> {
+    let key = seed_file_key(path)?;
+    
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"CHAOS_MAGNET_SEED_FILE");
+    hasher.update(pool);
+    let mut seed: [u8; 32] = hasher.finalize().into();
+    
+    let mut nonce = [0u8; 16];
+    rand::rngs::OsRng.fill_bytes(&mut nonce);
+    for (s, k) in seed.iter_mut().zip(seed_file_keystream(&key, &nonce)) { *s ^= k; }
+    let tag = seed_file_tag(&key, &nonce, &seed).finalize().into_bytes();
+    
+    let mut contents = Vec::with_capacity(SEED_FILE_LEN);
+    contents.extend_from_slice(SEED_FILE_MAGIC);
+    contents.extend_from_slice(&nonce);
+    contents.extend_from_slice(&seed);
+    contents.extend_from_slice(&tag);
+    
+    // A stale .tmp from an older run could carry looser permissions; mode only applies on create
+    let tmp_path = format!("{}.tmp", path);
+    let _ = fs::remove_file(&tmp_path);
+    write_vault_file(&tmp_path, &contents)?;
+    fs::rename(&tmp_path, path)
+}
+
+fn read_seed_file(path: &str) -> Result<[u8; 32], String> {
+    let contents = fs::read(path).map_err(|e| e.to_string())?;
+    if contents.len() != SEED_FILE_LEN || &contents[..8] != SEED_FILE_MAGIC {
+        return Err("Unrecognised seed file format".to_string());
+    }
+    let key = seed_file_key(path).map_err(|e| e.to_string())?;
+    
+    let (nonce, rest) = contents[8..].split_at(16);
+    let (ciphertext, tag) = rest.split_at(32);
+    seed_file_tag(&key, nonce, ciphertext).verify_slice(tag)
+        .map_err(|_| "Seed file failed authentication".to_string())?;
+    
+    let mut seed = [0u8; 32];
+    for ((s, c), k) in seed.iter_mut().zip(ciphertext).zip(seed_file_keystream(&key, nonce)) { *s = c ^ k; }
+    Ok(seed)
+}
+
+/// Mixes the saved seed into the pool and immediately replaces the file, so a crash before
+/// the next clean shutdown can't make two runs start from the same seed
+fn restore_seed_file(state: &mut SharedState, path: &str) {
+    let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+    let msg = match read_seed_file(path) {
+        Ok(mut seed) => {
+            state.accumulator.mix(&[b"CHAOS_MAGNET_SEED_RESTORE", &seed, &get_timestamp_nanos().to_le_bytes()]);
+            seed.zeroize();
+            
+            match write_seed_file(path, &state.accumulator.pool) {
+                Ok(()) => format!("[{}] SEED: Restored pool from {}", ts, path),
+                Err(e) => format!("[{}] SEED: Restored from {} but could not refresh it: {}", ts, path, e),
+            }
+        }
+        Err(_) if !std::path::Path::new(path).exists() => {
+            format!("[{}] SEED: No seed file at {}, cold start", ts, path)
+        }
+        Err(e) => format!("[{}] SEED: Ignoring {}: {}", ts, path, e),
+    };
+    state.accumulator.ratchet();
+    if state.logs.len() >= 20 { state.logs.pop_front(); }
+    state.logs.push_back(msg);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// KEY VAULT
+// ═══════════════════════════════════════════════════════════════════════════
+
+// Secret key fields in keys/*.json are sealed with XChaCha20-Poly1305 under a key derived
+// from an operator passphrase with Argon2id, with the bundle's public fields as associated
+// data. keys/vault.json holds the salt, KDF parameters and a sealed check value; the key
+// itself only exists in memory while the vault is unlocked. Writing plaintext secrets needs
+// an explicit opt-in.
+
+#[derive(Default)]
+struct Vault {
+    key: Option<[u8; 32]>,
+    allow_plaintext: bool,
+    bundles_sealed: u64,
+}
+
+impl Vault {
+    fn check_writable(&self) -> Result<(), String> {
+        if self.key.is_none() && !self.allow_plaintext {
+            return Err("Vault locked: call set_vault_passphrase() or unlock_vault()".to_string());
+        }
+        Ok(())
+    }
+    
+    fn lock(&mut self) {
+        if let Some(mut key) = self.key.take() {
+            key.zeroize();
+        }
+    }
+}
+
+impl Drop for Vault {
+    fn drop(&mut self) {
+        self.lock();
+    }
+}
+
+fn vault_derive_key(passphrase: &str, salt: &[u8], m_cost: u32, t_cost: u32, p_cost: u32) -> Result<[u8; 32], String> {
+    let params = argon2::Params::new(m_cost, t_cost, p_cost, Some(32)).map_err(|e| e.to_string())?;
+    let argon = argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+    let mut key = [0u8; 32];
+    argon.hash_password_into(passphrase.as_bytes(), salt, &mut key).map_err(|e| e.to_string())?;
+    Ok(key)
+}
+
+fn vault_seal(key: &[u8; 32], plaintext: &[u8], aad: &[u8]) -> Result<serde_json::Value, String> {
+    use chacha20poly1305::aead::{Aead, Payload};
+    let cipher = <chacha20poly1305::XChaCha20Poly1305 as chacha20poly1305::KeyInit>::new(chacha20poly1305::Key::from_slice(key));
+    let mut nonce = [0u8; 24];
+    rand::rngs::OsRng.fill_bytes(&mut nonce);
+    let ciphertext = cipher.encrypt(chacha20poly1305::XNonce::from_slice(&nonce), Payload { msg: plaintext, aad })
+        .map_err(|_| "Vault encryption failed".to_string())?;
+    Ok(serde_json::json!({
+        "cipher": "xchacha20poly1305",
+        "nonce": hex::encode(nonce),
+        "ciphertext": hex::encode(ciphertext),
+    }))
+}
+
+fn vault_open(key: &[u8; 32], sealed: &serde_json::Value, aad: &[u8]) -> Result<Vec<u8>, String> {
+    use chacha20poly1305::aead::{Aead, Payload};
+    let field = |name: &str| sealed.get(name).and_then(|v| v.as_str()).and_then(|h| hex::decode(h).ok());
+    let nonce = field("nonce").filter(|n| n.len() == 24).ok_or("Sealed data has no valid nonce")?;
+    let ciphertext = field("ciphertext").ok_or("Sealed data has no ciphertext")?;
+    let cipher = <chacha20poly1305::XChaCha20Poly1305 as chacha20poly1305::KeyInit>::new(chacha20poly1305::Key::from_slice(key));
+    cipher.decrypt(chacha20poly1305::XNonce::from_slice(&nonce), Payload { msg: &ciphertext, aad })
+        .map_err(|_| "Vault authentication failed (wrong passphrase or tampered file)".to_string())
+}
+
+/// Owner-only (0600 on unix) file write, used for everything under the vault
+fn write_vault_file(path: &str, contents: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(contents)
+}
+
+/// Moves `secret_fields` out of `bundle` into a sealed "vault" object bound to the remaining
+/// public fields, then writes it to `filename`. Secrets stay plaintext only when the vault is
+/// locked and plaintext export was explicitly enabled. Returns whether the bundle was sealed.
+fn write_vault_bundle(vault: &Vault, filename: &str, mut bundle: serde_json::Value, secret_fields: &[&str]) -> Result<bool, String> {
+    vault.check_writable()?;
+    let sealed = if let Some(key) = vault.key.as_ref() {
+        let fields = bundle.as_object_mut().ok_or("Bundle is not a JSON object")?;
+        let mut secrets = serde_json::Map::new();
+        for field in secret_fields {
+            if let Some(value) = fields.remove(*field) {
+                secrets.insert(field.to_string(), value);
+            }
+        }
+        let aad = bundle.to_string();
+        let mut plaintext = serde_json::Value::Object(secrets).to_string().into_bytes();
+        let sealed = vault_seal(key, &plaintext, aad.as_bytes());
+        plaintext.zeroize();
+        bundle["vault"] = sealed?;
+        true
+    } else {
+        false
+    };
+    let contents = serde_json::to_vec_pretty(&bundle).map_err(|e| e.to_string())?;
+    write_vault_file(filename, &contents).map_err(|e| e.to_string())?;
+    Ok(sealed)
+}
+
+/// Reads a bundle written by write_vault_bundle, merging sealed secrets back in (needs the
+/// vault unlocked when the bundle is sealed)
+fn read_vault_bundle(vault: &Vault, path: &str) -> Result<serde_json::Value, String> {
+    let contents = fs::read(path).map_err(|e| e.to_string())?;
+    let mut bundle: serde_json::Value = serde_json::from_slice(&contents).map_err(|e| e.to_string())?;
+    let fields = bundle.as_object_mut().ok_or("Bundle is not a JSON object")?;
+    let Some(sealed) = fields.remove("vault") else {
+        return Ok(bundle);
+    };
+    let key = vault.key.as_ref().ok_or("Vault locked")?;
+    let aad = bundle.to_string();
+    let mut plaintext = vault_open(key, &sealed, aad.as_bytes())?;
+    let secrets = serde_json::from_slice::<serde_json::Map<String, serde_json::Value>>(&plaintext);
+    plaintext.zeroize();
+    for (field, value) in secrets.map_err(|e| e.to_string())? {
+        bundle[field] = value;
+    }
+    Ok(bundle)
+}
+
+/// What sign() actually signs: caller data under its own domain tag, so a signature obtained
+/// through the API can never pass as a token, beacon pulse or bundle signature
+fn user_data_message(data: &[u8]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(22 + data.len());
+    message.extend_from_slice(b"CHAOS_MAGNET_USER_DATA");
+    message.extend_from_slice(data);
+    message
+}
+
+/// Persistent form of the Falcon identity; the secret key is always sealed
+fn write_identity_file(vault: &Vault, path: &str, kind: FalconKind, pk: &[u8], sk: &[u8]) -> Result<(), String> {
+    if vault.key.is_none() {
+        return Err("Vault locked; identity not persisted".to_string());
+    }
+    let bundle = serde_json::json!({
+        "type": "COBRA_IDENTITY",
+        "signer": kind.name(),
+        "public_key": hex::encode(pk),
+        "secret_key": hex::encode(sk),
+        "saved": get_timestamp(),
+    });
+    write_vault_bundle(vault, path, bundle, &["secret_key"]).map(|_| ())
+}
+
+/// (kind, public key, secret key) from write_identity_file, checked to be a working pair
+fn read_identity_file(vault: &Vault, path: &str) -> Result<(FalconKind, Vec<u8>, Vec<u8>), String> {
+    let bundle = read_vault_bundle(vault, path)?;
+    let kind = bundle["signer"].as_str().and_then(FalconKind::from_name).ok_or("Unknown identity signer")?;
+    let hex_field = |name: &str| bundle[name].as_str().and_then(|h| hex::decode(h).ok());
+    let (Some(pk), Some(sk)) = (hex_field("public_key"), hex_field("secret_key")) else {
+        return Err("Identity file is missing its keys".to_string());
+    };
+    let probe = kind.sign(&sk, b"CHAOS_MAGNET_IDENTITY_PROBE")?;
+    if !kind.verify(&pk, b"CHAOS_MAGNET_IDENTITY_PROBE", &probe) {
+        return Err("Identity public and secret keys do not match".to_string());
+    }
+    Ok((kind, pk, sk))
+}
+
+/// keys/<id>.json for a key id (the bundle's file stem); ids are never paths
+fn vault_key_path(id: &str) -> Result<String, String> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err(format!("Invalid key id: {:?}", id));
+    }
+    Ok(format!("{}/{}.json", VAULT_DIR, id))
+}
+
+/// Minted bundle at keys/<id>.json as stored (secrets still sealed); other vault files such
+/// as vault.json or the identity are not keys
+fn read_stored_key(id: &str) -> Result<serde_json::Value, String> {
+    let path = vault_key_path(id)?;
+    let contents = fs::read(&path).map_err(|e| format!("{}: {}", path, e))?;
+    let bundle: serde_json::Value = serde_json::from_slice(&contents).map_err(|e| format!("{}: {}", path, e))?;
+    if !bundle["type"].as_str().is_some_and(|t| VAULT_BUNDLE_TYPES.contains(&t)) {
+        return Err(format!("{} is not a key bundle", id));
+    }
+    Ok(bundle)
+}
+
+/// What the identity signs for a minted bundle, rebuilt from the bundle's own public fields so
+/// anyone holding it can verify: domain tag, type, algorithm, every public key, timestamp and
+/// the pool commitment (SHA3-256 of the slow pool state and the public key). None when a
+/// field is missing, as in bundles from before this format.
+fn vault_bundle_message(bundle: &serde_json::Value) -> Option<Vec<u8>> {
+    let bundle_type = bundle["type"].as_str()?;
+    let (algorithm, pk_fields): (String, &[&str]) = match bundle_type {
+        "COBRA_PQC_BUNDLE" => (bundle["kem"].as_str()?.to_string(), &["kyber_pk"][..]),
+        "COBRA_PQC_SIG_BUNDLE" | "COBRA_CLASSICAL_BUNDLE" => (bundle["algorithm"].as_str()?.to_string(), &["public_key"][..]),
+        "COBRA_HYBRID_BUNDLE" => (
+            format!("{}/{}", bundle["kex"].as_str()?, bundle["sig"].as_str()?),
+            &["x25519_pk", "kyber_pk", "ed25519_pk", "signer_pk"][..],
+        ),
+        _ => return None,
+    };
+    
+    let mut message = b"CHAOS_MAGNET_VAULT_BUNDLE".to_vec();
+    let mut field = |bytes: &[u8]| {
+        message.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+        message.extend_from_slice(bytes);
+    };
+    field(bundle_type.as_bytes());
+    field(algorithm.as_bytes());
+    for name in pk_fields {
+        field(&hex::decode(bundle[*name].as_str()?).ok()?);
+    }
+    field(&bundle["timestamp"].as_u64()?.to_be_bytes());
+    field(&hex::decode(bundle["pool_commitment"].as_str()?).ok()?);
+    Some(message)
+}
+
+/// Signs `bundle` with the engine identity (see vault_bundle_message) and records the signer
+fn sign_vault_bundle(state: &SharedState, bundle: &mut serde_json::Value) -> Result<(), String> {
+    let message = vault_bundle_message(bundle).ok_or("Bundle is missing signed fields")?;
+    let signature = state.falcon_kind.sign(&state.falcon_sk, &message)?;
+    bundle["falcon_sig"] = hex::encode(signature).into();
+    bundle["falcon_signer"] = state.falcon_kind.name().into();
+    bundle["falcon_signer_pk"] = hex::encode(&state.falcon_pk).into();
+    Ok(())
+}
+
+/// Listing entry for a stored bundle. `signature_valid` checks the identity signature over
+/// vault_bundle_message; None for bundles minted before that format.
+fn vault_key_metadata(id: &str, bundle: &serde_json::Value) -> serde_json::Value {
+    let algorithm = match bundle["type"].as_str() {
+        Some("COBRA_HYBRID_BUNDLE") => format!("{}/{}", bundle["kex"].as_str().unwrap_or("?"), bundle["sig"].as_str().unwrap_or("?")),
+        _ => bundle["algorithm"].as_str().or(bundle["kem"].as_str()).unwrap_or("kyber").to_string(),
+    };
+    let hex_field = |name: &str| bundle[name].as_str().and_then(|h| hex::decode(h).ok());
+    let signature_valid = match (vault_bundle_message(bundle), hex_field("falcon_signer_pk"), hex_field("falcon_sig")) {
+        (Some(message), Some(signer_pk), Some(sig)) => Some(
+            FalconKind::from_public_key(&signer_pk).is_some_and(|kind| kind.verify(&signer_pk, &message, &sig))
+        ),
+        _ => None,
+    };
+    serde_json::json!({
+        "id": id,
+        "type": bundle["type"],
+        "algorithm": algorithm,
+        "timestamp": bundle["timestamp"],
+        "requester": bundle["requester"],
+        "accumulated_true_bits": bundle["accumulated_true_bits"],
+        "raw_min_entropy": bundle["raw_min_entropy"],
+        "slow_pool_reseeds": bundle["slow_pool_reseeds"],
+        "sealed": bundle.get("vault").is_some(),
+        "falcon_signer": bundle["falcon_signer"],
+        "falcon_signer_pk": bundle["falcon_signer_pk"],
+        "signature_valid": signature_valid,
+    })
+}
+
+/// write_vault_bundle plus the VAULT log line
+fn save_vault_bundle(state: &mut SharedState, filename: &str, bundle: serde_json::Value, secret_fields: &[&str]) -> Result<(), String> {
+    let result = write_vault_bundle(&state.vault, filename, bundle, secret_fields);
+    
+    let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+    let msg = match &result {
+        Ok(true) => {
+            state.vault.bundles_sealed += 1;
+            format!("[{}] VAULT: Saved {} (sealed)", ts, filename)
+        }
+        Ok(false) => format!("[{}] VAULT: Saved {} (PLAINTEXT secrets)", ts, filename),
+        Err(e) => format!("[{}] VAULT: Could not save {}: {}", ts, filename, e),
+    };
+    if state.logs.len() >= 20 { state.logs.pop_front(); }
+    state.logs.push_back(msg);
+    result.map(|_| ())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// LOCAL OUTPUT SERVICES
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Mixes `data` into the kernel pool crediting `entropy_bits`; needs CAP_SYS_ADMIN
+#[cfg(target_os = "linux")]
+fn add_kernel_entropy(data: &[u8], entropy_bits: u32) -> std::io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+    const RNDADDENTROPY: u64 = 0x4008_5203;  // _IOW('R', 0x03, int[2])
+    
+    // struct rand_pool_info { int entropy_count; int buf_size; __u32 buf[]; }
+    let mut info = vec![0u32; 2 + data.len().div_ceil(4)];
+    info[0] = entropy_bits;
+    info[1] = data.len() as u32;
+    for (word, chunk) in info[2..].iter_mut().zip(data.chunks(4)) {
+        let mut bytes = [0u8; 4];
+        bytes[..chunk.len()].copy_from_slice(chunk);
+        *word = u32::from_ne_bytes(bytes);
+    }
+    
+    let file = fs::OpenOptions::new().write(true).open("/dev/random")?;
+    let rc = unsafe { libc::ioctl(file.as_raw_fd(), RNDADDENTROPY as _, info.as_ptr()) };
+    let result = if rc < 0 { Err(std::io::Error::last_os_error()) } else { Ok(()) };
+    info.zeroize();
+    result
+}
+
+#[cfg(not(target_os = "linux"))]
+fn add_kernel_entropy(_data: &[u8], _entropy_bits: u32) -> std::io::Result<()> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "RNDADDENTROPY needs Linux"))
+}
+
+/// Tops up the kernel pool every KERNEL_FEED_INTERVAL_SECS while feed_kernel is on. Nothing is
+/// written in failure mode, and the credit never exceeds what the ledger currently holds.
+fn start_kernel_feed_thread(state: Arc<Mutex<SharedState>>, running: Arc<AtomicBool>) {
+    thread::spawn(move || {
+        let mut last_feed = Instant::now();
+        
+        while running.load(Ordering::Relaxed) {
+            thread::sleep(Duration::from_secs(1));
+            if last_feed.elapsed() < Duration::from_secs(KERNEL_FEED_INTERVAL_SECS) {
+                continue;
+            }
+            last_feed = Instant::now();
+            
+            let (mut data, bits) = {
+                let mut lock = state.lock();
+                let Some(bits_per_byte) = lock.kernel_feed.as_ref().map(|f| f.bits_per_byte) else { continue };
+                if lock.failure_mode.is_some() { continue; }
+                let bits = (KERNEL_FEED_BYTES as f64 * bits_per_byte).min(lock.ledger.available_bits).floor() as u32;
+                match consumer_generate(&mut lock, "KERNEL", KERNEL_FEED_BYTES) {
+                    Ok(data) => (data, bits),
+                    Err(_) => continue,  // Not seeded yet
+                }
+            };
+            let result = add_kernel_entropy(&data, bits);
+            data.zeroize();
+            
+            let mut lock = state.lock();
+            let Some(feed) = lock.kernel_feed.as_mut() else { continue };
+            match result {
+                Ok(()) => {
+                    feed.bytes_fed += KERNEL_FEED_BYTES as u64;
+                    feed.bits_credited += bits as u64;
+                    feed.last_error = None;
+                }
+                Err(e) => {
+                    let error = e.to_string();
+                    if feed.last_error.as_ref() == Some(&error) { continue; }
+                    feed.last_error = Some(error);
+                    let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+                    let msg = format!("[{}] KERNEL: RNDADDENTROPY failed: {}", ts, e);
+                    if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+                    lock.logs.push_back(msg);
+                }
+            }
+        }
+    });
+}
+
+/// (uid, pid) of the process at the other end of a Unix socket
+#[cfg(target_os = "linux")]
+fn unix_peer_credentials(stream: &std::os::unix::net::UnixStream) -> Option<(u32, i32)> {
+    use std::os::unix::io::AsRawFd;
+    let mut cred = libc::ucred { pid: 0, uid: 0, gid: 0 };
+    let mut len = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+    let rc = unsafe {
+        libc::getsockopt(stream.as_raw_fd(), libc::SOL_SOCKET, libc::SO_PEERCRED,
+            (&mut cred as *mut libc::ucred).cast(), &mut len)
+    };
+    if rc < 0 { None } else { Some((cred.uid, cred.pid)) }
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn unix_peer_credentials(_stream: &std::os::unix::net::UnixStream) -> Option<(u32, i32)> {
+    None
+}
+
+/// `n` bytes from the `consumer` output generator, or None in failure mode or before the first reseed
+fn local_service_generate(state: &Arc<Mutex<SharedState>>, consumer: &str, n: usize) -> Option<Vec<u8>> {
+    let mut lock = state.lock();
+    if lock.failure_mode.is_some() { return None; }
+    consumer_generate(&mut lock, consumer, n).ok()
+}
+
+/// Binds the EGD socket at `path`, replacing any running server, and serves it until
+/// stop_egd_server or shutdown. Commands:
+/// 0x00 entropy level (u32 BE bits), 0x01 n non-blocking read (count byte + bytes), 0x02 n
+/// blocking read, 0x03 MSB LSB n data write (mixed in, the client's claim ignored), 0x04 PID.
+#[cfg(unix)]
+fn start_egd_server(
+    path: String,
+    tx: Sender<(String, Vec<u8>)>,
+    state: Arc<Mutex<SharedState>>,
+    running: Arc<AtomicBool>
+) -> std::io::Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::net::UnixListener;
+    
+    if let Some(old) = state.lock().egd_server.take() {
+        old.stop.store(true, Ordering::Relaxed);
+    }
+    // Only a stale socket is removed; anything else at the path is left for bind to refuse
+    if fs::symlink_metadata(&path).is_ok_and(|m| m.file_type().is_socket()) {
+        fs::remove_file(&path)?;
+    }
+    let listener = UnixListener::bind(&path)?;
+    listener.set_nonblocking(true)?;
+    let stop = Arc::new(AtomicBool::new(false));
+    state.lock().egd_server = Some(EgdServer { path, clients: HashMap::new(), stop: stop.clone() });
+    
+    thread::spawn(move || {
+        while running.load(Ordering::Relaxed) && !stop.load(Ordering::Relaxed) {
+            match listener.accept() {
+                Ok((mut stream, _)) => {
+                    let (tx, state, running, stop) = (tx.clone(), state.clone(), running.clone(), stop.clone());
+                    thread::spawn(move || {
+                        use std::io::{Read, Write};
+                        if stream.set_nonblocking(false).is_err() { return; }
+                        let _ = stream.set_read_timeout(Some(Duration::from_secs(1)));
+                        
+                        let client = match unix_peer_credentials(&stream) {
+                            Some((uid, pid)) => format!("uid {} pid {}", uid, pid),
+                            None => "unknown".to_string(),
+                        };
+                        {
+                            let mut lock = state.lock();
+                            let Some(server) = lock.egd_server.as_mut() else { return };
+                            if server.clients.len() >= EGD_MAX_CLIENTS {
+                                server.clients.retain(|_, c| c.connected > 0);
+                            }
+                            let entry = server.clients.entry(client.clone()).or_default();
+                            entry.connected += 1;
+                            entry.connections += 1;
+                        }
+                        
+                        let mut command = [0u8; 1];
+                        while running.load(Ordering::Relaxed) && !stop.load(Ordering::Relaxed) {
+                            match stream.read_exact(&mut command) {
+                                Ok(()) => {}
+                                Err(e) if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) => continue,
+                                Err(_) => break,
+                            }
+                            let mut arg = [0u8; 1];
+                            let mut received = 0;
+                            let reply = match command[0] {
+                                0x00 => {
+                                    let bits = state.lock().ledger.available_bits.floor() as u32;
+                                    bits.to_be_bytes().to_vec()
+                                }
+                                0x01 => {
+                                    if stream.read_exact(&mut arg).is_err() { break; }
+                                    let data = local_service_generate(&state, "EGD", arg[0] as usize).unwrap_or_default();
+                                    let mut reply = vec![data.len() as u8];
+                                    reply.extend_from_slice(&data);
+                                    reply
+                                }
+                                0x02 => {
+                                    if stream.read_exact(&mut arg).is_err() { break; }
+                                    // Blocks through failure mode and the unseeded start, like /dev/random
+                                    loop {
+                                        if let Some(data) = local_service_generate(&state, "EGD", arg[0] as usize) { break data; }
+                                        if !running.load(Ordering::Relaxed) || stop.load(Ordering::Relaxed) { return; }
+                                        thread::sleep(Duration::from_millis(200));
+                                    }
+                                }
+                                0x03 => {
+                                    let mut header = [0u8; 3];
+                                    if stream.read_exact(&mut header).is_err() { break; }
+                                    let mut data = vec![0u8; header[2] as usize];
+                                    if stream.read_exact(&mut data).is_err() { break; }
+                                    received = data.len();
+                                    if !data.is_empty() {
+                                        let _ = tx.try_send(("EGD".to_string(), data));
+                                    }
+                                    Vec::new()
+                                }
+                                0x04 => {
+                                    let pid = std::process::id().to_string();
+                                    let mut reply = vec![pid.len() as u8];
+                                    reply.extend_from_slice(pid.as_bytes());
+                                    reply
+                                }
+                                _ => break,
+                            };
+                            
+                            if let Some(entry) = state.lock().egd_server.as_mut().and_then(|s| s.clients.get_mut(&client)) {
+                                entry.requests += 1;
+                                entry.bytes_received += received as u64;
+                                if command[0] == 0x01 || command[0] == 0x02 {
+                                    entry.bytes_served += reply.len() as u64;
+                                }
+                            }
+                            if !reply.is_empty() && stream.write_all(&reply).is_err() { break; }
+                        }
+                        
+                        if let Some(entry) = state.lock().egd_server.as_mut().and_then(|s| s.clients.get_mut(&client)) {
+                            entry.connected = entry.connected.saturating_sub(1);
+                        }
+                    });
+                }
+                Err(_) => thread::sleep(Duration::from_millis(100)),  // WouldBlock: nothing pending
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Creates the FIFO at `path` if needed and keeps it full of FIFO-consumer output, paced to
+/// `rate_limit` bytes/s. Waits for a reader, reopens when it goes away, and pauses in failure mode.
+#[cfg(unix)]
+fn start_fifo_output(path: String, rate_limit: Option<u64>, state: Arc<Mutex<SharedState>>, running: Arc<AtomicBool>) -> std::io::Result<()> {
+    use std::os::unix::fs::{FileTypeExt, OpenOptionsExt};
+    use std::io::Write;
+    
+    match fs::metadata(&path) {
+        Ok(meta) if !meta.file_type().is_fifo() => {
+            return Err(std::io::Error::new(std::io::ErrorKind::AlreadyExists, "exists and is not a FIFO"));
+        }
+        Ok(_) => {}
+        Err(_) => {
+            let c_path = std::ffi::CString::new(path.clone())
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+            if unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) } < 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+        }
+    }
+    
+    let stop = Arc::new(AtomicBool::new(false));
+    if let Some(old) = state.lock().fifo_outputs.insert(path.clone(), FifoOutput {
+        rate_limit,
+        bytes_written: 0,
+        readers: 0,
+        stop: stop.clone(),
+    }) {
+        old.stop.store(true, Ordering::Relaxed);
+    }
+    
+    thread::spawn(move || {
+        let chunk_len = rate_limit.map_or(FIFO_CHUNK_BYTES, |r| (r as usize / 10).clamp(1, FIFO_CHUNK_BYTES));
+        let active = || running.load(Ordering::Relaxed) && !stop.load(Ordering::Relaxed);
+        
+        while active() {
+            // Non-blocking open fails with ENXIO until a reader shows up
+            let mut fifo = match fs::OpenOptions::new().write(true).custom_flags(libc::O_NONBLOCK).open(&path) {
+                Ok(f) => f,
+                Err(_) => {
+                    thread::sleep(Duration::from_millis(500));
+                    continue;
+                }
+            };
+            if let Some(output) = state.lock().fifo_outputs.get_mut(&path) {
+                output.readers += 1;
+            }
+            
+            let started = Instant::now();
+            let mut sent: u64 = 0;
+            'reader: while active() {
+                if let Some(rate) = rate_limit {
+                    let due = Duration::from_secs_f64(sent as f64 / rate as f64);
+                    if let Some(wait) = due.checked_sub(started.elapsed()) {
+                        thread::sleep(wait.min(Duration::from_millis(100)));
+                        continue;
+                    }
+                }
+                let Some(mut data) = local_service_generate(&state, "FIFO", chunk_len) else {
+                    thread::sleep(Duration::from_millis(200));
+                    continue;
+                };
+                
+                let mut offset = 0;
+                while offset < data.len() {
+                    match fifo.write(&data[offset..]) {
+                        Ok(n) => offset += n,
+                        Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                            if !active() { break; }
+                            thread::sleep(Duration::from_millis(10));
+                        }
+                        Err(_) => {
+                            // EPIPE: the reader closed its end
+                            data.zeroize();
+                            break 'reader;
+                        }
+                    }
+                }
+                data.zeroize();
+                sent += offset as u64;
+                if let Some(output) = state.lock().fifo_outputs.get_mut(&path) {
+                    output.bytes_written += offset as u64;
+                }
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Owner-only (0600 on unix) output file, truncated; an existing file is tightened before any
+/// random bytes land in it, since these may be used as one-time pads
+fn create_output_file(path: &str) -> std::io::Result<fs::File> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let file = options.open(path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(file)
+}
+
+/// `num_bytes` of FILE-consumer output to `path`, generated and written per FILE_CHUNK_BYTES
+fn write_random_file(state: &Arc<Mutex<SharedState>>, path: &str, num_bytes: u64) -> Result<(), String> {
+    use std::io::Write;
+    let mut file = std::io::BufWriter::new(create_output_file(path).map_err(|e| e.to_string())?);
+    let mut remaining = num_bytes;
+    while remaining > 0 {
+        let n = remaining.min(FILE_CHUNK_BYTES as u64) as usize;
+        let mut data = {
+            let mut lock = state.lock();
+            if let Some(reason) = entropy_failure(&lock) {
+                return Err(format!("Entropy failure: {}", reason));
+            }
+            consumer_generate(&mut lock, "FILE", n)?
+        };
+        let result = file.write_all(&data);
+        data.zeroize();
+        result.map_err(|e| e.to_string())?;
+        remaining -= n as u64;
+    }
+    file.flush().map_err(|e| e.to_string())
+}
+
+/// Shifts `path.N` to `path.N+1` (dropping the oldest) and moves `path` to `path.1`
+fn rotate_output_file(path: &str, keep_files: u32) -> std::io::Result<()> {
+    if keep_files == 0 {
+        return fs::remove_file(path);
+    }
+    for i in (1..keep_files).rev() {
+        let from = format!("{}.{}", path, i);
+        if std::path::Path::new(&from).exists() {
+            fs::rename(&from, format!("{}.{}", path, i + 1))?;
+        }
+    }
+    fs::rename(path, format!("{}.1", path))
+}
+
+/// Streams FILE-consumer output into `sink.path` until stopped, rotating at `rotate_at_bytes`.
+/// Pauses in failure mode; an I/O error stops the sink.
+fn start_file_sink(sink: FileSink, state: Arc<Mutex<SharedState>>, running: Arc<AtomicBool>) {
+    let (path, rotate_at, keep_files, rate_limit, stop) =
+        (sink.path.clone(), sink.rotate_at_bytes, sink.keep_files, sink.rate_limit, sink.stop.clone());
+    if let Some(old) = state.lock().file_sink.replace(sink) {
+        old.stop.store(true, Ordering::Relaxed);
+    }
+    
+    thread::spawn(move || {
+        use std::io::Write;
+        let chunk_len = rate_limit.map_or(FILE_CHUNK_BYTES, |r| (r as usize / 10).clamp(1, FILE_CHUNK_BYTES));
+        let started = Instant::now();
+        let mut sent: u64 = 0;
+        let mut file: Option<fs::File> = None;
+        let mut file_len: u64 = 0;
+        
+        let error = loop {
+            if !running.load(Ordering::Relaxed) || stop.load(Ordering::Relaxed) { break None; }
+            if let Some(rate) = rate_limit {
+                let due = Duration::from_secs_f64(sent as f64 / rate as f64);
+                if let Some(wait) = due.checked_sub(started.elapsed()) {
+                    thread::sleep(wait.min(Duration::from_millis(100)));
+                    continue;
+                }
+            }
+            
+            if file.is_none() || file_len >= rotate_at {
+                if file.take().is_some() {
+                    if let Err(e) = rotate_output_file(&path, keep_files) { break Some(e); }
+                    if let Some(sink) = state.lock().file_sink.as_mut() {
+                        sink.rotations += 1;
+                    }
+                }
+                match create_output_file(&path) {
+                    Ok(f) => file = Some(f),
+                    Err(e) => break Some(e),
+                }
+                file_len = 0;
+            }
+            
+            let n = (chunk_len as u64).min(rotate_at - file_len) as usize;
+            let Some(mut data) = local_service_generate(&state, "FILE", n) else {
+                thread::sleep(Duration::from_millis(200));
+                continue;
+            };
+            let result = file.as_mut().map_or(Ok(()), |f| f.write_all(&data));
+            data.zeroize();
+            if let Err(e) = result { break Some(e); }
+            file_len += n as u64;
+            sent += n as u64;
+            if let Some(sink) = state.lock().file_sink.as_mut() {
+                sink.bytes_written += n as u64;
+            }
+        };
+        
+        if let Some(e) = error {
+            let mut lock = state.lock();
+            if lock.file_sink.as_ref().is_some_and(|s| Arc::ptr_eq(&s.stop, &stop)) {
+                lock.file_sink = None;
+            }
+            let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+            let msg = format!("[{}] FILE: Sink {} stopped: {}", ts, path, e);
+            if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+            lock.logs.push_back(msg);
+        }
+    });
+}
+
+/// Length-prefixed randomness service on a Unix socket. Frames are a u32 BE length and a body.
+/// Requests: 0x01 + u32 BE n (bytes), 0x02 (status JSON). Replies: a status byte (0 ok, 1 denied,
+/// 2 unavailable, 3 bad request) then the payload. Peers whose uid isn't allowed are refused.
+#[cfg(unix)]
+fn start_socket_service(path: String, allowed_uids: Vec<u32>, state: Arc<Mutex<SharedState>>, running: Arc<AtomicBool>) -> std::io::Result<()> {
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+    use std::os::unix::net::UnixListener;
+    
+    if let Some(old) = state.lock().socket_service.take() {
+        old.stop.store(true, Ordering::Relaxed);
+    }
+    if fs::symlink_metadata(&path).is_ok_and(|m| m.file_type().is_socket()) {
+        fs::remove_file(&path)?;
+    }
+    let listener = UnixListener::bind(&path)?;
+    listener.set_nonblocking(true)?;
+    // Access is decided by peer credentials, so any local user may connect
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o666))?;
+    let stop = Arc::new(AtomicBool::new(false));
+    state.lock().socket_service = Some(SocketService { path, allowed_uids, clients: HashMap::new(), stop: stop.clone() });
+    
+    thread::spawn(move || {
+        let own_uid = unsafe { libc::getuid() };
+        
+        while running.load(Ordering::Relaxed) && !stop.load(Ordering::Relaxed) {
+            match listener.accept() {
+                Ok((mut stream, _)) => {
+                    let (state, running, stop) = (state.clone(), running.clone(), stop.clone());
+                    thread::spawn(move || {
+                        use std::io::{Read, Write};
+                        if stream.set_nonblocking(false).is_err() { return; }
+                        let _ = stream.set_read_timeout(Some(Duration::from_secs(1)));
+                        let reply = |stream: &mut std::os::unix::net::UnixStream, status: u8, payload: &[u8]| {
+                            let mut frame = Vec::with_capacity(5 + payload.len());
+                            frame.extend_from_slice(&(payload.len() as u32 + 1).to_be_bytes());
+                            frame.push(status);
+                            frame.extend_from_slice(payload);
+                            let result = stream.write_all(&frame);
+                            frame.zeroize();
+                            result
+                        };
+                        
+                        // Fails closed where peer credentials are unavailable
+                        let uid = unix_peer_credentials(&stream).map(|(uid, _)| uid);
+                        {
+                            let mut lock = state.lock();
+                            let Some(service) = lock.socket_service.as_mut() else { return };
+                            let allowed = uid.is_some_and(|u| u == 0 || u == own_uid || service.allowed_uids.contains(&u));
+                            if !allowed {
+                                if let Some(u) = uid {
+                                    service.clients.entry(u).or_default().denied += 1;
+                                }
+                                drop(lock);
+                                let _ = reply(&mut stream, 1, b"uid not allowed");
+                                return;
+                            }
+                        }
+                        let Some(uid) = uid else { return };
+                        
+                        // The read timeout only lets the loop notice a stop; a partly read length
+                        // prefix is kept across it so the stream never desyncs mid-frame
+                        let mut len_bytes = [0u8; 4];
+                        let mut filled = 0;
+                        while running.load(Ordering::Relaxed) && !stop.load(Ordering::Relaxed) {
+                            match stream.read(&mut len_bytes[filled..]) {
+                                Ok(0) => break,
+                                Ok(n) => {
+                                    filled += n;
+                                    if filled < len_bytes.len() { continue; }
+                                }
+                                Err(e) if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut | std::io::ErrorKind::Interrupted) => continue,
+                                Err(_) => break,
+                            }
+                            filled = 0;
+                            let len = u32::from_be_bytes(len_bytes);
+                            if !(1..=16).contains(&len) {
+                                let _ = reply(&mut stream, 3, b"bad frame length");
+                                break;
+                            }
+                            let mut body = vec![0u8; len as usize];
+                            if stream.read_exact(&mut body).is_err() { break; }
+                            
+                            let result = match (body[0], body.len()) {
+                                (0x01, 5) => {
+                                    let n = u32::from_be_bytes([body[1], body[2], body[3], body[4]]);
+                                    if (1..=SOCKET_MAX_REQUEST).contains(&n) {
+                                        match local_service_generate(&state, "SOCKET", n as usize) {
+                                            Some(mut data) => {
+                                                if let Some(service) = state.lock().socket_service.as_mut() {
+                                                    let client = service.clients.entry(uid).or_default();
+                                                    client.requests += 1;
+                                                    client.bytes_served += data.len() as u64;
+                                                }
+                                                let result = reply(&mut stream, 0, &data);
+                                                data.zeroize();
+                                                result
+                                            }
+                                            None => reply(&mut stream, 2, b"output unavailable"),
+                                        }
+                                    } else {
+                                        reply(&mut stream, 3, b"n must be 1..=1048576")
+                                    }
+                                }
+                                (0x02, 1) => {
+                                    let status = {
+                                        let mut lock = state.lock();
+                                        if let Some(service) = lock.socket_service.as_mut() {
+                                            service.clients.entry(uid).or_default().requests += 1;
+                                        }
+                                        serde_json::json!({
+                                            "available_bits": lock.ledger.available_bits,
+                                            "total_credited_bits": lock.ledger.total_credited_bits,
+                                            "pool_reseeds": lock.fortuna.reseed_count,
+                                            "failure_mode": entropy_failure(&lock),
+                                        }).to_string()
+                                    };
+                                    reply(&mut stream, 0, status.as_bytes())
+                                }
+                                _ => reply(&mut stream, 3, b"unknown request"),
+                            };
+                            if result.is_err() { break; }
+                        }
+                    });
+                }
+                Err(_) => thread::sleep(Duration::from_millis(100)),  // WouldBlock: nothing pending
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn start_socket_service(_path: String, _allowed_uids: Vec<u32>, _state: Arc<Mutex<SharedState>>, _running: Arc<AtomicBool>) -> std::io::Result<()> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "Unix sockets need a Unix platform"))
+}
+
+#[cfg(not(unix))]
+fn start_fifo_output(_path: String, _rate_limit: Option<u64>, _state: Arc<Mutex<SharedState>>, _running: Arc<AtomicBool>) -> std::io::Result<()> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "named pipes need a Unix platform"))
+}
+
+#[cfg(not(unix))]
+fn start_egd_server(
+    _path: String,
+    _tx: Sender<(String, Vec<u8>)>,
+    _state: Arc<Mutex<SharedState>>,
+    _running: Arc<AtomicBool>
+) -> std::io::Result<()> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "EGD sockets need a Unix platform"))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// HARVESTERS (WITH THROTTLING)
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn start_trng_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        let mut rng = rand::rngs::OsRng;
+        while running.load(Ordering::Relaxed) {
+            let enabled = state.lock().harvester_states.trng;
+            if enabled {
+                let mut buf = [0u8; 1024];
+                rng.fill_bytes(&mut buf);
+                if admits_sample(&state, &buf) {
+                    let _ = tx.try_send(("TRNG".to_string(), buf.to_vec()));
+                }
+            }
+            thread::sleep(Duration::from_secs(1));
+        }
+    });
+}
+
+/// Second-order (RBJ cookbook) Butterworth section
+struct Biquad {
+    b0: f32, b1: f32, b2: f32,
+    a1: f32, a2: f32,
+    x1: f32, x2: f32,
+    y1: f32, y2: f32,
+}
+
+impl Biquad {
+    fn highpass(sample_rate: f32, cutoff_hz: f32) -> Self {
+        let w0 = 2.0 * std::f32::consts::PI * cutoff_hz / sample_rate;
+        let cos_w0 = w0.cos();
+        let alpha = w0.sin() / (2.0 * std::f32::consts::FRAC_1_SQRT_2);
+        let a0 = 1.0 + alpha;
+        Self {
+            b0: (1.0 + cos_w0) / 2.0 / a0,
+            b1: -(1.0 + cos_w0) / a0,
+            b2: (1.0 + cos_w0) / 2.0 / a0,
+            a1: -2.0 * cos_w0 / a0,
+            a2: (1.0 - alpha) / a0,
+            x1: 0.0, x2: 0.0,
+            y1: 0.0, y2: 0.0,
+        }
+    }
+    
+    fn process(&mut self, x: f32) -> f32 {
+        let y = self.b0 * x + self.b1 * self.x1 + self.b2 * self.x2 - self.a1 * self.y1 - self.a2 * self.y2;
+        self.x2 = self.x1;
+        self.x1 = x;
+        self.y2 = self.y1;
+        self.y1 = y;
+        y
+    }
+}
+
+/// Quadrature-demodulates the mic at 50 and 60 Hz and reports how far the hum's
+/// phase wandered from an ideal oscillator at the nominal grid frequency
+struct MainsTracker {
+    sample_rate: u64,
+    block_len: u64,
+    position: u64,            // Samples since start, modulo sample_rate (keeps the reference exact)
+    block_count: u64,
+    acc: [(f64, f64); 2],     // (I, Q) for 50 Hz, 60 Hz
+    nominal: Option<usize>,   // Index into MAINS_FREQS once the stronger tone is known
+    last_phase: Option<f64>,
+}
+
+const MAINS_FREQS: [f64; 2] = [50.0, 60.0];
+
+impl MainsTracker {
+    fn new(sample_rate: u32) -> Self {
+        let sample_rate = sample_rate.max(1) as u64;
+        Self {
+            sample_rate,
+            block_len: (sample_rate * MAINS_BLOCK_MS as u64 / 1000).max(1),
+            position: 0,
+            block_count: 0,
+            acc: [(0.0, 0.0); 2],
+            nominal: None,
+            last_phase: None,
+        }
+    }
+    
+    /// Feeds mono samples; pushes one residual per completed block onto `out`
+    fn process(&mut self, samples: impl Iterator<Item = f32>, out: &mut Vec<u8>) {
+        for x in samples {
+            let t = self.position as f64 / self.sample_rate as f64;
+            for (acc, freq) in self.acc.iter_mut().zip(MAINS_FREQS) {
+                let angle = 2.0 * std::f64::consts::PI * freq * t;
+                acc.0 += x as f64 * angle.cos();
+                acc.1 += x as f64 * angle.sin();
+            }
+            self.position = (self.position + 1) % self.sample_rate;
+            self.block_count += 1;
+            
+            if self.block_count >= self.block_len {
+                self.finish_block(out);
+            }
+        }
+    }
+    
+    fn finish_block(&mut self, out: &mut Vec<u8>) {
+        let amplitude = |(i, q): (f64, f64)| 2.0 * (i * i + q * q).sqrt() / self.block_count as f64;
+        let nominal = *self.nominal.get_or_insert_with(|| {
+            if amplitude(self.acc[1]) > amplitude(self.acc[0]) { 1 } else { 0 }
+        });
+        let (i, q) = self.acc[nominal];
+        
+        if amplitude((i, q)) >= MAINS_MIN_AMPLITUDE {
+            let phase = q.atan2(i);
+            if let Some(last) = self.last_phase {
+                // Wrapped phase error over one block; grid wander plus mic noise
+                let mut residual = phase - last;
+                while residual > std::f64::consts::PI { residual -= 2.0 * std::f64::consts::PI; }
+                while residual < -std::f64::consts::PI { residual += 2.0 * std::f64::consts::PI; }
+                let micro_radians = (residual * 1e6) as i64;
+                out.extend_from_slice(&(micro_radians as u16).to_le_bytes());
+            }
+            self.last_phase = Some(phase);
+        } else {
+            // Lost the hum (muted mic, battery power); don't difference across the gap
+            self.last_phase = None;
+            self.nominal = None;
+        }
+        
+        self.acc = [(0.0, 0.0); 2];
+        self.block_count = 0;
+    }
+}
+
+/// "AUDIO" for the default input, "AUDIO_<NAME>" for an explicitly selected device
+fn audio_source_name(device: &str) -> String {
+    if device.is_empty() {
+        return "AUDIO".to_string();
+    }
+    let tag: String = device.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    format!("AUDIO_{}", tag.trim_matches('_'))
+}
+
+/// `device_name` empty means the host's default input device
+fn start_audio_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>, device_name: String) {
+    thread::spawn(move || {
+        use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+        
+        let host = cpal::default_host();
+        let device = if device_name.is_empty() {
+            host.default_input_device()
+        } else {
+            host.input_devices().ok().and_then(|mut devices| {
+                devices.find(|d| d.name().map(|n| n == device_name).unwrap_or(false))
+            })
+        };
+        let config = device.as_ref().and_then(|d| d.default_input_config().ok());
+        
+        let (device, config) = match (device, config) {
+            (Some(d), Some(c)) => (d, c),
+            _ => {
+                let mut lock = state.lock();
+                lock.audio_threads.remove(&device_name);
+                let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+                let msg = format!("[{}] AUDIO: Input '{}' unavailable", ts, device_name);
+                if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+                lock.logs.push_back(msg);
+                return;
+            }
+        };
+        
+        let source = audio_source_name(&device_name);
+        let tx_clone = tx.clone();
+        let running_stream = running.clone();
+        let state_clone = state.clone();
+        
+        let sample_rate = config.sample_rate().0 as f32;
+        let channels = (config.channels() as usize).max(1);
+        let mut highpass = Biquad::highpass(sample_rate, ULTRASONIC_CUTOFF_HZ);
+        let mut filtered: Vec<f32> = Vec::new();
+        let mut mains = MainsTracker::new(config.sample_rate().0);
+        let mut mains_bytes: Vec<u8> = Vec::with_capacity(128);
+        let mains_source = source.replacen("AUDIO", "MAINS", 1);
+        
+        // THROTTLE: Track last send time
+        let last_send = Arc::new(Mutex::new(Instant::now()));
+        let last_send_clone = last_send.clone();
+
+        let stream = device.build_input_stream(
+            &config.into(),
+            move |data: &[f32], _: &_| {
+                if !running_stream.load(Ordering::Relaxed) { return; }
+                
+                let (enabled, mains_enabled, mode) = {
+                    let lock = state_clone.lock();
+                    (lock.harvester_states.audio, lock.harvester_states.mains, lock.audio_mode)
+                };
+                
+                // MAINS is its own source: it needs every sample, independent of AUDIO's toggle and throttle
+                if mains_enabled {
+                    mains.process(data.iter().step_by(channels).copied(), &mut mains_bytes);
+                    if mains_bytes.len() >= 64 {
+                        if admits_sample(&state_clone, &mains_bytes) {
+                            let _ = tx_clone.try_send((mains_source.clone(), mains_bytes.clone()));
+                        }
+                        mains_bytes.clear();
+                    }
+                }
+                
+                if !enabled { return; }
+                
+                // The filter runs on every callback so its state stays continuous across throttled ones
+                let ultrasonic = mode == AudioMode::Ultrasonic;
+                if ultrasonic {
+                    // Below 44.1 kHz there is no band above 18 kHz to isolate
+                    if sample_rate < 2.0 * ULTRASONIC_CUTOFF_HZ + 4000.0 { return; }
+                    filtered.clear();
+                    filtered.extend(data.iter().step_by(channels).map(|&x| highpass.process(x)));
+                }
+                
+                // THROTTLE: Max 5 sends/second (200ms minimum interval)
+                let mut last = last_send_clone.lock();
+                if last.elapsed() < Duration::from_millis(200) {
+                    return;  // Skip this callback
+                }
+                *last = Instant::now();
+                drop(last);
+                
+                // LIMIT: Only take first 256 samples to avoid flooding
+                let sample_limit = data.len().min(256);
+                let mut bytes = Vec::with_capacity(sample_limit * 4);
+                
+                if ultrasonic {
+                    // Only mantissa LSBs of the residual: thermal/quantization noise, no audible content
+                    for &sample in filtered.iter().take(sample_limit) {
+                        bytes.extend_from_slice(&(sample.to_bits() as u16).to_le_bytes());
+                    }
+                } else {
+                    for &sample in data.iter().take(sample_limit).step_by(4) {
+                        let bits = sample.to_bits();
+                        bytes.extend_from_slice(&bits.to_le_bytes());
+                    }
+                }
+                
+                let nanos = get_timestamp_nanos();
+                bytes.extend_from_slice(&nanos.to_le_bytes());
+                
+                if admits_sample(&state_clone, &bytes) {
+                    let _ = tx_clone.try_send((source.clone(), bytes));
+                }
+            },
+            |_| {}, None
+        );
+
+        // Held for the whole loop below: dropping the cpal Stream stops capture
+        let started = stream.map_err(|e| e.to_string())
+            .and_then(|s| s.play().map(|_| s).map_err(|e| e.to_string()));
+        let _stream = match started {
+            Ok(s) => s,
+            Err(e) => {
+                let mut lock = state.lock();
+                lock.audio_threads.remove(&device_name);
+                let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+                let msg = format!("[{}] AUDIO: Could not start '{}': {}", ts, device_name, e);
+                if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+                lock.logs.push_back(msg);
+                return;
+            }
+        };
+        
+        // Keep the stream alive until this device is deselected; exit bookkeeping under one lock
+        loop {
+            {
+                let mut lock = state.lock();
+                if !running.load(Ordering::Relaxed) || !lock.audio_devices.contains(&device_name) {
+                    lock.audio_threads.remove(&device_name);
+                    break;
+                }
+            }
+            thread::sleep(Duration::from_secs(1));
+        }
+    });
+}
+
+/// Spawns a harvester for `device_name` unless one is already running for it
+fn ensure_audio_harvester(tx: &Sender<(String, Vec<u8>)>, running: &Arc<AtomicBool>, state: &Arc<Mutex<SharedState>>, device_name: &str) {
+    let mut lock = state.lock();
+    if !lock.audio_devices.iter().any(|d| d == device_name) {
+        lock.audio_devices.push(device_name.to_string());
+    }
+    if lock.audio_threads.insert(device_name.to_string()) {
+        drop(lock);
+        start_audio_harvester(tx.clone(), running.clone(), state.clone(), device_name.to_string());
+    }
+}
+
+fn start_system_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        use sysinfo::System;
+        let mut sys = System::new_all();
+        
+        while running.load(Ordering::Relaxed) {
+            let enabled = state.lock().harvester_states.system;
+            if enabled {
+                sys.refresh_all();
+                let mut raw_bytes = Vec::with_capacity(128);
+                
+                for cpu in sys.cpus() {
+                    let usage_bits = cpu.cpu_usage().to_bits();
+                    let freq = cpu.frequency();
+                    raw_bytes.extend_from_slice(&usage_bits.to_le_bytes());
+                    raw_bytes.extend_from_slice(&freq.to_le_bytes());
+                }
+                
+                let nanos = get_timestamp_nanos();
+                raw_bytes.extend_from_slice(&nanos.to_le_bytes());
+                let mem = sys.used_memory();
+                raw_bytes.extend_from_slice(&mem.to_le_bytes());
+                let avail = sys.available_memory();
+                raw_bytes.extend_from_slice(&avail.to_le_bytes());
+                
+                if !raw_bytes.is_empty() && admits_sample(&state, &raw_bytes) {
+                    let _ = tx.try_send(("SYS".to_string(), raw_bytes));
+                }
+            }
+            thread::sleep(Duration::from_millis(500));
+        }
+    });
+}
+
+fn start_mouse_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        use rdev::{listen, EventType};
+        
+        let counter = Arc::new(AtomicUsize::new(0));
+        let counter_clone = counter.clone();
+        let last_instant = Arc::new(Mutex::new(Instant::now()));
+        let last_instant_clone = last_instant.clone();
+        
+        let callback = move |event: rdev::Event| {
+            if !running.load(Ordering::Relaxed) { return; }
+            
+            let enabled = state.lock().harvester_states.mouse;
+            if !enabled { return; }
+            
+            match event.event_type {
+                EventType::MouseMove { x, y } => {
+                    let count = counter_clone.fetch_add(1, Ordering::Relaxed);
+                    if count % 20 != 0 { return; }
+                    
+                    let now = Instant::now();
+                    let mut last = last_instant_clone.lock();
+                    let delta_nanos = now.duration_since(*last).as_nanos() as u64;
+                    *last = now;
+                    drop(last);
+                    
+                    let mut payload = Vec::with_capacity(24);
+                    payload.extend_from_slice(&(x as f64).to_bits().to_le_bytes());
+                    payload.extend_from_slice(&(y as f64).to_bits().to_le_bytes());
+                    payload.extend_from_slice(&delta_nanos.to_le_bytes());
+                    
+                    let _ = tx.try_send(("MOUSE_MOV".to_string(), payload));
+                },
+                EventType::ButtonPress(btn) => {
+                    let now = Instant::now();
+                    let mut last = last_instant_clone.lock();
+                    let delta_nanos = now.duration_since(*last).as_nanos() as u64;
+                    *last = now;
+                    drop(last);
+                    
+                    let mut payload = Vec::with_capacity(24);
+                    let btn_bytes = format!("{:?}", btn).into_bytes();
+                    payload.extend_from_slice(&btn_bytes);
+                    payload.extend_from_slice(&delta_nanos.to_le_bytes());
+                    payload.extend_from_slice(&get_timestamp_nanos().to_le_bytes());
+                    
+                    let _ = tx.try_send(("MOUSE_CLK".to_string(), payload));
+                }
+                _ => {}
+            }
+        };
+        
+        let _ = listen(callback);
+    });
+}
+
+/// Crop an RGB frame to the ROI (clamped to the frame); whole frame when unset
+fn crop_rgb_region(rgb: &[u8], width: u32, height: u32, roi: Option<(u32, u32, u32, u32)>) -> Vec<u8> {
+    let (x, y, w, h) = roi.unwrap_or((0, 0, width, height));
+    let x = x.min(width);
+    let y = y.min(height);
+    let w = w.min(width - x) as usize;
+    let h = h.min(height - y) as usize;
+    
+    let mut region = Vec::with_capacity(w * h * 3);
+    for row in y as usize..y as usize + h {
+        let start = (row * width as usize + x as usize) * 3;
+        if let Some(line) = rgb.get(start..start + w * 3) {
+            region.extend_from_slice(line);
+        }
+    }
+    region
+}
+
+/// Lowest 2 bits of each per-pixel difference, packed four to a byte
+fn pack_frame_noise(current: &[u8], previous: &[u8]) -> Vec<u8> {
+    let diffs: Vec<u8> = current.iter().zip(previous)
+        .map(|(&c, &p)| c.wrapping_sub(p) & 0x03)
+        .collect();
+    diffs.chunks(4)
+        .map(|c| c.iter().enumerate().fold(0u8, |acc, (i, &d)| acc | (d << (i * 2))))
+        .collect()
+}
+
+/// Pushes a camera control to its maximum, returning the previous value for restoring
+fn max_camera_control(
+    camera: &mut nokhwa::Camera,
+    control: nokhwa::utils::KnownCameraControl
+) -> Option<nokhwa::utils::ControlValueSetter> {
+    use nokhwa::utils::{ControlValueDescription, ControlValueSetter};
+    
+    let current = camera.camera_control(control).ok()?;
+    let max = match current.description() {
+        ControlValueDescription::IntegerRange { max, .. } => *max,
+        _ => return None,
+    };
+    camera.set_camera_control(control, ControlValueSetter::Integer(max)).ok()?;
+    Some(current.value())
+}
+
+fn start_video_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>, device_index: u32) {
+    thread::spawn(move || {
+        use nokhwa::pixel_format::RgbFormat;
+        use nokhwa::utils::{CameraIndex, RequestedFormat, RequestedFormatType};
+        use nokhwa::Camera;
+        
+        let index = CameraIndex::Index(device_index);
+        let format = RequestedFormat::new::<RgbFormat>(RequestedFormatType::AbsoluteHighestFrameRate);
+        let source = format!("VIDEO_{}", device_index);
+        
+        let camera = Camera::new(index, format).ok().and_then(|mut c| {
+            c.open_stream().ok()?;
+            Some(c)
+        });
+        
+        let mut camera = match camera {
+            Some(c) => c,
+            None => {
+                let mut lock = state.lock();
+                lock.video_threads.remove(&device_index);
+                let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+                let msg = format!("[{}] VIDEO: Camera {} unavailable", ts, device_index);
+                if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+                lock.logs.push_back(msg);
+                return;
+            }
+        };
+        
+        let mut last_frame_hash: Option<[u8; 32]> = None;
+        let mut last_region: Vec<u8> = Vec::new();
+        let mut saved_controls: Vec<(nokhwa::utils::KnownCameraControl, nokhwa::utils::ControlValueSetter)> = Vec::new();
+        let mut dark_applied = false;
+        
+        loop {
+            // Exit check and thread bookkeeping happen under one lock so a re-add can't race us
+            let (enabled, roi, dark) = {
+                let mut lock = state.lock();
+                if !running.load(Ordering::Relaxed) || !lock.video_devices.contains(&device_index) {
+                    lock.video_threads.remove(&device_index);
+                    break;
+                }
+                (lock.harvester_states.video, lock.video_roi, lock.video_dark_mode)
+            };
+            
+            if dark != dark_applied {
+                use nokhwa::utils::KnownCameraControl;
+                if dark {
+                    for control in [KnownCameraControl::Exposure, KnownCameraControl::Gain] {
+                        if let Some(previous) = max_camera_control(&mut camera, control) {
+                            saved_controls.push((control, previous));
+                        }
+                    }
+                } else {
+                    for (control, previous) in saved_controls.drain(..) {
+                        let _ = camera.set_camera_control(control, previous);
+                    }
+                }
+                dark_applied = dark;
+                last_region.clear();
+            }
+            
+            if enabled {
+                if let Ok(frame) = camera.frame() {
+                    let mut noise: Vec<u8> = if roi.is_none() && !dark {
+                        frame.buffer().iter()
+                            .step_by(7)
+                            .map(|&b| b & 0x0F)
+                            .collect()
+                    } else {
+                        // ROI and dark mode need pixel coordinates, so work on the decoded frame
+                        let image = match frame.decode_image::<RgbFormat>() {
+                            Ok(img) => img,
+                            Err(_) => { thread::sleep(Duration::from_secs(1)); continue; }
+                        };
+                        let region = crop_rgb_region(image.as_raw(), image.width(), image.height(), roi);
+                        
+                        if dark {
+                            // Differencing cancels fixed-pattern noise; the first frame only primes it
+                            let same_shape = last_region.len() == region.len();
+                            let packed = if same_shape { pack_frame_noise(&region, &last_region) } else { Vec::new() };
+                            last_region = region;
+                            if packed.is_empty() {
+                                thread::sleep(Duration::from_secs(1));
+                                continue;
+                            }
+                            packed
+                        } else {
+                            region.iter()
+                                .step_by(7)
+                                .map(|&b| b & 0x0F)
+                                .collect()
+                        }
+                    };
+                    
+                    let nanos = get_timestamp_nanos();
+                    noise.extend_from_slice(&nanos.to_le_bytes());
+                    
+                    if let Some(ref prev_hash) = last_frame_hash {
+                        for (i, b) in noise.iter_mut().enumerate().take(32) {
+                            *b ^= prev_hash[i % 32];
+                        }
+                    }
+                    
+                    let mut hasher = Sha3_256::new();
+                    hasher.update(&noise);
+                    last_frame_hash = Some(hasher.finalize().into());
+                    
+                    if admits_sample(&state, &noise) {
+                        let _ = tx.try_send((source.clone(), noise));
+                    }
+                }
+            }
+            thread::sleep(Duration::from_secs(1));
+        }
+        
+        for (control, previous) in saved_controls {
+            let _ = camera.set_camera_control(control, previous);
+        }
+        let _ = camera.stop_stream();
+    });
+}
+
+/// Spawns a harvester for `index` unless one is already running for it
+fn ensure_video_harvester(tx: &Sender<(String, Vec<u8>)>, running: &Arc<AtomicBool>, state: &Arc<Mutex<SharedState>>, index: u32) {
+    let mut lock = state.lock();
+    if !lock.video_devices.contains(&index) {
+        lock.video_devices.push(index);
+    }
+    if lock.video_threads.insert(index) {
+        drop(lock);
+        start_video_harvester(tx.clone(), running.clone(), state.clone(), index);
+    }
+}
+
+fn start_net_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        use sysinfo::Networks;
+        
+        let mut networks = Networks::new_with_refreshed_list();
+        let mut last_activity = Instant::now();
+        let mut raw_bytes: Vec<u8> = Vec::with_capacity(128);
+        
+        while running.load(Ordering::Relaxed) {
+            let enabled = state.lock().harvester_states.net;
+            if enabled {
+                networks.refresh();
+                
+                for data in networks.values() {
+                    let rx_packets = data.packets_received();
+                    let tx_packets = data.packets_transmitted();
+                    if rx_packets == 0 && tx_packets == 0 {
+                        continue;
+                    }
+                    
+                    // Jitter: time since the previous observed counter movement
+                    let now = Instant::now();
+                    let delta_nanos = now.duration_since(last_activity).as_nanos() as u64;
+                    last_activity = now;
+                    
+                    // Only the low-order bits move unpredictably; high bytes are mostly zero
+                    raw_bytes.extend_from_slice(&(delta_nanos as u32).to_le_bytes());
+                    raw_bytes.extend_from_slice(&(data.received() as u16).to_le_bytes());
+                    raw_bytes.extend_from_slice(&(data.transmitted() as u16).to_le_bytes());
+                    raw_bytes.push((rx_packets ^ tx_packets.rotate_left(4)) as u8);
+                    raw_bytes.push(get_timestamp_nanos() as u8);
+                }
+                
+                // Batch deltas so the health checks see a meaningful sample
+                if raw_bytes.len() >= 64 {
+                    if admits_sample(&state, &raw_bytes) {
+                        let _ = tx.try_send(("NET_JITTER".to_string(), raw_bytes.clone()));
+                    }
+                    raw_bytes.clear();
+                }
+            } else {
+                raw_bytes.clear();
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+    });
+}
+
+fn read_sysfs_u64(path: &std::path::Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+fn collect_sysfs_gpu_counters(counters: &mut Vec<(String, u64)>) {
+    let cards = match fs::read_dir("/sys/class/drm") {
+        Ok(d) => d,
+        Err(_) => return,
+    };
+    
+    for entry in cards.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        // Skip connector nodes like card0-HDMI-A-1
+        if !name.starts_with("card") || name.contains('-') { continue; }
+        let base = entry.path();
+        
+        // AMD (amdgpu) and Intel (i915) expose different counter sets
+        for file in ["device/gpu_busy_percent", "device/mem_busy_percent", "device/mem_info_vram_used",
+                     "gt_cur_freq_mhz", "gt_act_freq_mhz"] {
+            let path = base.join(file);
+            if let Some(v) = read_sysfs_u64(&path) {
+                counters.push((path.display().to_string(), v));
+            }
+        }
+        
+        if let Ok(hwmons) = fs::read_dir(base.join("device/hwmon")) {
+            for hwmon in hwmons.flatten() {
+                for file in ["temp1_input", "power1_average", "freq1_input", "fan1_input"] {
+                    let path = hwmon.path().join(file);
+                    if let Some(v) = read_sysfs_u64(&path) {
+                        counters.push((path.display().to_string(), v));
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn collect_nvml_counters(nvml: &nvml_wrapper::Nvml, counters: &mut Vec<(String, u64)>) {
+    use nvml_wrapper::enum_wrappers::device::{Clock, TemperatureSensor};
+    
+    let count = nvml.device_count().unwrap_or(0);
+    for i in 0..count {
+        let device = match nvml.device_by_index(i) {
+            Ok(d) => d,
+            Err(_) => continue,
+        };
+        if let Ok(util) = device.utilization_rates() {
+            counters.push((format!("nvml{}/util_gpu", i), util.gpu as u64));
+            counters.push((format!("nvml{}/util_mem", i), util.memory as u64));
+        }
+        if let Ok(clock) = device.clock_info(Clock::Graphics) {
+            counters.push((format!("nvml{}/clock", i), clock as u64));
+        }
+        if let Ok(temp) = device.temperature(TemperatureSensor::Gpu) {
+            counters.push((format!("nvml{}/temp", i), temp as u64));
+        }
+        if let Ok(mem) = device.memory_info() {
+            counters.push((format!("nvml{}/vram_used", i), mem.used));
+        }
+        if let Ok(power) = device.power_usage() {
+            counters.push((format!("nvml{}/power_mw", i), power as u64));
+        }
+    }
+}
+
+fn start_gpu_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        // NVML is loaded at runtime; machines without NVIDIA drivers just use sysfs
+        let nvml = nvml_wrapper::Nvml::init().ok();
+        
+        let mut last_values: HashMap<String, u64> = HashMap::new();
+        let mut counters: Vec<(String, u64)> = Vec::new();
+        let mut raw_bytes: Vec<u8> = Vec::with_capacity(128);
+        let mut last_poll = Instant::now();
+        
+        while running.load(Ordering::Relaxed) {
+            let enabled = state.lock().harvester_states.gpu;
+            if enabled {
+                counters.clear();
+                if let Some(ref nvml) = nvml {
+                    collect_nvml_counters(nvml, &mut counters);
+                }
+                collect_sysfs_gpu_counters(&mut counters);
+                
+                let poll_nanos = last_poll.elapsed().as_nanos() as u64;
+                last_poll = Instant::now();
+                
+                if !counters.is_empty() {
+                    for (key, value) in &counters {
+                        let prev = last_values.insert(key.clone(), *value).unwrap_or(0);
+                        let delta = value.wrapping_sub(prev);
+                        raw_bytes.extend_from_slice(&(delta as u16).to_le_bytes());
+                        raw_bytes.push(*value as u8);
+                    }
+                    raw_bytes.extend_from_slice(&(poll_nanos as u32).to_le_bytes());
+                    raw_bytes.extend_from_slice(&(get_timestamp_nanos() as u32).to_le_bytes());
+                }
+                
+                if raw_bytes.len() >= 64 {
+                    if admits_sample(&state, &raw_bytes) {
+                        let _ = tx.try_send(("GPU".to_string(), raw_bytes.clone()));
+                    }
+                    raw_bytes.clear();
+                }
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+    });
+}
+
+fn collect_hwmon_readings(readings: &mut Vec<u64>) {
+    let hwmons = match fs::read_dir("/sys/class/hwmon") {
+        Ok(d) => d,
+        Err(_) => return,
+    };
+    
+    for hwmon in hwmons.flatten() {
+        let files = match fs::read_dir(hwmon.path()) {
+            Ok(f) => f,
+            Err(_) => continue,
+        };
+        for file in files.flatten() {
+            let name = file.file_name().to_string_lossy().to_string();
+            // temp*_input is millidegrees C, fan*_input is RPM
+            if (name.starts_with("temp") || name.starts_with("fan")) && name.ends_with("_input") {
+                if let Some(v) = read_sysfs_u64(&file.path()) {
+                    readings.push(v);
+                }
+            }
+        }
+    }
+}
+
+fn start_thermal_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        use sysinfo::Components;
+        
+        // sysinfo covers macOS (SMC) and Windows; hwmon adds fan RPM on Linux
+        let mut components = Components::new_with_refreshed_list();
+        let mut readings: Vec<u64> = Vec::new();
+        let mut raw_bytes: Vec<u8> = Vec::with_capacity(128);
+        
+        while running.load(Ordering::Relaxed) {
+            let enabled = state.lock().harvester_states.thermal;
+            if enabled {
+                readings.clear();
+                collect_hwmon_readings(&mut readings);
+                if readings.is_empty() {
+                    components.refresh();
+                    for component in components.iter() {
+                        readings.push(component.temperature().to_bits() as u64);
+                    }
+                }
+                
+                // Keep only the low-order bits; the high bits track slow thermal drift
+                for &r in &readings {
+                    raw_bytes.push(r as u8);
+                }
+                if !readings.is_empty() {
+                    raw_bytes.extend_from_slice(&(get_timestamp_nanos() as u32).to_le_bytes());
+                }
+                
+                if raw_bytes.len() >= 64 {
+                    if admits_sample(&state, &raw_bytes) {
+                        let _ = tx.try_send(("THERMAL".to_string(), raw_bytes.clone()));
+                    }
+                    raw_bytes.clear();
+                }
+            }
+            thread::sleep(Duration::from_millis(250));
+        }
+    });
+}
+
+// BLE scanning needs btleplug (DBus on Linux), so it is behind the `bluetooth` feature
+#[cfg(feature = "bluetooth")]
+fn start_bt_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        let rt = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+            Ok(rt) => rt,
+            Err(_) => return,
+        };
+        rt.block_on(bt_scan_loop(tx, running, state));
+    });
+}
+
+#[cfg(not(feature = "bluetooth"))]
+fn start_bt_harvester(_tx: Sender<(String, Vec<u8>)>, _running: Arc<AtomicBool>, _state: Arc<Mutex<SharedState>>) {}
+
+#[cfg(feature = "bluetooth")]
+async fn bt_scan_loop(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    use btleplug::api::{Central, CentralEvent, Manager as _, Peripheral as _, ScanFilter};
+    use btleplug::platform::Manager;
+    use futures::stream::StreamExt;
+    
+    let manager = match Manager::new().await {
+        Ok(m) => m,
+        Err(_) => return,
+    };
+    let central = match manager.adapters().await.ok().and_then(|a| a.into_iter().next()) {
+        Some(c) => c,
+        None => return,
+    };
+    let mut events = match central.events().await {
+        Ok(e) => e,
+        Err(_) => return,
+    };
+    
+    let mut last_seen: HashMap<[u8; 6], Instant> = HashMap::new();
+    let mut last_arrival = Instant::now();
+    let mut raw_bytes: Vec<u8> = Vec::with_capacity(128);
+    
+    while running.load(Ordering::Relaxed) {
+        let enabled = state.lock().harvester_states.bt;
+        if !enabled {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            continue;
+        }
+        
+        if central.start_scan(ScanFilter::default()).await.is_err() {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            continue;
+        }
+        
+        // Scan window: 5s listening, then 10s idle
+        let scan_deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < scan_deadline && running.load(Ordering::Relaxed) {
+            let remaining = scan_deadline.saturating_duration_since(Instant::now());
+            let event = match tokio::time::timeout(remaining, events.next()).await {
+                Ok(Some(e)) => e,
+                _ => break,
+            };
+            let id = match event {
+                CentralEvent::DeviceDiscovered(id) | CentralEvent::DeviceUpdated(id) => id,
+                _ => continue,
+            };
+            let props = match central.peripheral(&id).await {
+                Ok(p) => p.properties().await.ok().flatten(),
+                Err(_) => None,
+            };
+            let (rssi, mac) = match props {
+                Some(p) => match p.rssi {
+                    Some(r) => (r, p.address.into_inner()),
+                    None => continue,
+                },
+                None => continue,
+            };
+            
+            let now = Instant::now();
+            // Random-address rotation would grow this forever
+            if last_seen.len() > 1024 { last_seen.clear(); }
+            let mac_delta = last_seen.insert(mac, now)
+                .map(|t| now.duration_since(t).as_nanos() as u64)
+                .unwrap_or(0);
+            let arrival_delta = now.duration_since(last_arrival).as_nanos() as u64;
+            last_arrival = now;
+            
+            raw_bytes.push(rssi as u8);
+            raw_bytes.extend_from_slice(&(mac_delta as u32).to_le_bytes());
+            raw_bytes.extend_from_slice(&(arrival_delta as u32).to_le_bytes());
+            raw_bytes.push(mac[5]);
+            
+            if raw_bytes.len() >= 64 {
+                if admits_sample(&state, &raw_bytes) {
+                    let _ = tx.try_send(("BT".to_string(), raw_bytes.clone()));
+                }
+                raw_bytes.clear();
+            }
+        }
+        
+        let _ = central.stop_scan().await;
+        tokio::time::sleep(Duration::from_secs(10)).await;
+    }
+}
+
+fn start_wifi_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        let mut last_counters: HashMap<String, [u64; 5]> = HashMap::new();
+        let mut last_poll = Instant::now();
+        let mut raw_bytes: Vec<u8> = Vec::with_capacity(128);
+        
+        while running.load(Ordering::Relaxed) {
+            let enabled = state.lock().harvester_states.wifi;
+            if enabled {
+                // Linux wireless extensions: iface: status link level noise nwid crypt frag retry misc beacon
+                if let Ok(table) = fs::read_to_string("/proc/net/wireless") {
+                    let poll_nanos = last_poll.elapsed().as_nanos() as u64;
+                    last_poll = Instant::now();
+                    
+                    for line in table.lines().skip(2) {
+                        let fields: Vec<&str> = line.split_whitespace().collect();
+                        if fields.len() < 11 { continue; }
+                        
+                        let parse = |f: &str| f.trim_end_matches('.').parse::<f64>().unwrap_or(0.0);
+                        let link = parse(fields[2]);
+                        let level = parse(fields[3]);
+                        let noise = parse(fields[4]);
+                        
+                        let mut counters = [0u64; 5];
+                        for (i, f) in fields[6..11].iter().enumerate() {
+                            counters[i] = f.parse().unwrap_or(0);
+                        }
+                        let prev = last_counters.insert(fields[0].to_string(), counters).unwrap_or(counters);
+                        
+                        raw_bytes.push(link as i64 as u8);
+                        raw_bytes.push(level as i64 as u8);
+                        raw_bytes.push(noise as i64 as u8);
+                        for (now, before) in counters.iter().zip(prev.iter()) {
+                            raw_bytes.push(now.wrapping_sub(*before) as u8);
+                        }
+                        raw_bytes.extend_from_slice(&(poll_nanos as u16).to_le_bytes());
+                    }
+                }
+                
+                if raw_bytes.len() >= 64 {
+                    if admits_sample(&state, &raw_bytes) {
+                        let _ = tx.try_send(("WIFI".to_string(), raw_bytes.clone()));
+                    }
+                    raw_bytes.clear();
+                }
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+    });
+}
+
+fn start_hwrng_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        use std::io::Read;
+        
+        let mut device: Option<(String, fs::File)> = None;
+        
+        while running.load(Ordering::Relaxed) {
+            let (enabled, path) = {
+                let lock = state.lock();
+                (lock.harvester_states.hwrng, lock.hwrng_path.clone())
+            };
+            if enabled {
+                // Reopen when the configured path changes or the dongle was unplugged
+                if device.as_ref().map(|(p, _)| p != &path).unwrap_or(true) {
+                    device = fs::File::open(&path).ok().map(|f| (path.clone(), f));
+                }
+                
+                if let Some((_, ref mut file)) = device {
+                    let mut buf = [0u8; 512];
+                    match file.read_exact(&mut buf) {
+                        Ok(()) => {
+                            if admits_sample(&state, &buf) {
+                                let _ = tx.try_send(("HWRNG".to_string(), buf.to_vec()));
+                            }
+                        },
+                        Err(_) => device = None,
+                    }
+                }
+            } else {
+                device = None;
+            }
+            thread::sleep(Duration::from_secs(1));
+        }
+    });
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "rdseed")]
+unsafe fn fill_rdseed(buf: &mut [u8]) -> bool {
+    use std::arch::x86_64::_rdseed64_step;
+    for chunk in buf.chunks_mut(8) {
+        let mut val = 0u64;
+        // RDSEED underflows when the conditioner is drained; retry before giving up
+        let mut ok = false;
+        for _ in 0..100 {
+            if _rdseed64_step(&mut val) == 1 { ok = true; break; }
+            std::hint::spin_loop();
+        }
+        if !ok { return false; }
+        let n = chunk.len();
+        chunk.copy_from_slice(&val.to_le_bytes()[..n]);
+    }
+    true
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "rdrand")]
+unsafe fn fill_rdrand(buf: &mut [u8]) -> bool {
+    use std::arch::x86_64::_rdrand64_step;
+    for chunk in buf.chunks_mut(8) {
+        let mut val = 0u64;
+        let mut ok = false;
+        for _ in 0..10 {
+            if _rdrand64_step(&mut val) == 1 { ok = true; break; }
+        }
+        if !ok { return false; }
+        let n = chunk.len();
+        chunk.copy_from_slice(&val.to_le_bytes()[..n]);
+    }
+    true
+}
+
+/// Returns the instruction used, or None if the CPU has no DRNG
+#[cfg(target_arch = "x86_64")]
+fn read_cpu_rng(buf: &mut [u8]) -> Option<&'static str> {
+    if is_x86_feature_detected!("rdseed") && unsafe { fill_rdseed(buf) } {
+        Some("RDSEED")
+    } else if is_x86_feature_detected!("rdrand") && unsafe { fill_rdrand(buf) } {
+        Some("RDRAND")
+    } else {
+        None
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn read_cpu_rng(_buf: &mut [u8]) -> Option<&'static str> {
+    None
+}
+
+fn start_cpu_rng_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        let mut last_instruction: Option<&'static str> = None;
+        
+        while running.load(Ordering::Relaxed) {
+            let enabled = state.lock().harvester_states.cpu_rng;
+            if enabled {
+                let mut buf = [0u8; 1024];
+                let instruction = read_cpu_rng(&mut buf);
+                
+                if instruction != last_instruction {
+                    last_instruction = instruction;
+                    let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+                    let msg = format!("[{}] CPU_RNG: Using {}", ts, instruction.unwrap_or("nothing (no RDSEED/RDRAND)"));
+                    let mut lock = state.lock();
+                    if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+                    lock.logs.push_back(msg);
+                }
+                
+                if instruction.is_some() && admits_sample(&state, &buf) {
+                    let _ = tx.try_send(("CPU_RNG".to_string(), buf.to_vec()));
+                }
+            }
+            thread::sleep(Duration::from_secs(1));
+        }
+    });
+}
+
+fn start_ping_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+        
+        // ICMP needs raw sockets (root); a TCP handshake gives the same RTT without privileges
+        let mut resolved: HashMap<String, SocketAddr> = HashMap::new();
+        let mut last_probe: HashMap<String, Instant> = HashMap::new();
+        let mut last_rtt: HashMap<String, u64> = HashMap::new();
+        let mut next_target = 0usize;
+        let mut raw_bytes: Vec<u8> = Vec::with_capacity(128);
+        
+        while running.load(Ordering::Relaxed) {
+            let (enabled, targets) = {
+                let lock = state.lock();
+                (lock.harvester_states.ping, lock.ping_targets.clone())
+            };
+            if enabled && !targets.is_empty() {
+                next_target = (next_target + 1) % targets.len();
+                let target = &targets[next_target];
+                
+                let due = last_probe.get(target)
+                    .map(|t| t.elapsed() >= Duration::from_millis(PING_PER_HOST_INTERVAL_MS))
+                    .unwrap_or(true);
+                
+                if due {
+                    last_probe.insert(target.clone(), Instant::now());
+                    
+                    if !resolved.contains_key(target) {
+                        if let Some(addr) = target.to_socket_addrs().ok().and_then(|mut a| a.next()) {
+                            resolved.insert(target.clone(), addr);
+                        }
+                    }
+                    
+                    if let Some(addr) = resolved.get(target) {
+                        let start = Instant::now();
+                        if TcpStream::connect_timeout(addr, Duration::from_secs(1)).is_ok() {
+                            let rtt = start.elapsed().as_nanos() as u64;
+                            let prev = last_rtt.insert(target.clone(), rtt).unwrap_or(rtt);
+                            
+                            raw_bytes.extend_from_slice(&(rtt as u32).to_le_bytes());
+                            raw_bytes.extend_from_slice(&(rtt.wrapping_sub(prev) as u16).to_le_bytes());
+                        }
+                    }
+                }
+                
+                if raw_bytes.len() >= 64 {
+                    if admits_sample(&state, &raw_bytes) {
+                        let _ = tx.try_send(("PING".to_string(), raw_bytes.clone()));
+                    }
+                    raw_bytes.clear();
+                }
+            }
+            thread::sleep(Duration::from_millis(PING_MIN_INTERVAL_MS));
+        }
+    });
+}
+
+fn build_dns_query(id: u16, hostname: &str) -> Vec<u8> {
+    let mut query = Vec::with_capacity(18 + hostname.len());
+    query.extend_from_slice(&id.to_be_bytes());
+    query.extend_from_slice(&[0x01, 0x00]);  // Recursion desired
+    query.extend_from_slice(&[0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);  // QDCOUNT=1
+    for label in hostname.trim_end_matches('.').split('.') {
+        query.push(label.len() as u8);
+        query.extend_from_slice(label.as_bytes());
+    }
+    query.push(0);
+    query.extend_from_slice(&[0x00, 0x01, 0x00, 0x01]);  // QTYPE=A, QCLASS=IN
+    query
+}
+
+fn skip_dns_name(resp: &[u8], mut pos: usize) -> Option<usize> {
+    loop {
+        let len = *resp.get(pos)? as usize;
+        if len == 0 { return Some(pos + 1); }
+        if len & 0xC0 == 0xC0 { return Some(pos + 2); }  // Compression pointer
+        pos += len + 1;
+    }
+}
+
+/// TTL of the first answer record, if the response carries one
+fn parse_dns_first_ttl(resp: &[u8]) -> Option<u32> {
+    if resp.len() < 12 { return None; }
+    let ancount = u16::from_be_bytes([resp[6], resp[7]]);
+    if ancount == 0 { return None; }
+    
+    let pos = skip_dns_name(resp, 12)? + 4;   // QTYPE + QCLASS
+    let pos = skip_dns_name(resp, pos)? + 4;  // TYPE + CLASS
+    let ttl = resp.get(pos..pos + 4)?;
+    Some(u32::from_be_bytes([ttl[0], ttl[1], ttl[2], ttl[3]]))
+}
+
+fn start_dns_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        use std::net::UdpSocket;
+        
+        let socket = match UdpSocket::bind("0.0.0.0:0") {
+            Ok(s) => s,
+            Err(_) => return,
+        };
+        let _ = socket.set_read_timeout(Some(Duration::from_secs(2)));
+        
+        let mut rng = rand::thread_rng();
+        let mut round = 0usize;
+        let mut last_rtt = 0u64;
+        let mut last_ttl: HashMap<String, u32> = HashMap::new();
+        let mut raw_bytes: Vec<u8> = Vec::with_capacity(128);
+        
+        while running.load(Ordering::Relaxed) {
+            let (enabled, resolvers, hostnames) = {
+                let lock = state.lock();
+                (lock.harvester_states.dns, lock.dns_resolvers.clone(), lock.dns_hostnames.clone())
+            };
+            if enabled && !resolvers.is_empty() && !hostnames.is_empty() {
+                round = round.wrapping_add(1);
+                let resolver = &resolvers[round % resolvers.len()];
+                let hostname = &hostnames[(round / resolvers.len()) % hostnames.len()];
+                
+                let id: u16 = rng.gen();
+                let query = build_dns_query(id, hostname);
+                let start = Instant::now();
+                
+                if socket.send_to(&query, resolver.as_str()).is_ok() {
+                    let mut resp = [0u8; 512];
+                    if let Ok((len, _)) = socket.recv_from(&mut resp) {
+                        let rtt = start.elapsed().as_nanos() as u64;
+                        let resp = &resp[..len];
+                        
+                        // Ignore stale answers to earlier (timed-out) queries
+                        if len >= 2 && u16::from_be_bytes([resp[0], resp[1]]) == id {
+                            raw_bytes.extend_from_slice(&(rtt as u32).to_le_bytes());
+                            raw_bytes.extend_from_slice(&(rtt.wrapping_sub(last_rtt) as u16).to_le_bytes());
+                            last_rtt = rtt;
+                            
+                            if let Some(ttl) = parse_dns_first_ttl(resp) {
+                                let key = format!("{}@{}", hostname, resolver);
+                                let prev = last_ttl.insert(key, ttl).unwrap_or(ttl);
+                                raw_bytes.push(ttl.wrapping_sub(prev) as u8);
+                                raw_bytes.push(ttl as u8);
+                            }
+                        }
+                    }
+                }
+                
+                if raw_bytes.len() >= 64 {
+                    if admits_sample(&state, &raw_bytes) {
+                        let _ = tx.try_send(("DNS".to_string(), raw_bytes.clone()));
+                    }
+                    raw_bytes.clear();
+                }
+            }
+            thread::sleep(Duration::from_secs(1));
+        }
+    });
+}
+
+fn read_irq_counters(counters: &mut HashMap<String, u64>) {
+    counters.clear();
+    
+    // "  27:   1204   88   IR-PCI-MSI 327680-edge  xhci_hcd" -> sum across CPUs
+    if let Ok(table) = fs::read_to_string("/proc/interrupts") {
+        for line in table.lines().skip(1) {
+            let mut fields = line.split_whitespace();
+            let label = match fields.next() {
+                Some(l) if l.ends_with(':') => l.trim_end_matches(':').to_string(),
+                _ => continue,
+            };
+            let total: u64 = fields.map_while(|f| f.parse::<u64>().ok()).sum();
+            counters.insert(label, total);
+        }
+    }
+    
+    if let Ok(stat) = fs::read_to_string("/proc/stat") {
+        for line in stat.lines() {
+            if let Some(rest) = line.strip_prefix("ctxt ") {
+                counters.insert("ctxt".to_string(), rest.trim().parse().unwrap_or(0));
+            } else if let Some(rest) = line.strip_prefix("intr ") {
+                let total = rest.split_whitespace().next().and_then(|t| t.parse().ok()).unwrap_or(0);
+                counters.insert("intr".to_string(), total);
+            }
+        }
+    }
+}
+
+fn start_irq_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        let mut counters: HashMap<String, u64> = HashMap::new();
+        let mut previous: HashMap<String, u64> = HashMap::new();
+        let mut last_poll = Instant::now();
+        let mut raw_bytes: Vec<u8> = Vec::with_capacity(256);
+        
+        while running.load(Ordering::Relaxed) {
+            let enabled = state.lock().harvester_states.irq;
+            if enabled {
+                read_irq_counters(&mut counters);
+                let poll_nanos = last_poll.elapsed().as_nanos() as u64;
+                last_poll = Instant::now();
+                
+                if !previous.is_empty() {
+                    for (label, &count) in &counters {
+                        let delta = count.wrapping_sub(previous.get(label).copied().unwrap_or(count));
+                        // Idle IRQs contribute nothing but zeros
+                        if delta != 0 {
+                            raw_bytes.push(delta as u8);
+                        }
+                    }
+                    raw_bytes.extend_from_slice(&(poll_nanos as u16).to_le_bytes());
+                }
+                std::mem::swap(&mut counters, &mut previous);
+                
+                if raw_bytes.len() >= 64 {
+                    if admits_sample(&state, &raw_bytes) {
+                        let _ = tx.try_send(("IRQ".to_string(), raw_bytes.clone()));
+                    }
+                    raw_bytes.clear();
+                }
+            } else {
+                previous.clear();
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    });
+}
+
+fn start_power_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        let mut previous: HashMap<std::path::PathBuf, u64> = HashMap::new();
+        let mut raw_bytes: Vec<u8> = Vec::with_capacity(128);
+        
+        while running.load(Ordering::Relaxed) {
+            let enabled = state.lock().harvester_states.power;
+            if enabled {
+                if let Ok(supplies) = fs::read_dir("/sys/class/power_supply") {
+                    let mut sampled = false;
+                    for supply in supplies.flatten() {
+                        // µV, µA, µAh, µWh, µW as exposed by the battery/AC drivers
+                        for file in ["voltage_now", "current_now", "charge_now", "energy_now", "power_now"] {
+                            let path = supply.path().join(file);
+                            if let Some(v) = read_sysfs_u64(&path) {
+                                let prev = previous.insert(path, v).unwrap_or(v);
+                                raw_bytes.push(v as u8);
+                                raw_bytes.push(v.wrapping_sub(prev) as u8);
+                                sampled = true;
+                            }
+                        }
+                    }
+                    if sampled {
+                        raw_bytes.extend_from_slice(&(get_timestamp_nanos() as u16).to_le_bytes());
+                    }
+                }
+                
+                if raw_bytes.len() >= 64 {
+                    if admits_sample(&state, &raw_bytes) {
+                        let _ = tx.try_send(("POWER".to_string(), raw_bytes.clone()));
+                    }
+                    raw_bytes.clear();
+                }
+            }
+            thread::sleep(Duration::from_millis(200));
+        }
+    });
+}
+
+fn find_iio_channels(prefixes: &[&str]) -> Vec<std::path::PathBuf> {
+    let mut channels = Vec::new();
+    if let Ok(devices) = fs::read_dir("/sys/bus/iio/devices") {
+        for device in devices.flatten() {
+            if let Ok(files) = fs::read_dir(device.path()) {
+                for file in files.flatten() {
+                    let name = file.file_name().to_string_lossy().to_string();
+                    if name.ends_with("_raw") && prefixes.iter().any(|p| name.starts_with(p)) {
+                        channels.push(file.path());
+                    }
+                }
+            }
+        }
+    }
+    channels
+}
+
+fn start_imu_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        // iio exposes accel/gyro/magnetometer axes as signed raw ADC counts
+        let channels = find_iio_channels(&["in_accel_", "in_anglvel_", "in_magn_"]);
+        if channels.is_empty() { return; }
+        
+        let mut raw_bytes: Vec<u8> = Vec::with_capacity(128);
+        
+        while running.load(Ordering::Relaxed) {
+            let enabled = state.lock().harvester_states.imu;
+            if enabled {
+                for channel in &channels {
+                    if let Some(v) = fs::read_to_string(channel).ok().and_then(|s| s.trim().parse::<i64>().ok()) {
+                        raw_bytes.push(v as u8);
+                    }
+                }
+                raw_bytes.push(get_timestamp_nanos() as u8);
+                
+                if raw_bytes.len() >= 64 {
+                    if admits_sample(&state, &raw_bytes) {
+                        let _ = tx.try_send(("IMU".to_string(), raw_bytes.clone()));
+                    }
+                    raw_bytes.clear();
+                }
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+    });
+}
+
+// RTL-SDR needs librtlsdr, so it is behind the `sdr` feature
+#[cfg(feature = "sdr")]
+fn start_sdr_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        let mut device: Option<rtlsdr::RTLSDRDevice> = None;
+        let mut tuned_hz = 0u32;
+        
+        while running.load(Ordering::Relaxed) {
+            let (enabled, frequency) = {
+                let lock = state.lock();
+                (lock.harvester_states.sdr, lock.sdr_frequency_hz)
+            };
+            if enabled {
+                if device.is_none() {
+                    device = rtlsdr::open(0).ok().and_then(|mut d| {
+                        // Auto gain keeps the noise floor in the ADC's useful range
+                        d.set_sample_rate(1_024_000).ok()?;
+                        d.set_tuner_gain_mode(false).ok()?;
+                        Some(d)
+                    });
+                    tuned_hz = 0;
+                }
+                
+                if let Some(ref mut d) = device {
+                    if tuned_hz != frequency && d.set_center_freq(frequency).is_ok() {
+                        tuned_hz = frequency;
+                        let _ = d.reset_buffer();
+                    }
+                    
+                    match d.read_sync(16384) {
+                        Ok(iq) => {
+                            // Pack the 2 LSBs of four consecutive I/Q bytes into one byte
+                            let noise: Vec<u8> = iq.chunks_exact(4)
+                                .map(|c| (c[0] & 3) | ((c[1] & 3) << 2) | ((c[2] & 3) << 4) | ((c[3] & 3) << 6))
+                                .collect();
+                            if admits_sample(&state, &noise) {
+                                let _ = tx.try_send(("SDR".to_string(), noise));
+                            }
+                        },
+                        Err(_) => device = None,
+                    }
+                }
+            } else if let Some(mut d) = device.take() {
+                let _ = d.close();
+            }
+            thread::sleep(Duration::from_secs(1));
+        }
+    });
+}
+
+#[cfg(not(feature = "sdr"))]
+fn start_sdr_harvester(_tx: Sender<(String, Vec<u8>)>, _running: Arc<AtomicBool>, _state: Arc<Mutex<SharedState>>) {}
+
+fn start_serial_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        use std::io::Read;
+        
+        let mut port: Option<((String, u32), Box<dyn serialport::SerialPort>)> = None;
+        let mut raw_bytes: Vec<u8> = Vec::with_capacity(512);
+        
+        while running.load(Ordering::Relaxed) {
+            let (enabled, configured) = {
+                let lock = state.lock();
+                (lock.harvester_states.serial, lock.serial_device.clone())
+            };
+            let configured = match configured {
+                Some(c) if enabled => c,
+                _ => {
+                    port = None;
+                    raw_bytes.clear();
+                    thread::sleep(Duration::from_secs(1));
+                    continue;
+                }
+            };
+            
+            // (Re)open on first use, config change, or after the device dropped out
+            if port.as_ref().map(|(c, _)| c != &configured).unwrap_or(true) {
+                port = serialport::new(&configured.0, configured.1)
+                    .timeout(Duration::from_millis(500))
+                    .open()
+                    .ok()
+                    .map(|p| (configured.clone(), p));
+                if port.is_none() {
+                    thread::sleep(Duration::from_secs(2));
+                    continue;
+                }
+            }
+            
+            if let Some((_, ref mut p)) = port {
+                let mut buf = [0u8; 256];
+                match p.read(&mut buf) {
+                    Ok(n) => raw_bytes.extend_from_slice(&buf[..n]),
+                    Err(ref e) if e.kind() == std::io::ErrorKind::TimedOut => {},
+                    Err(_) => port = None,
+                }
+            }
+            
+            if raw_bytes.len() >= 256 {
+                if admits_sample(&state, &raw_bytes) {
+                    let _ = tx.try_send(("SERIAL".to_string(), raw_bytes.clone()));
+                }
+                raw_bytes.clear();
+                // Don't drain a slow DIY generator faster than the mixer needs it
+                thread::sleep(Duration::from_millis(200));
+            }
+        }
+    });
+}
+
+fn open_sysfs_gpio(pin: u32) -> Option<fs::File> {
+    let base = format!("/sys/class/gpio/gpio{}", pin);
+    if !std::path::Path::new(&base).exists() {
+        let _ = fs::write("/sys/class/gpio/export", pin.to_string());
+        // udev needs a moment to fix up permissions on the new node
+        thread::sleep(Duration::from_millis(100));
+    }
+    let _ = fs::write(format!("{}/direction", base), "in");
+    fs::File::open(format!("{}/value", base)).ok()
+}
+
+fn start_gpio_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        use std::io::{Read, Seek, SeekFrom};
+        
+        let mut gpio: Option<(u32, fs::File)> = None;
+        let mut debiased: Vec<u8> = Vec::with_capacity(256);
+        
+        while running.load(Ordering::Relaxed) {
+            let (enabled, pin) = {
+                let lock = state.lock();
+                (lock.harvester_states.gpio, lock.gpio_pin)
+            };
+            let pin = match pin {
+                Some(p) if enabled => p,
+                _ => {
+                    gpio = None;
+                    thread::sleep(Duration::from_secs(1));
+                    continue;
+                }
+            };
+            
+            if gpio.as_ref().map(|(p, _)| *p != pin).unwrap_or(true) {
+                gpio = open_sysfs_gpio(pin).map(|f| (pin, f));
+                if gpio.is_none() {
+                    thread::sleep(Duration::from_secs(2));
+                    continue;
+                }
+            }
+            
+            if let Some((_, ref mut file)) = gpio {
+                // Bit-bang 8192 samples, packed MSB first
+                let mut packed = vec![0u8; 1024];
+                let mut value = [0u8; 1];
+                let mut ok = true;
+                for i in 0..8192 {
+                    if file.seek(SeekFrom::Start(0)).is_err() || file.read(&mut value).is_err() {
+                        ok = false;
+                        break;
+                    }
+                    if value[0] == b'1' {
+                        packed[i / 8] |= 0x80 >> (i % 8);
+                    }
+                }
+                
+                if ok {
+                    // Avalanche circuits are heavily biased; debias before health checks
+                    debiased.extend(von_neumann_debias(&packed));
+                } else {
+                    gpio = None;
+                }
+            }
+            
+            if debiased.len() >= 128 {
+                if admits_sample(&state, &debiased) {
+                    let _ = tx.try_send(("GPIO".to_string(), debiased.clone()));
+                }
+                debiased.clear();
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+    });
+}
+
+// PC/SC needs pcsclite, so smartcard tokens are behind the `token` feature
+#[cfg(feature = "token")]
+fn start_token_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        use pcsc::{Context, Protocols, Scope, ShareMode, MAX_BUFFER_SIZE};
+        use std::ffi::CString;
+        
+        // OpenPGP applet (YubiKey, Nitrokey, ...) answers ISO 7816 GET CHALLENGE from its RNG
+        const SELECT_OPENPGP: [u8; 12] = [0x00, 0xA4, 0x04, 0x00, 0x06, 0xD2, 0x76, 0x00, 0x01, 0x24, 0x01, 0x00];
+        const GET_CHALLENGE: [u8; 5] = [0x00, 0x84, 0x00, 0x00, 0x40];
+        
+        let mut context: Option<Context> = None;
+        let mut known_readers: Vec<CString> = Vec::new();
+        let mut raw_bytes: Vec<u8> = Vec::with_capacity(256);
+        
+        while running.load(Ordering::Relaxed) {
+            let enabled = state.lock().harvester_states.token;
+            if enabled {
+                if context.is_none() {
+                    context = Context::establish(Scope::User).ok();
+                }
+                
+                let mut reset_context = false;
+                if let Some(ref ctx) = context {
+                    let mut readers_buf = [0u8; 2048];
+                    let readers: Vec<CString> = match ctx.list_readers(&mut readers_buf) {
+                        Ok(r) => r.map(|r| r.to_owned()).collect(),
+                        Err(pcsc::Error::NoReadersAvailable) => Vec::new(),
+                        Err(_) => {
+                            // pcscd restarted; re-establish on the next pass
+                            reset_context = true;
+                            Vec::new()
+                        }
+                    };
+                    
+                    // Hot-plug: log tokens appearing and disappearing between polls
+                    if readers != known_readers {
+                        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+                        let mut lock = state.lock();
+                        for r in readers.iter().filter(|r| !known_readers.contains(r)) {
+                            if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+                            lock.logs.push_back(format!("[{}] TOKEN: Attached {}", ts, r.to_string_lossy()));
+                        }
+                        for r in known_readers.iter().filter(|r| !readers.contains(r)) {
+                            if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+                            lock.logs.push_back(format!("[{}] TOKEN: Removed {}", ts, r.to_string_lossy()));
+                        }
+                        drop(lock);
+                        known_readers = readers.clone();
+                    }
+                    
+                    for reader in &readers {
+                        let card = match ctx.connect(reader, ShareMode::Shared, Protocols::ANY) {
+                            Ok(c) => c,
+                            Err(_) => continue,
+                        };
+                        let mut rapdu_buf = [0u8; MAX_BUFFER_SIZE];
+                        let selected = card.transmit(&SELECT_OPENPGP, &mut rapdu_buf)
+                            .map(|r| r.ends_with(&[0x90, 0x00]))
+                            .unwrap_or(false);
+                        if !selected { continue; }
+                        
+                        if let Ok(resp) = card.transmit(&GET_CHALLENGE, &mut rapdu_buf) {
+                            if resp.len() > 2 && resp.ends_with(&[0x90, 0x00]) {
+                                raw_bytes.extend_from_slice(&resp[..resp.len() - 2]);
+                            }
+                        }
+                    }
+                }
+                if reset_context {
+                    context = None;
+                }
+                
+                if raw_bytes.len() >= 64 {
+                    if admits_sample(&state, &raw_bytes) {
+                        let _ = tx.try_send(("TOKEN".to_string(), raw_bytes.clone()));
+                    }
+                    raw_bytes.clear();
+                }
+            }
+            thread::sleep(Duration::from_secs(2));
+        }
+    });
+}
+
+#[cfg(not(feature = "token"))]
+fn start_token_harvester(_tx: Sender<(String, Vec<u8>)>, _running: Arc<AtomicBool>, _state: Arc<Mutex<SharedState>>) {}
+
+#[cfg(target_os = "linux")]
+fn open_i2c_device(bus: &str, addr: u16) -> Option<fs::File> {
+    use std::os::unix::io::AsRawFd;
+    const I2C_SLAVE: u64 = 0x0703;
+    
+    let file = fs::OpenOptions::new().read(true).write(true).open(bus).ok()?;
+    let rc = unsafe { libc::ioctl(file.as_raw_fd(), I2C_SLAVE as _, addr as libc::c_ulong) };
+    if rc < 0 { None } else { Some(file) }
+}
+
+#[cfg(target_os = "linux")]
+enum EnvSensor {
+    Bme280(fs::File),
+    Bh1750(fs::File),
+}
+
+#[cfg(target_os = "linux")]
+fn probe_env_sensors(bus: &str) -> Vec<EnvSensor> {
+    use std::io::{Read, Write};
+    let mut sensors = Vec::new();
+    
+    // BME280/BMP280: chip id 0x60/0x58 at register 0xD0
+    for addr in [0x76u16, 0x77] {
+        if let Some(mut dev) = open_i2c_device(bus, addr) {
+            let mut id = [0u8; 1];
+            if dev.write_all(&[0xD0]).is_ok() && dev.read_exact(&mut id).is_ok() && (id[0] == 0x60 || id[0] == 0x58) {
+                // Oversampling x1 for humidity/temperature/pressure, normal mode
+                let _ = dev.write_all(&[0xF2, 0x01]);
+                let _ = dev.write_all(&[0xF4, 0x27]);
+                sensors.push(EnvSensor::Bme280(dev));
+            }
+        }
+    }
+    
+    // BH1750 ambient light: continuous high-resolution mode
+    for addr in [0x23u16, 0x5C] {
+        if let Some(mut dev) = open_i2c_device(bus, addr) {
+            if dev.write_all(&[0x10]).is_ok() {
+                sensors.push(EnvSensor::Bh1750(dev));
+            }
+        }
+    }
+    sensors
+}
+
+#[cfg(target_os = "linux")]
+fn start_env_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        use std::io::{Read, Write};
+        
+        let mut probed_bus: Option<String> = None;
+        let mut sensors: Vec<EnvSensor> = Vec::new();
+        let mut raw_bytes: Vec<u8> = Vec::with_capacity(128);
+        
+        while running.load(Ordering::Relaxed) {
+            let (enabled, bus) = {
+                let lock = state.lock();
+                (lock.harvester_states.env, lock.i2c_bus.clone())
+            };
+            if enabled {
+                if probed_bus.as_ref() != Some(&bus) {
+                    sensors = probe_env_sensors(&bus);
+                    probed_bus = Some(bus);
+                }
+                
+                for sensor in sensors.iter_mut() {
+                    match sensor {
+                        EnvSensor::Bme280(dev) => {
+                            // 0xF7..0xFE: press[19:0], temp[19:0], hum[15:0]; keep the fractional LSB bytes
+                            let mut data = [0u8; 8];
+                            if dev.write_all(&[0xF7]).is_ok() && dev.read_exact(&mut data).is_ok() {
+                                raw_bytes.extend_from_slice(&[data[1], data[2], data[4], data[5], data[7]]);
+                            }
+                        },
+                        EnvSensor::Bh1750(dev) => {
+                            let mut data = [0u8; 2];
+                            if dev.read_exact(&mut data).is_ok() {
+                                raw_bytes.push(data[1]);
+                            }
+                        },
+                    }
+                }
+                
+                if raw_bytes.len() >= 64 {
+                    if admits_sample(&state, &raw_bytes) {
+                        let _ = tx.try_send(("ENV".to_string(), raw_bytes.clone()));
+                    }
+                    raw_bytes.clear();
+                }
+            } else {
+                probed_bus = None;
+                sensors.clear();
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+    });
+}
+
+#[cfg(not(target_os = "linux"))]
+fn start_env_harvester(_tx: Sender<(String, Vec<u8>)>, _running: Arc<AtomicBool>, _state: Arc<Mutex<SharedState>>) {}
+
+fn start_sched_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        let mut rng = rand::thread_rng();
+        let mut raw_bytes: Vec<u8> = Vec::with_capacity(128);
+        
+        while running.load(Ordering::Relaxed) {
+            let enabled = state.lock().harvester_states.sched;
+            if enabled {
+                // Short-lived threads: spawn + wake-up latency both depend on scheduler state
+                for _ in 0..32 {
+                    let requested = Duration::from_micros(rng.gen_range(50..500));
+                    let spawned_at = Instant::now();
+                    let handle = thread::spawn(move || {
+                        let start = Instant::now();
+                        thread::sleep(requested);
+                        let overshoot = start.elapsed().saturating_sub(requested);
+                        (start.duration_since(spawned_at), overshoot)
+                    });
+                    if let Ok((spawn_latency, overshoot)) = handle.join() {
+                        raw_bytes.extend_from_slice(&(overshoot.as_nanos() as u16).to_le_bytes());
+                        raw_bytes.push(spawn_latency.as_nanos() as u8);
+                    }
+                }
+                
+                if raw_bytes.len() >= 64 {
+                    if admits_sample(&state, &raw_bytes) {
+                        let _ = tx.try_send(("SCHED".to_string(), raw_bytes.clone()));
+                    }
+                    raw_bytes.clear();
+                }
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+    });
+}
+
+#[cfg(target_arch = "x86_64")]
+fn cycle_counter() -> u64 {
+    unsafe { std::arch::x86_64::_rdtsc() }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn cycle_counter() -> u64 {
+    get_timestamp_nanos()
+}
+
+fn start_mem_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        let mut chain: Vec<usize> = Vec::new();
+        let mut idx = 0usize;
+        let mut raw_bytes: Vec<u8> = Vec::with_capacity(512);
+        
+        while running.load(Ordering::Relaxed) {
+            let enabled = state.lock().harvester_states.mem;
+            if enabled {
+                if chain.is_empty() {
+                    // Sattolo's algorithm: one random cycle through every slot, defeats prefetchers
+                    chain = (0..MEM_CHASE_ENTRIES).collect();
+                    let mut rng = rand::thread_rng();
+                    for i in (1..MEM_CHASE_ENTRIES).rev() {
+                        let j = rng.gen_range(0..i);
+                        chain.swap(i, j);
+                    }
+                    idx = 0;
+                }
+                
+                for _ in 0..256 {
+                    let start = cycle_counter();
+                    idx = std::hint::black_box(chain[idx]);
+                    let cycles = cycle_counter().wrapping_sub(start);
+                    raw_bytes.push(cycles as u8);
+                }
+                
+                if raw_bytes.len() >= 256 {
+                    if admits_sample(&state, &raw_bytes) {
+                        let _ = tx.try_send(("MEM".to_string(), raw_bytes.clone()));
+                    }
+                    raw_bytes.clear();
+                }
+            } else if !chain.is_empty() {
+                chain = Vec::new();  // Release the 32 MB while idle
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+    });
+}
+
+// Screen capture pulls in platform capture stacks (X11/Wayland/DXGI), so it is behind the `screen` feature
+#[cfg(feature = "screen")]
+fn start_screen_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        const REGION: u32 = 32;
+        const REGIONS_PER_FRAME: usize = 4;
+        
+        let mut rng = rand::thread_rng();
+        let mut previous: Option<xcap::image::RgbaImage> = None;
+        
+        while running.load(Ordering::Relaxed) {
+            let enabled = state.lock().harvester_states.screen;
+            if enabled {
+                let frame = xcap::Monitor::all().ok()
+                    .and_then(|monitors| monitors.into_iter().next())
+                    .and_then(|m| m.capture_image().ok());
+                
+                if let (Some(frame), Some(prev)) = (frame.as_ref(), previous.as_ref()) {
+                    if frame.dimensions() == prev.dimensions() && frame.width() > REGION && frame.height() > REGION {
+                        let mut payload = Vec::with_capacity(REGIONS_PER_FRAME * 32);
+                        
+                        for _ in 0..REGIONS_PER_FRAME {
+                            let x0 = rng.gen_range(0..frame.width() - REGION);
+                            let y0 = rng.gen_range(0..frame.height() - REGION);
+                            
+                            let mut deltas = Vec::with_capacity((REGION * REGION * 4) as usize);
+                            for y in y0..y0 + REGION {
+                                for x in x0..x0 + REGION {
+                                    let a = frame.get_pixel(x, y).0;
+                                    let b = prev.get_pixel(x, y).0;
+                                    deltas.extend(a.iter().zip(b.iter()).map(|(p, q)| p ^ q));
+                                }
+                            }
+                            
+                            // A static region contributes nothing; don't dress up a constant as entropy
+                            if deltas.iter().all(|&d| d == 0) { continue; }
+                            
+                            let mut hasher = Sha3_256::new();
+                            hasher.update(&deltas);
+                            hasher.update(&get_timestamp_nanos().to_le_bytes());
+                            payload.extend_from_slice(&hasher.finalize());
+                        }
+                        
+                        if !payload.is_empty() && admits_sample(&state, &payload) {
+                            let _ = tx.try_send(("SCREEN".to_string(), payload));
+                        }
+                    }
+                }
+                previous = frame;
+            } else {
+                previous = None;
+            }
+            thread::sleep(Duration::from_secs(2));
+        }
+    });
+}
+
+#[cfg(not(feature = "screen"))]
+fn start_screen_harvester(_tx: Sender<(String, Vec<u8>)>, _running: Arc<AtomicBool>, _state: Arc<Mutex<SharedState>>) {}
+
+fn unix_nanos_to_ntp(nanos: u64) -> u64 {
+    let secs = nanos / 1_000_000_000 + NTP_UNIX_OFFSET_SECS;
+    let frac = ((nanos % 1_000_000_000) << 32) / 1_000_000_000;
+    (secs << 32) | frac
+}
+
+fn ntp_to_unix_nanos(ntp: u64) -> i128 {
+    let secs = (ntp >> 32) as i128 - NTP_UNIX_OFFSET_SECS as i128;
+    let frac = (((ntp & 0xFFFF_FFFF) as i128) * 1_000_000_000) >> 32;
+    secs * 1_000_000_000 + frac
+}
+
+fn start_clock_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        use std::net::UdpSocket;
+        
+        let socket = match UdpSocket::bind("0.0.0.0:0") {
+            Ok(s) => s,
+            Err(_) => return,
+        };
+        let _ = socket.set_read_timeout(Some(Duration::from_secs(2)));
+        
+        let mut round = 0usize;
+        let mut last_query: Option<Instant> = None;
+        let mut last_offset: HashMap<String, i128> = HashMap::new();
+        let mut raw_bytes: Vec<u8> = Vec::with_capacity(128);
+        
+        while running.load(Ordering::Relaxed) {
+            let (enabled, servers) = {
+                let lock = state.lock();
+                (lock.harvester_states.clock, lock.ntp_servers.clone())
+            };
+            let due = last_query.map(|t| t.elapsed() >= Duration::from_secs(NTP_QUERY_INTERVAL_SECS)).unwrap_or(true);
+            
+            if enabled && due && !servers.is_empty() {
+                last_query = Some(Instant::now());
+                round = round.wrapping_add(1);
+                let server = &servers[round % servers.len()];
+                
+                // SNTPv4 client request; our T1 goes in the transmit field and is echoed as originate
+                let t1 = get_timestamp_nanos();
+                let t1_ntp = unix_nanos_to_ntp(t1);
+                let mut request = [0u8; 48];
+                request[0] = 0x23;  // LI=0, VN=4, Mode=3
+                request[40..48].copy_from_slice(&t1_ntp.to_be_bytes());
+                
+                if socket.send_to(&request, server.as_str()).is_ok() {
+                    let mut resp = [0u8; 48];
+                    if let Ok((48, _)) = socket.recv_from(&mut resp) {
+                        let t4 = get_timestamp_nanos() as i128;
+                        let read_ts = |off: usize| {
+                            let mut b = [0u8; 8];
+                            b.copy_from_slice(&resp[off..off + 8]);
+                            u64::from_be_bytes(b)
+                        };
+                        
+                        if read_ts(24) == t1_ntp {
+                            let t1 = t1 as i128;
+                            let t2 = ntp_to_unix_nanos(read_ts(32));
+                            let t3 = ntp_to_unix_nanos(read_ts(40));
+                            
+                            let offset = ((t2 - t1) + (t3 - t4)) / 2;
+                            let delay = (t4 - t1) - (t3 - t2);
+                            let asymmetry = (t2 - t1) - (t4 - t3);
+                            let prev = last_offset.insert(server.clone(), offset).unwrap_or(offset);
+                            
+                            // Only the sub-microsecond jitter components are unpredictable
+                            raw_bytes.extend_from_slice(&(offset as u16).to_le_bytes());
+                            raw_bytes.extend_from_slice(&((offset - prev) as u16).to_le_bytes());
+                            raw_bytes.extend_from_slice(&(delay as u16).to_le_bytes());
+                            raw_bytes.extend_from_slice(&(asymmetry as u16).to_le_bytes());
+                            raw_bytes.push(resp[39]);  // Server receive timestamp fraction LSB
+                            raw_bytes.push(resp[47]);  // Server transmit timestamp fraction LSB
+                        }
+                    }
+                }
+                
+                if raw_bytes.len() >= 64 {
+                    if admits_sample(&state, &raw_bytes) {
+                        let _ = tx.try_send(("CLOCK".to_string(), raw_bytes.clone()));
+                    }
+                    raw_bytes.clear();
+                }
+            }
+            thread::sleep(Duration::from_secs(1));
+        }
+    });
+}
+
+fn start_fs_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        use notify::{RecursiveMode, Watcher};
+        
+        // inotify / FSEvents / ReadDirectoryChangesW, whichever the platform provides
+        let (event_tx, event_rx) = bounded::<(Instant, notify::Event)>(4096);
+        let mut watcher = match notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                let _ = event_tx.try_send((Instant::now(), event));
+            }
+        }) {
+            Ok(w) => w,
+            Err(_) => return,
+        };
+        
+        let mut watched: Vec<String> = Vec::new();
+        let mut last_event = Instant::now();
+        let mut raw_bytes: Vec<u8> = Vec::with_capacity(128);
+        
+        while running.load(Ordering::Relaxed) {
+            let (enabled, paths) = {
+                let lock = state.lock();
+                (lock.harvester_states.fs, lock.fs_watch_paths.clone())
+            };
+            let wanted = if enabled { paths } else { Vec::new() };
+            
+            if wanted != watched {
+                for path in watched.iter().filter(|p| !wanted.contains(p)) {
+                    let _ = watcher.unwatch(std::path::Path::new(path));
+                }
+                for path in wanted.iter().filter(|p| !watched.contains(p)) {
+                    let _ = watcher.watch(std::path::Path::new(path), RecursiveMode::Recursive);
+                }
+                watched = wanted;
+            }
+            
+            match event_rx.recv_timeout(Duration::from_millis(500)) {
+                Ok((arrived, event)) => {
+                    if !enabled { continue; }
+                    
+                    let delta_nanos = arrived.duration_since(last_event).as_nanos() as u64;
+                    last_event = arrived;
+                    
+                    let mut hasher = Sha256::new();
+                    for path in &event.paths {
+                        hasher.update(path.to_string_lossy().as_bytes());
+                    }
+                    hasher.update(format!("{:?}", event.kind).as_bytes());
+                    let path_hash = hasher.finalize();
+                    
+                    raw_bytes.extend_from_slice(&(delta_nanos as u32).to_le_bytes());
+                    raw_bytes.extend_from_slice(&path_hash[..2]);
+                },
+                Err(_) => continue,
+            }
+            
+            if raw_bytes.len() >= 64 {
+                if admits_sample(&state, &raw_bytes) {
+                    let _ = tx.try_send(("FS".to_string(), raw_bytes.clone()));
+                }
+                raw_bytes.clear();
+            }
+        }
+    });
+}
+
+#[cfg(feature = "usb")]
+struct UsbHotplugRecorder {
+    events: Sender<(Instant, u16, u16)>,
+}
+
+#[cfg(feature = "usb")]
+impl<T: rusb::UsbContext> rusb::Hotplug<T> for UsbHotplugRecorder {
+    fn device_arrived(&mut self, device: rusb::Device<T>) {
+        if let Ok(desc) = device.device_descriptor() {
+            let _ = self.events.try_send((Instant::now(), desc.vendor_id(), desc.product_id()));
+        }
+    }
+    
+    fn device_left(&mut self, device: rusb::Device<T>) {
+        // Descriptors are gone once unplugged; bus/address still identify the slot
+        let slot = ((device.bus_number() as u16) << 8) | device.address() as u16;
+        let _ = self.events.try_send((Instant::now(), slot, 0));
+    }
+}
+
+// libusb is a system dependency, so USB enumeration is behind the `usb` feature
+#[cfg(feature = "usb")]
+fn start_usb_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        use rusb::{HotplugBuilder, UsbContext};
+        
+        let context = match rusb::Context::new() {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+        
+        let (event_tx, event_rx) = bounded::<(Instant, u16, u16)>(256);
+        let _registration = if rusb::has_hotplug() {
+            HotplugBuilder::new()
+                .enumerate(false)
+                .register(context.clone(), Box::new(UsbHotplugRecorder { events: event_tx }))
+                .ok()
+        } else {
+            None
+        };
+        
+        let mut last_event = Instant::now();
+        let mut last_enumeration: Option<Instant> = None;
+        let mut raw_bytes: Vec<u8> = Vec::with_capacity(128);
+        
+        while running.load(Ordering::Relaxed) {
+            // Drives hotplug callbacks; doubles as this loop's sleep
+            let _ = context.handle_events(Some(Duration::from_millis(500)));
+            
+            let enabled = state.lock().harvester_states.usb;
+            if !enabled {
+                while event_rx.try_recv().is_ok() {}
+                continue;
+            }
+            
+            while let Ok((arrived, vid, pid)) = event_rx.try_recv() {
+                let delta_nanos = arrived.duration_since(last_event).as_nanos() as u64;
+                last_event = arrived;
+                raw_bytes.extend_from_slice(&(delta_nanos as u32).to_le_bytes());
+                raw_bytes.push((vid ^ pid) as u8);
+            }
+            
+            // Periodic full re-enumeration: latency depends on bus/controller state
+            if last_enumeration.map(|t| t.elapsed() >= Duration::from_secs(5)).unwrap_or(true) {
+                last_enumeration = Some(Instant::now());
+                let start = Instant::now();
+                let mut hasher = Sha256::new();
+                
+                if let Ok(devices) = context.devices() {
+                    for device in devices.iter() {
+                        if let Ok(desc) = device.device_descriptor() {
+                            hasher.update(desc.vendor_id().to_le_bytes());
+                            hasher.update(desc.product_id().to_le_bytes());
+                            hasher.update([desc.class_code(), device.bus_number(), device.address()]);
+                        }
+                    }
+                    let latency = start.elapsed().as_nanos() as u64;
+                    let descriptor_hash = hasher.finalize();
+                    
+                    raw_bytes.extend_from_slice(&(latency as u32).to_le_bytes());
+                    raw_bytes.push(descriptor_hash[0]);
+                }
+            }
+            
+            if raw_bytes.len() >= 64 {
+                if admits_sample(&state, &raw_bytes) {
+                    let _ = tx.try_send(("USB".to_string(), raw_bytes.clone()));
+                }
+                raw_bytes.clear();
+            }
+        }
+    });
+}
+
+#[cfg(not(feature = "usb"))]
+fn start_usb_harvester(_tx: Sender<(String, Vec<u8>)>, _running: Arc<AtomicBool>, _state: Arc<Mutex<SharedState>>) {}
+
+// gilrs needs libudev on Linux, so controllers are behind the `gamepad` feature
+#[cfg(feature = "gamepad")]
+fn start_gamepad_harvester(tx: Sender<(String, Vec<u8>)>, running: Arc<AtomicBool>, state: Arc<Mutex<SharedState>>) {
+    thread::spawn(move || {
+        use gilrs::{Axis, EventType, GilrsBuilder};
+        
+        // Default filters include a deadzone that would hide idle stick drift
+        let mut gilrs = match GilrsBuilder::new().with_default_filters(false).build() {
+            Ok(g) => g,
+            Err(_) => return,
+        };
+        
+        let axes = [
+            Axis::LeftStickX, Axis::LeftStickY,
+            Axis::RightStickX, Axis::RightStickY,
+            Axis::LeftZ, Axis::RightZ,
+        ];
+        let mut last_event = Instant::now();
+        let mut raw_bytes: Vec<u8> = Vec::with_capacity(128);
+        
+        while running.load(Ordering::Relaxed) {
+            let enabled = state.lock().harvester_states.gamepad;
+            
+            // Always drain so the queue doesn't grow while disabled
+            while let Some(event) = gilrs.next_event() {
+                if !enabled { continue; }
+                let now = Instant::now();
+                let delta_nanos = now.duration_since(last_event).as_nanos() as u64;
+                last_event = now;
+                
+                match event.event {
+                    EventType::ButtonPressed(_, code) | EventType::ButtonReleased(_, code) => {
+                        raw_bytes.extend_from_slice(&(delta_nanos as u32).to_le_bytes());
+                        raw_bytes.push(code.into_u32() as u8);
+                    }
+                    EventType::ButtonChanged(_, value, _) | EventType::AxisChanged(_, value, _) => {
+                        raw_bytes.extend_from_slice(&(delta_nanos as u16).to_le_bytes());
+                        raw_bytes.push(value.to_bits() as u8);
+                    }
+                    _ => {}
+                }
+            }
+            
+            if enabled {
+                // Idle sticks/triggers still wander in their low bits
+                for (_, gamepad) in gilrs.gamepads() {
+                    for axis in axes {
+                        if let Some(data) = gamepad.axis_data(axis) {
+                            raw_bytes.push(data.value().to_bits() as u8);
+                        }
+                    }
+                }
+                
+                if raw_bytes.len() >= 64 {
+                    if admits_sample(&state, &raw_bytes) {
+                        let _ = tx.try_send(("GAMEPAD".to_string(), raw_bytes.clone()));
+                    }
+                    raw_bytes.clear();
+                }
+            }
+            
+            thread::sleep(Duration::from_millis(50));
+        }
+    });
+}
+
+#[cfg(not(feature = "gamepad"))]
+fn start_gamepad_harvester(_tx: Sender<(String, Vec<u8>)>, _running: Arc<AtomicBool>, _state: Arc<Mutex<SharedState>>) {}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// P2P SERVER (NEW)
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn start_p2p_server(
+    tx: Sender<(String, Vec<u8>)>,
+    state: Arc<Mutex<SharedState>>,
+    running: Arc<AtomicBool>
+) {
+    thread::spawn(move || {
+        use std::net::TcpListener;
+        use std::io::{Read, Write};
+        
+        let port = state.lock().p2p_config.listen_port;
+        let addr = format!("0.0.0.0:{}", port);
+        
+        let listener = match TcpListener::bind(&addr) {
             Ok(l) => {
                 let ts = chrono::Local::now().format("%H:%M:%S").to_string();
                 let mut lock = state.lock();
-                let msg = format!("[{}] P2P: Listening on port {}", ts, port);
+                let msg = format!("[{}] P2P: Listening on port {}", ts, port);
+                if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+                lock.logs.push_back(msg);
+                drop(lock);
+                l
+            },
+            Err(e) => {
+                eprintln!("P2P: Failed to bind to {}: {}", addr, e);
+                return;
+            }
+        };
+        
+        // Set non-blocking for graceful shutdown
+        listener.set_nonblocking(true).ok();
+        
+        while running.load(Ordering::Relaxed) {
+            match listener.accept() {
+                Ok((mut stream, addr)) => {
+                    let tx_clone = tx.clone();
+                    let state_clone = state.clone();
+                    
+                    thread::spawn(move || {
+                        let mut buffer = String::new();
+                        if stream.read_to_string(&mut buffer).is_ok() {
+                            // Parse HTTP request (simple POST body extraction)
+                            if let Some(body_start) = buffer.find("\r\n\r\n") {
+                                let body = &buffer[body_start + 4..];
+                                
+                                // Phone companions have their own authenticated path, independent of P2P mode
+                                if buffer.starts_with("POST /mobile_ingest") {
+                                    let response = match handle_mobile_ingest(body, &tx_clone, &state_clone) {
+                                        Ok(()) => "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nOK".to_string(),
+                                        Err(reason) => format!(
+                                            "HTTP/1.1 403 Forbidden\r\nContent-Length: {}\r\n\r\n{}",
+                                            reason.len(), reason
+                                        ),
+                                    };
+                                    let _ = stream.write_all(response.as_bytes());
+                                    return;
+                                }
+                                
+                                // Check if P2P is still active
+                                if !state_clone.lock().p2p_config.active {
+                                    return;
+                                }
+                                
+                                let peer_ip = addr.ip().to_string();
+                                let banned = state_clone.lock().p2p_config.peer_health
+                                    .get(&peer_ip).is_some_and(|p| p.banned);
+                                if banned {
+                                    let response = "HTTP/1.1 403 Forbidden\r\nContent-Length: 6\r\n\r\nBANNED";
+                                    let _ = stream.write_all(response.as_bytes());
+                                    return;
+                                }
+                                
+                                // Parse JSON payload
+                                if let Ok(json) = serde_json::from_str::<serde_json::Value>(body) {
+                                    if let Some(payload_hex) = json["payload_hex"].as_str() {
+                                        if let Ok(entropy_bytes) = hex::decode(payload_hex) {
+                                            // Health check; the mixer's stricter P2P cutoffs also count against the peer
+                                            let source = format!("P2P_{}", peer_ip);
+                                            if !passes_health_checks(&entropy_bytes) {
+                                                record_peer_result(&mut state_clone.lock(), &source, false);
+                                            } else {
+                                                // Add to processing queue
+                                                let _ = tx_clone.try_send((source, entropy_bytes));
+                                                
+                                                // Update P2P stats
+                                                let mut lock = state_clone.lock();
+                                                lock.p2p_config.received_count += 1;
+                                                
+                                                // HTTP response
+                                                let response = "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nOK";
+                                                let _ = stream.write_all(response.as_bytes());
+                                                return;
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        
+                        // Error response
+                        let response = "HTTP/1.1 400 Bad Request\r\nContent-Length: 5\r\n\r\nERROR";
+                        let _ = stream.write_all(response.as_bytes());
+                    });
+                },
+                Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    // No connection, sleep briefly
+                    thread::sleep(Duration::from_millis(100));
+                },
+                Err(_) => {
+                    // Other error, continue
+                    thread::sleep(Duration::from_millis(100));
+                }
+            }
+        }
+    });
+}
+
+/// Updates a peer's history for a submission from P2P source `source`; repeated failures ban
+/// the peer, so one passing sample is no longer enough to keep a hostile peer in the pool
+fn record_peer_result(state: &mut SharedState, source: &str, passed: bool) {
+    let Some(ip) = source.strip_prefix("P2P_") else { return };
+    let peer = state.p2p_config.peer_health.entry(ip.to_string()).or_default();
+    if passed {
+        peer.accepted += 1;
+        peer.consecutive_failures = 0;
+        return;
+    }
+    
+    peer.rejected += 1;
+    peer.consecutive_failures += 1;
+    if peer.consecutive_failures >= P2P_MAX_FAILURES && !peer.banned {
+        peer.banned = true;
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] P2P: Banned {} after {} health failures", ts, ip, P2P_MAX_FAILURES);
+        if state.logs.len() >= 20 { state.logs.pop_front(); }
+        state.logs.push_back(msg);
+        push_health_event(state, source, "PEER_BANNED", P2P_MAX_FAILURES as f64, "critical");
+    }
+}
+
+/// Body: {"device_id", "sensor", "counter", "payload_hex", "mac_hex"} where
+/// mac = HMAC-SHA256(device key, device_id || counter_le || payload)
+fn handle_mobile_ingest(
+    body: &str,
+    tx: &Sender<(String, Vec<u8>)>,
+    state: &Arc<Mutex<SharedState>>
+) -> Result<(), &'static str> {
+    let json: serde_json::Value = serde_json::from_str(body).map_err(|_| "BAD_JSON")?;
+    let device_id = json["device_id"].as_str().ok_or("NO_DEVICE")?;
+    let sensor = json["sensor"].as_str().unwrap_or("unknown");
+    let counter = json["counter"].as_u64().ok_or("NO_COUNTER")?;
+    let payload = json["payload_hex"].as_str().and_then(|h| hex::decode(h).ok()).ok_or("BAD_PAYLOAD")?;
+    let tag = json["mac_hex"].as_str().and_then(|h| hex::decode(h).ok()).ok_or("BAD_MAC")?;
+    
+    let mut lock = state.lock();
+    let device = lock.mobile_devices.get_mut(device_id).ok_or("UNKNOWN_DEVICE")?;
+    if device.quarantined {
+        device.rejected += 1;
+        return Err("QUARANTINED");
+    }
+    
+    let mut mac = HmacSha256::new_from_slice(&device.key).map_err(|_| "BAD_KEY")?;
+    mac.update(device_id.as_bytes());
+    mac.update(&counter.to_le_bytes());
+    mac.update(&payload);
+    if mac.verify_slice(&tag).is_err() || counter <= device.last_counter {
+        device.rejected += 1;
+        return Err("ATTESTATION_FAILED");
+    }
+    device.last_counter = counter;
+    
+    // Per-device gating: one misbehaving phone can't keep feeding the pool
+    if !passes_health_checks(&payload) {
+        device.rejected += 1;
+        device.consecutive_failures += 1;
+        if device.consecutive_failures >= MOBILE_MAX_FAILURES {
+            device.quarantined = true;
+            let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+            let msg = format!("[{}] MOBILE: {} quarantined after {} health failures", ts, device_id, MOBILE_MAX_FAILURES);
+            if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+            lock.logs.push_back(msg);
+        }
+        return Err("HEALTH_FAILED");
+    }
+    device.consecutive_failures = 0;
+    device.accepted += 1;
+    
+    if device.accepted == 1 {
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] MOBILE: First {} sample from {}", ts, sensor, device_id);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+    drop(lock);
+    
+    let _ = tx.try_send((mobile_source_name(device_id), payload));
+    Ok(())
+}
+
+fn mobile_source_name(device_id: &str) -> String {
+    let tag: String = device_id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    format!("MOBILE_{}", tag.trim_matches('_'))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// MIXER THREAD (WITH P2P SUPPORT)
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Why output must be refused, if it must: every recently active source is quarantined, or
+/// credited pool entropy is below the configured floor
+fn entropy_failure(state: &SharedState) -> Option<String> {
+    let mut active = state.source_health.values()
+        .filter(|h| h.last_seen.is_some_and(|t| t.elapsed() < Duration::from_secs(ACTIVE_SOURCE_SECS)))
+        .peekable();
+    if active.peek().is_some() && active.all(|h| h.state == HealthState::Disabled) {
+        return Some("all active sources quarantined".to_string());
+    }
+    if state.ledger.available_bits < state.entropy_floor_bits {
+        return Some(format!(
+            "pool entropy {:.0} bits below floor {:.0}", state.ledger.available_bits, state.entropy_floor_bits
+        ));
+    }
+    None
+}
+
+/// Python-registered sources live in their own PY_ namespace
+fn plugin_source_name(name: &str) -> String {
+    let name = name.trim().to_uppercase();
+    if name.starts_with("PY_") { name } else { format!("PY_{}", name) }
+}
+
+fn check_output_allowed(state: &SharedState) -> PyResult<()> {
+    match entropy_failure(state) {
+        Some(reason) => Err(EntropyFailureError::new_err(reason)),
+        None => Ok(()),
+    }
+}
+
+/// Re-evaluates failure mode, logging and raising health events on entry and exit
+fn update_failure_mode(state: &mut SharedState) {
+    let failure = entropy_failure(state);
+    if failure.is_some() == state.failure_mode.is_some() {
+        state.failure_mode = failure;
+        return;
+    }
+    
+    let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+    let msg = match &failure {
+        Some(reason) => format!("[{}] FAILURE MODE: {}; output and minting blocked", ts, reason),
+        None => format!("[{}] FAILURE MODE cleared; output resumed", ts),
+    };
+    if state.logs.len() >= 20 { state.logs.pop_front(); }
+    state.logs.push_back(msg);
+    let severity = if failure.is_some() { "critical" } else { "info" };
+    let available = state.ledger.available_bits;
+    push_health_event(state, "ALL", "FAILURE_MODE", available, severity);
+    state.failure_mode = failure;
+}
+
+/// Delivers queued health events to the Python callbacks. Takes the GIL with the state lock
+/// released, so a callback may call back into the engine.
+fn dispatch_health_events(state: &Arc<Mutex<SharedState>>) {
+    let events: Vec<HealthEvent> = {
+        let mut lock = state.lock();
+        if lock.health_events.is_empty() {
+            return;
+        }
+        lock.health_events.drain(..).collect()
+    };
+    
+    Python::with_gil(|py| {
+        let callbacks: Vec<Py<PyAny>> = state.lock().health_callbacks.iter()
+            .map(|callback| callback.clone_ref(py))
+            .collect();
+        for event in &events {
+            for callback in &callbacks {
+                let args = (event.source.as_str(), event.test, event.statistic, event.severity);
+                if let Err(e) = callback.call1(py, args) {
+                    let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+                    let msg = format!("[{}] HEALTH: callback error: {}", ts, e);
+                    let mut lock = state.lock();
+                    if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+                    lock.logs.push_back(msg);
+                }
+            }
+        }
+    });
+}
+
+/// Runs the compression estimate over each source's raw history every
+/// COMPRESSION_INTERVAL_SECS; too slow for the mixer's per-sample path
+fn start_estimator_thread(state: Arc<Mutex<SharedState>>, running: Arc<AtomicBool>) {
+    thread::spawn(move || {
+        let mut last_run = Instant::now();
+        
+        while running.load(Ordering::Relaxed) {
+            thread::sleep(Duration::from_secs(1));
+            if last_run.elapsed() < Duration::from_secs(COMPRESSION_INTERVAL_SECS) {
+                continue;
+            }
+            last_run = Instant::now();
+            
+            let histories: Vec<(String, Vec<u8>)> = state.lock().source_health.iter()
+                .filter(|(_, h)| h.history.len() >= COMPRESSION_MIN_BYTES)
+                .map(|(name, h)| (name.clone(), h.history.iter().copied().collect()))
+                .collect();
+            
+            for (source, history) in histories {
+                let estimate = compression_min_entropy(&history);
+                state.lock().source_metrics.entry(source).or_default().compression_min_entropy = Some(estimate);
+            }
+        }
+    });
+}
+
+fn start_mixer_thread(
+    rx: Receiver<(String, Vec<u8>)>,
+    state: Arc<Mutex<SharedState>>,
+    running: Arc<AtomicBool>
+) {
+    thread::spawn(move || {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_millis(500))
+            .build()
+            .unwrap_or_else(|_| reqwest::blocking::Client::new());
+        
+        let mut last_net_time = 0u64;
+        let mut rate_mark = (Instant::now(), 0.0f64);
+        let mut rate_alarm_raised = false;
+        
+        while running.load(Ordering::Relaxed) {
+            // Total credited entropy rate, checked once per window
+            if rate_mark.0.elapsed() >= Duration::from_secs(ENTROPY_RATE_WINDOW_SECS) {
+                let mut lock = state.lock();
+                let credited = lock.ledger.total_credited_bits;
+                let rate = (credited - rate_mark.1) / rate_mark.0.elapsed().as_secs_f64();
+                rate_mark = (Instant::now(), credited);
+                if let Some(min_rate) = lock.entropy_rate_alarm {
+                    let low = rate < min_rate;
+                    if low != rate_alarm_raised {
+                        rate_alarm_raised = low;
+                        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+                        let msg = if low {
+                            format!("[{}] HEALTH: entropy rate {:.1} bits/s below {:.1}", ts, rate, min_rate)
+                        } else {
+                            format!("[{}] HEALTH: entropy rate recovered ({:.1} bits/s)", ts, rate)
+                        };
+                        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+                        lock.logs.push_back(msg);
+                        push_health_event(&mut lock, "ALL", "ENTROPY_RATE", rate, if low { "warning" } else { "info" });
+                    }
+                }
+            }
+            dispatch_health_events(&state);
+            
+            let (source, mut data) = match rx.recv_timeout(Duration::from_secs(1)) {
+                Ok(d) => d,
+                Err(_) => continue,
+            };
+            
+            // Raw capture sees the harvester's bytes before debiasing and health gating
+            {
+                let mut lock = state.lock();
+                let result = lock.raw_captures.get_mut(&source).map(|capture| capture.write(&data));
+                let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+                let msg = match result {
+                    Some(Ok(true)) => lock.raw_captures.remove(&source).map(|c| {
+                        format!("[{}] CAPTURE: {} complete, {} samples in {}", ts, source, c.written, c.path)
+                    }),
+                    Some(Err(e)) => {
+                        lock.raw_captures.remove(&source);
+                        Some(format!("[{}] CAPTURE: {} aborted: {}", ts, source, e))
+                    }
+                    _ => None,
+                };
+                if let Some(msg) = msg {
+                    if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+                    lock.logs.push_back(msg);
+                }
+            }
+            
+            // Debias first so the entropy accounting reflects what actually reaches the pool
+            let debias = state.lock().debias_sources.get(&source).copied();
+            if let Some(stage) = debias {
+                data = stage.apply(&data);
+                if data.is_empty() { continue; }
+            }
+            
+            // Startup tests gate everything: until they pass, nothing from the source is used.
+            // After that the continuous tests drop individual failing samples, and runs of
+            // failures move the source to SUSPECT (no credit) and then DISABLED (quarantined).
+            // None means the sample is rejected.
+            let verdict = 'health: {
+                let mut lock = state.lock();
+                let params = lock.health_params.get(&source).copied()
+                    .unwrap_or_else(|| HealthParams::default_for(&source));
+                let health = lock.source_health.entry(source.clone()).or_default();
+                health.record_history(&data);
+                if health.begin_retest() {
+                    let attempt = health.retest_attempts;
+                    let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+                    let msg = format!("[{}] HEALTH: {} DISABLED -> STARTUP (retest {})", ts, source, attempt);
+                    if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+                    lock.logs.push_back(msg);
+                    push_health_event(&mut lock, &source, HealthState::Startup.name(), attempt as f64, "info");
+                }
+                let health = lock.source_health.entry(source.clone()).or_default();
+                if health.state == HealthState::Disabled {
+                    health.bytes_rejected += data.len() as u64;
+                    break 'health None;
+                }
+                if !health.startup_passed {
+                    let verdict = health.run_startup(&data, &params);
+                    let failures = health.startup_failures;
+                    let disabled = health.state == HealthState::Disabled;
+                    let retest = health.retest_attempts;
+                    let retest_in = health.next_retest.map_or(0, |at| at.saturating_duration_since(Instant::now()).as_secs());
+                    if let Some(passed) = verdict {
+                        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+                        let msg = if passed && retest > 0 {
+                            format!("[{}] HEALTH: {} re-admitted after retest {}", ts, source, retest)
+                        } else if passed {
+                            format!("[{}] HEALTH: {} passed startup tests ({} samples)", ts, source, STARTUP_TEST_SAMPLES)
+                        } else if disabled {
+                            format!("[{}] HEALTH: {} FAILED startup tests (attempt {}), DISABLED, retest in {}s", ts, source, failures, retest_in)
+                        } else {
+                            format!("[{}] HEALTH: {} FAILED startup tests (attempt {}), retesting", ts, source, failures)
+                        };
+                        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+                        lock.logs.push_back(msg);
+                        if !passed {
+                            let severity = if disabled { "critical" } else { "warning" };
+                            push_health_event(&mut lock, &source, "STARTUP", failures as f64, severity);
+                        }
+                        record_peer_result(&mut lock, &source, passed);
+                    }
+                    break 'health None;
+                }
+                let failure = health.run_continuous(&data, &params);
+                let transition = health.record_result(failure.is_none());
+                let consecutive_failures = health.consecutive_failures;
+                let retest_in = health.next_retest.map_or(0, |at| at.saturating_duration_since(Instant::now()).as_secs());
+                if let Some((test, statistic)) = failure {
+                    push_health_event(&mut lock, &source, test, statistic, "warning");
+                }
+                record_peer_result(&mut lock, &source, failure.is_none());
+                if let Some((old, new)) = transition {
+                    let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+                    let msg = format!(
+                        "[{}] HEALTH: {} {} -> {} ({} consecutive failures)",
+                        ts, source, old.name(), new.name(), consecutive_failures
+                    );
+                    if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+                    lock.logs.push_back(msg);
+                    if new == HealthState::Disabled {
+                        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+                        let msg = format!("[{}] HEALTH: {} retest in {}s", ts, source, retest_in);
+                        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+                        lock.logs.push_back(msg);
+                    }
+                    let severity = match new {
+                        HealthState::Disabled => "critical",
+                        HealthState::Suspect => "warning",
+                        _ => "info",
+                    };
+                    push_health_event(&mut lock, &source, new.name(), consecutive_failures as f64, severity);
+                }
+                if failure.is_some() {
+                    break 'health None;
+                }
+                let health = lock.source_health.entry(source.clone()).or_default();
+                
+                // Rolling chi-square: flags distribution skews RCT/APT are blind to; it alarms
+                // but doesn't drop samples. Only sources given a threshold are tested, since
+                // a uniform-bytes threshold would alarm constantly on raw physical noise.
+                let chi_square = params.chi_square_threshold
+                    .and_then(|threshold| health.update_chi_square(&data).map(|statistic| (statistic, threshold)));
+                if let Some((statistic, threshold)) = chi_square {
+                    let alarm = statistic > threshold;
+                    let metrics = lock.source_metrics.entry(source.clone()).or_default();
+                    let newly_raised = alarm && !metrics.chi_square_alarm;
+                    metrics.chi_square = statistic;
+                    metrics.chi_square_alarm = alarm;
+                    
+                    if newly_raised {
+                        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+                        let msg = format!(
+                            "[{}] HEALTH: {} chi-square alarm ({:.1} > {:.1})",
+                            ts, source, statistic, threshold
+                        );
+                        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+                        lock.logs.push_back(msg);
+                        push_health_event(&mut lock, &source, "CHI_SQUARE", statistic, "warning");
+                    }
+                }
+                
+                Some(lock.source_health.get(&source).is_some_and(|h| h.state == HealthState::Healthy))
+            };
+            
+            // Research mode keeps rejected samples, mixed in with zero credit and flagged
+            let tainted = verdict.is_none();
+            let credit_allowed = match verdict {
+                Some(credit) => credit,
+                None if !source.starts_with("P2P_") && !source.starts_with("MOBILE_") && state.lock().research_mode => false,
+                None => continue,
+            };
+            
+            // Non-IID estimates over the source's recent history: the per-sample byte
+            // histogram can't see structure across samples, so once a window has run the most
+            // conservative of these is what gets credited
+            let assessed_min = {
+                let mut lock = state.lock();
+                let window = lock.source_health.get_mut(&source).and_then(|h| h.take_estimator_window());
+                let metrics = lock.source_metrics.entry(source.clone()).or_default();
+                if let Some(window) = window {
+                    let (lag_min, lag_period) = lag_prediction_min_entropy(&window);
+                    metrics.lag_min_entropy = Some(lag_min);
+                    metrics.lag_period = lag_period;
+                    metrics.markov_min_entropy = Some(markov_min_entropy(&window));
+                    metrics.collision_min_entropy = Some(collision_min_entropy(&window));
+                    metrics.mcv_min_entropy = Some(mcv_min_entropy(&window));
+                }
+                metrics.assessed_min_entropy()
+            };
+            
+            // Measure RAW entropy
+            let raw_shannon = shannon_entropy(&data);
+            let raw_min = min_entropy(&data);
+            // Input entropy assessment for the conditioning chain's h_out claim; SUSPECT sources
+            // are mixed in with no claim
+            let assessed_bits = if credit_allowed {
+                assessed_min.unwrap_or_else(|| mcv_min_entropy(&data)) * data.len() as f64
+            } else {
+                0.0
+            };
+            let entropy_contribution_bits = (raw_min * data.len() as f64).min(data.len() as f64 * 8.0);
+            
+            let mut lock = state.lock();
+            
+            // Stage 1: whiten the sample (assessment above stays on the pre-whitening bytes)
+            let (whitened, whitened_bits) = lock.whitener.whiten(&source, &data, assessed_bits);
+            
+            // Stage 2: conditioning/accumulation, on the fast path, the slow pool, or both
+            let route = lock.pool_routes.get(&source).copied().unwrap_or(PoolRoute::Both);
+            if route.feeds_slow() && lock.slow_pool.add_raw_bytes(&source, &whitened, whitened_bits) {
+                let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+                let msg = format!(
+                    "[{}] SLOW RESEED #{} | {:.0} bits from {} sources",
+                    ts, lock.slow_pool.reseeds, lock.slow_pool.last_reseed_bits, lock.slow_pool.last_contributors
+                );
                 if lock.logs.len() >= 20 { lock.logs.pop_front(); }
                 lock.logs.push_back(msg);
-                drop(lock);
-                l
+            }
+            
+            // Feed to extraction pool
+            let extracted_opt = if route.feeds_fast() {
+                lock.fortuna.add_raw_bytes(&source, &whitened, whitened_bits)
+            } else {
+                None
+            };
+            
+            // Update source metrics
+            let metrics = lock.source_metrics.entry(source.clone()).or_default();
+            metrics.samples += 1;
+            metrics.raw_shannon = raw_shannon;
+            metrics.min_entropy = raw_min;
+            metrics.total_bits_contributed += entropy_contribution_bits;
+            metrics.tainted = tainted;
+            if tainted {
+                metrics.tainted_samples += 1;
+            }
+            metrics.avg_raw_entropy = if metrics.samples == 1 {
+                raw_shannon
+            } else {
+                metrics.avg_raw_entropy * 0.95 + raw_shannon * 0.05
+            };
+            
+            if credit_allowed {
+                lock.ledger.credit(&source, data.len(), assessed_min.unwrap_or(raw_min));
+            }
+            update_failure_mode(&mut lock);
+            
+            // Update history
+            if lock.history_raw_entropy.len() >= HISTORY_LEN {
+                lock.history_raw_entropy.pop_front();
+            }
+            lock.history_raw_entropy.push_back(raw_min);
+            
+            // Process extracted entropy
+            if let Some(extracted) = extracted_opt {
+                let extracted_shannon = shannon_entropy(&extracted);
+                
+                if lock.history_whitened_entropy.len() >= HISTORY_LEN {
+                    lock.history_whitened_entropy.pop_front();
+                }
+                lock.history_whitened_entropy.push_back(extracted_shannon);
+                
+                // Stage 3: mix into the main pool and refresh the extractor key from it
+                lock.accumulator.mix(&[source.as_bytes(), &extracted]);
+                lock.fortuna.conditioner.key = lock.accumulator.extractor_key();
+                
+                // Output generators: instantiate on the first reseed, then reseed periodically
+                refresh_output_consumers(&mut lock);
+                
+                // Anything leaving the process comes from a DRBG, never the extracted bytes themselves.
+                // Only generated when it will actually be sent, since output is debited from the ledger.
+                let now = get_timestamp();
+                let uplink_due = lock.net_mode && now > last_net_time;
+                let p2p_due = lock.p2p_config.active && !lock.p2p_config.peers.is_empty();
+                let output = if (uplink_due || p2p_due) && lock.failure_mode.is_none() {
+                    consumer_generate(&mut lock, "NETWORK", extracted.len()).ok()
+                } else {
+                    None
+                };
+                
+                // Update display pool
+                for &b in extracted.iter() {
+                    if lock.display_pool.len() >= POOL_SIZE {
+                        lock.display_pool.pop_front();
+                    }
+                    lock.display_pool.push_back(b);
+                }
+                
+                lock.total_bytes += extracted.len();
+                lock.sequence_id += 1;
+                lock.accumulator.ratchet();
+                
+                // Log extraction
+                let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+                let claim = match lock.fortuna.last_claim_bits {
+                    Some(bits) => format!("h_out:{:.1}", bits),
+                    None => "h_out:n/a".to_string(),
+                };
+                let msg = format!(
+                    "[{}] RESEED #{} | {} pools→{} bytes | {} ({:.1}:1) | {} | {} | Quality:{:.2} | Source:{}",
+                    ts, lock.fortuna.reseed_count, lock.fortuna.last_reseed_depth(), extracted.len(),
+                    lock.fortuna.conditioner.ratio_label(), lock.fortuna.last_effective_ratio,
+                    lock.fortuna.conditioner.extractor.name(),
+                    claim, extracted_shannon, source
+                );
+                if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+                lock.logs.push_back(msg);
+            
+                // AUTO-MINT (every 10 extractions if quality is good)
+                if lock.fortuna.reseed_count % 10 == 0
+                    && raw_min > AUTO_MINT_THRESHOLD
+                    && lock.pqc_active
+                    && lock.slow_pool.reseeds > 0
+                    && lock.failure_mode.is_none()
+                    && lock.vault.check_writable().is_ok()
+                {
+                    let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+                    let msg = format!(
+                        "[{}] AUTO-MINT: Quality={:.2}, Minting keypair...", 
+                        ts, raw_min
+                    );
+                    if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+                    lock.logs.push_back(msg);
+                    
+                    let kem = KemKind::Kyber768;
+                    let (kyber_pk, kyber_sk) = kem.keypair();
+                    
+                    let mut context_hasher = Sha3_256::new();
+                    context_hasher.update(lock.slow_pool.state);
+                    context_hasher.update(&kyber_pk);
+                    let context = context_hasher.finalize();
+                    lock.slow_pool.ratchet();
+                    
+                    let timestamp = get_timestamp();
+                    let mut bundle = serde_json::json!({
+                        "type": "COBRA_PQC_BUNDLE",
+                        "requester": "RUST_AUTO",
+                        "timestamp": timestamp,
+                        "raw_min_entropy": raw_min,
+                        "accumulated_true_bits": lock.ledger.available_bits,
+                        "conditioning": lock.fortuna.conditioner.extractor.name(),
+                        "sp800_90b_h_out_bits": lock.fortuna.last_claim_bits,
+                        "slow_pool_reseeds": lock.slow_pool.reseeds,
+                        "kem": kem.name(),
+                        "kyber_pk": hex::encode(&kyber_pk),
+                        "kyber_sk": hex::encode(&kyber_sk),
+                        "pool_commitment": hex::encode(context),
+                    });
+                    
+                    if sign_vault_bundle(&lock, &mut bundle).is_ok() {
+                        lock.ledger.debit(LEDGER_MINT_DEBIT_BITS);
+                        
+                        let filename = format!("keys/key_{}_{}.json", timestamp, hex::encode(&kyber_pk[0..4]));
+                        let _ = save_vault_bundle(&mut lock, &filename, bundle, &["kyber_sk"]);
+                    }
+                }
+                
+                // Network uplink
+                if let (true, Some(output)) = (uplink_due, output.as_ref()) {
+                    last_net_time = now;
+                    
+                    let target = lock.uplink_url.clone();
+                    let seq = lock.sequence_id;
+                    let source_clone = source.clone();
+                    let c = client.clone();
+                    
+                    let payload_hex = hex::encode(output);
+                    let payload_size = output.len();
+                    
+                    let digest = {
+                        let mut hasher = Sha3_256::new();
+                        hasher.update(&data);
+                        hex::encode(hasher.finalize())
+                    };
+                
+                    let ts_epoch = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs_f64();
+                    
+                    let raw_min_copy = raw_min;
+                    let raw_shannon_copy = raw_shannon;
+                    
+                    thread::spawn(move || {
+                        let _ = c.post(&target)
+                            .json(&serde_json::json!({
+                                "node": "chaos_magnet",
+                                "seq": seq,
+                                "timestamp": get_timestamp(),
+                                "ts_epoch": ts_epoch,
+                                "entropy_estimate_raw_shannon": raw_shannon_copy,
+                                "entropy_estimate_raw_min": raw_min_copy,
+                                "health": "OK",
+                                "source": source_clone,
+                                "metrics": {"size": payload_size},
+                                "payload_hex": payload_hex,
+                                "digest": digest
+                            }))
+                            .send();
+                    });
+                }
+                
+                // P2P distribution (send to all peers)
+                if let (true, Some(output)) = (p2p_due, output.as_ref()) {
+                    let peers = lock.p2p_config.peers.clone();
+                    let payload_hex = hex::encode(output);
+                    let seq = lock.sequence_id;
+                    let c = client.clone();
+                    
+                    thread::spawn(move || {
+                        for peer in peers {
+                            let url = format!("http://{}/ingest", peer);
+                            let _ = c.post(&url)
+                                .json(&serde_json::json!({
+                                    "node": "chaos_magnet_p2p",
+                                    "seq": seq,
+                                    "timestamp": get_timestamp(),
+                                    "payload_hex": payload_hex,
+                                }))
+                                .send();
+                        }
+                    });
+                }
+            }
+        }
+    });
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// PYTHON CLASS
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[pymethods]
+impl ChaosEngine {
+    #[new]
+    #[pyo3(signature = (extraction_input_bytes=EXTRACTION_POOL_SIZE, extraction_output_bytes=EXTRACTION_OUTPUT_SIZE, seed_file=None, signer="falcon512", extractor="sha256"))]
+    fn new(extraction_input_bytes: usize, extraction_output_bytes: usize, seed_file: Option<String>, signer: &str, extractor: &str) -> PyResult<Self> {
+        let extractor = Extractor::from_name(extractor)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("Unknown extractor: {}", extractor)))?;
+        Conditioner::validate_params(extractor, extraction_input_bytes, extraction_output_bytes)
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        let falcon_kind = FalconKind::from_name(signer)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("Unknown signer: {} (falcon512 or falcon1024)", signer)))?;
+        let mut conditioner = Conditioner::new(extraction_input_bytes, extraction_output_bytes);
+        conditioner.extractor = extractor;
+        
+        let (tx, rx) = bounded(1000);
+        let _ = fs::create_dir_all("keys");
+        
+        let (pk, sk) = falcon_kind.keypair();
+        let pqc_active = true;
+        
+        let mut display_pool = VecDeque::with_capacity(POOL_SIZE);
+        display_pool.extend(vec![0u8; POOL_SIZE]);
+        
+        let state = Arc::new(Mutex::new(SharedState {
+            whitener: Whitener::new(),
+            fortuna: FortunaAccumulator::new(conditioner),
+            accumulator: Accumulator::new(),
+            slow_pool: SlowPool::new(),
+            pool_routes: HashMap::new(),
+            display_pool,
+            history_raw_entropy: VecDeque::from(vec![0.0; HISTORY_LEN]),
+            history_whitened_entropy: VecDeque::from(vec![0.0; HISTORY_LEN]),
+            source_metrics: HashMap::new(),
+            source_health: HashMap::new(),
+            health_params: HashMap::new(),
+            research_mode: false,
+            ledger: EntropyLedger::new(),
+            logs: VecDeque::from(vec!["ENGINE: Rust Core v3.3 (P2P Enabled)".to_string()]),
+            total_bytes: 0,
+            net_mode: true,
+            uplink_url: "http://192.168.1.19:8000/ingest".to_string(),
+            sequence_id: 0,
+            falcon_kind,
+            falcon_pk: pk,
+            falcon_sk: sk,
+            pqc_active,
+            harvester_states: HarvesterStates::default(),
+            p2p_config: P2PConfig::default(),
+            hwrng_path: "/dev/hwrng".to_string(),
+            ping_targets: vec!["1.1.1.1:443".to_string(), "8.8.8.8:443".to_string(), "9.9.9.9:443".to_string()],
+            dns_resolvers: vec!["1.1.1.1:53".to_string(), "8.8.8.8:53".to_string()],
+            dns_hostnames: ["example.com", "wikipedia.org", "debian.org", "rust-lang.org", "python.org"]
+                .iter().map(|h| h.to_string()).collect(),
+            sdr_frequency_hz: 100_000_000,
+            serial_device: None,
+            gpio_pin: None,
+            i2c_bus: "/dev/i2c-1".to_string(),
+            video_devices: Vec::new(),
+            video_threads: HashSet::new(),
+            audio_devices: Vec::new(),
+            audio_threads: HashSet::new(),
+            ntp_servers: vec!["time.cloudflare.com:123".to_string(), "time.google.com:123".to_string(), "pool.ntp.org:123".to_string()],
+            fs_watch_paths: Vec::new(),
+            audio_mode: AudioMode::Normal,
+            mobile_devices: HashMap::new(),
+            video_roi: None,
+            video_dark_mode: false,
+            custom_sources: HashSet::new(),
+            debias_sources: HashMap::new(),
+            output_consumers: HashMap::new(),
+            reseed_policy: ReseedPolicy::default(),
+            seed_file: seed_file.clone(),
+            streams_opened: 0,
+            health_callbacks: Vec::new(),
+            health_events: VecDeque::new(),
+            entropy_rate_alarm: None,
+            entropy_floor_bits: 0.0,
+            raw_captures: HashMap::new(),
+            failure_mode: None,
+            kernel_feed: None,
+            egd_server: None,
+            fifo_outputs: HashMap::new(),
+            socket_service: None,
+            file_sink: None,
+            uuid7_last: (0, 0),
+            entropy_tokens_issued: 0,
+            entropy_blocks_exported: 0,
+            blocks_imported: HashMap::new(),
+            beacon: None,
+            vault: Vault::default(),
+            identity_path: None,
+        }));
+        
+        {
+            let mut lock = state.lock();
+            let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+            lock.logs.push_back(format!("[{}] IDENTITY: {} Session Key Generated", ts, match falcon_kind {
+                FalconKind::Falcon512 => "Falcon-512",
+                FalconKind::Falcon1024 => "Falcon-1024",
+            }));
+            let ratio = lock.fortuna.conditioner.ratio_label();
+            lock.logs.push_back(format!("[{}] EXTRACTION: {} byte compression", ts, ratio));
+            lock.logs.push_back(format!("[{}] ACCUMULATOR: Fortuna, {} pools", ts, FORTUNA_POOLS));
+            
+            // Before the mixer starts, so the seed lands ahead of any harvester data
+            if let Some(path) = seed_file.as_deref() {
+                restore_seed_file(&mut lock, path);
+            }
+        }
+
+        let running = Arc::new(AtomicBool::new(true));
+        
+        start_mixer_thread(rx, state.clone(), running.clone());
+        start_estimator_thread(state.clone(), running.clone());
+        start_kernel_feed_thread(state.clone(), running.clone());
+        start_p2p_server(tx.clone(), state.clone(), running.clone());
+        start_trng_harvester(tx.clone(), running.clone(), state.clone());
+        ensure_audio_harvester(&tx, &running, &state, "");
+        start_system_harvester(tx.clone(), running.clone(), state.clone());
+        start_mouse_harvester(tx.clone(), running.clone(), state.clone());
+        ensure_video_harvester(&tx, &running, &state, 0);
+        start_net_harvester(tx.clone(), running.clone(), state.clone());
+        start_gpu_harvester(tx.clone(), running.clone(), state.clone());
+        start_thermal_harvester(tx.clone(), running.clone(), state.clone());
+        start_bt_harvester(tx.clone(), running.clone(), state.clone());
+        start_wifi_harvester(tx.clone(), running.clone(), state.clone());
+        start_hwrng_harvester(tx.clone(), running.clone(), state.clone());
+        start_cpu_rng_harvester(tx.clone(), running.clone(), state.clone());
+        start_ping_harvester(tx.clone(), running.clone(), state.clone());
+        start_dns_harvester(tx.clone(), running.clone(), state.clone());
+        start_irq_harvester(tx.clone(), running.clone(), state.clone());
+        start_power_harvester(tx.clone(), running.clone(), state.clone());
+        start_imu_harvester(tx.clone(), running.clone(), state.clone());
+        start_sdr_harvester(tx.clone(), running.clone(), state.clone());
+        start_serial_harvester(tx.clone(), running.clone(), state.clone());
+        start_gpio_harvester(tx.clone(), running.clone(), state.clone());
+        start_token_harvester(tx.clone(), running.clone(), state.clone());
+        start_env_harvester(tx.clone(), running.clone(), state.clone());
+        start_sched_harvester(tx.clone(), running.clone(), state.clone());
+        start_mem_harvester(tx.clone(), running.clone(), state.clone());
+        start_screen_harvester(tx.clone(), running.clone(), state.clone());
+        start_clock_harvester(tx.clone(), running.clone(), state.clone());
+        start_fs_harvester(tx.clone(), running.clone(), state.clone());
+        start_usb_harvester(tx.clone(), running.clone(), state.clone());
+        start_gamepad_harvester(tx.clone(), running.clone(), state.clone());
+
+        Ok(ChaosEngine { state, running, tx_entropy: tx })
+    }
+
+    fn toggle_harvester(&self, name: String, active: bool) {
+        let mut lock = self.state.lock();
+        match name.to_uppercase().as_str() {
+            "TRNG" | "HARDWARE/TRNG" => lock.harvester_states.trng = active,
+            "AUDIO" | "AUDIO (MIC)" => lock.harvester_states.audio = active,
+            "SYS" | "SYSTEM" | "SYSTEM/CPU" => lock.harvester_states.system = active,
+            "MOUSE" | "HID (MOUSE)" => lock.harvester_states.mouse = active,
+            "VIDEO" | "VIDEO (CAM)" => lock.harvester_states.video = active,
+            "NET" | "NET_JITTER" | "NETWORK (JITTER)" => lock.harvester_states.net = active,
+            "GPU" | "GPU (TELEMETRY)" => lock.harvester_states.gpu = active,
+            "THERMAL" | "THERMAL/FANS" => lock.harvester_states.thermal = active,
+            "BT" | "BLUETOOTH" | "BLUETOOTH (BLE)" => lock.harvester_states.bt = active,
+            "WIFI" | "WI-FI" | "WI-FI (RSSI)" => lock.harvester_states.wifi = active,
+            "HWRNG" | "HARDWARE/HWRNG" => lock.harvester_states.hwrng = active,
+            "CPU_RNG" | "RDSEED" | "CPU (RDSEED)" => lock.harvester_states.cpu_rng = active,
+            "PING" | "NETWORK (PING)" => lock.harvester_states.ping = active,
+            "DNS" | "NETWORK (DNS)" => lock.harvester_states.dns = active,
+            "IRQ" | "INTERRUPTS" | "KERNEL (IRQ)" => lock.harvester_states.irq = active,
+            "POWER" | "BATTERY" | "BATTERY/POWER" => lock.harvester_states.power = active,
+            "IMU" | "ACCEL/GYRO" | "MOTION (IMU)" => lock.harvester_states.imu = active,
+            "SDR" | "RTL-SDR" | "RADIO (SDR)" => lock.harvester_states.sdr = active,
+            "SERIAL" | "SERIAL (DIY TRNG)" => lock.harvester_states.serial = active,
+            "GPIO" | "GPIO (AVALANCHE)" => lock.harvester_states.gpio = active,
+            "TOKEN" | "SECURITY KEY" | "SECURITY KEY (TOKEN)" => lock.harvester_states.token = active,
+            "ENV" | "I2C" | "ENVIRONMENT (I2C)" => lock.harvester_states.env = active,
+            "SCHED" | "SCHEDULER" | "SCHEDULER JITTER" => lock.harvester_states.sched = active,
+            "MEM" | "MEMORY" | "MEMORY TIMING" => lock.harvester_states.mem = active,
+            "SCREEN" | "SCREEN (CAPTURE)" => lock.harvester_states.screen = active,
+            "CLOCK" | "NTP" | "CLOCK DRIFT (NTP)" => lock.harvester_states.clock = active,
+            "FS" | "FILESYSTEM" | "FILESYSTEM CHURN" => lock.harvester_states.fs = active,
+            "USB" | "USB (HOT-PLUG)" => lock.harvester_states.usb = active,
+            "GAMEPAD" | "JOYSTICK" | "GAMEPAD (HID)" => lock.harvester_states.gamepad = active,
+            "MAINS" | "MAINS HUM" | "MAINS HUM (AUDIO)" => lock.harvester_states.mains = active,
+            _ => {}
+        }
+        
+        let status = if active { "Active" } else { "Inactive" };
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] Toggle: {} -> {}", ts, name, status);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    fn toggle_uplink(&self, active: bool) {
+        let mut lock = self.state.lock();
+        lock.net_mode = active;
+        
+        let status = if active { "ENABLED" } else { "PAUSED" };
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] Network Uplink -> {}", ts, status);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    fn toggle_p2p(&self, active: bool) {
+        let mut lock = self.state.lock();
+        lock.p2p_config.active = active;
+        
+        let status = if active { "ENABLED" } else { "PAUSED" };
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] P2P Mode -> {}", ts, status);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    fn set_p2p_port(&self, port: u16) {
+        let mut lock = self.state.lock();
+        lock.p2p_config.listen_port = port;
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] P2P: Listen port set to {}", ts, port);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    fn add_peer(&self, peer_addr: String) {
+        let mut lock = self.state.lock();
+        if !lock.p2p_config.peers.contains(&peer_addr) {
+            lock.p2p_config.peers.push(peer_addr.clone());
+            
+            let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+            let msg = format!("[{}] P2P: Added peer {}", ts, peer_addr);
+            if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+            lock.logs.push_back(msg);
+        }
+    }
+
+    /// Lifts a health ban on a sending peer (by IP) and clears its failure count
+    fn unban_peer(&self, ip: String) -> bool {
+        let mut lock = self.state.lock();
+        let Some(peer) = lock.p2p_config.peer_health.get_mut(&ip) else {
+            return false;
+        };
+        peer.banned = false;
+        peer.consecutive_failures = 0;
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] P2P: Unbanned {}", ts, ip);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        true
+    }
+
+    fn set_hwrng_device(&self, path: String) {
+        let mut lock = self.state.lock();
+        lock.hwrng_path = path.clone();
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] HWRNG: Device set to {}", ts, path);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    fn set_ping_targets(&self, targets: Vec<String>) {
+        let mut lock = self.state.lock();
+        lock.ping_targets = targets;
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] PING: {} target(s) configured", ts, lock.ping_targets.len());
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    fn set_dns_config(&self, resolvers: Vec<String>, hostnames: Vec<String>) {
+        let mut lock = self.state.lock();
+        lock.dns_resolvers = resolvers;
+        lock.dns_hostnames = hostnames;
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] DNS: {} resolver(s), {} hostname(s)", ts, lock.dns_resolvers.len(), lock.dns_hostnames.len());
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    fn set_sdr_frequency(&self, frequency_hz: u32) {
+        let mut lock = self.state.lock();
+        lock.sdr_frequency_hz = frequency_hz;
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] SDR: Tuned to {:.3} MHz", ts, frequency_hz as f64 / 1e6);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    #[pyo3(signature = (path, baud=115200))]
+    fn set_serial_device(&self, path: String, baud: u32) {
+        let mut lock = self.state.lock();
+        lock.serial_device = Some((path.clone(), baud));
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] SERIAL: Device set to {} @ {} baud", ts, path, baud);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    fn set_gpio_pin(&self, pin: u32) {
+        let mut lock = self.state.lock();
+        lock.gpio_pin = Some(pin);
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] GPIO: Sampling pin {}", ts, pin);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    fn set_i2c_bus(&self, bus: String) {
+        let mut lock = self.state.lock();
+        lock.i2c_bus = bus.clone();
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] ENV: I2C bus set to {}", ts, bus);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    /// Replace the harvested camera set with a single device
+    fn set_video_device(&self, index: u32) {
+        self.state.lock().video_devices.retain(|&i| i == index);
+        ensure_video_harvester(&self.tx_entropy, &self.running, &self.state, index);
+        
+        let mut lock = self.state.lock();
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] VIDEO: Using camera {} (VIDEO_{})", ts, index, index);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    /// Harvest an additional camera alongside the current ones
+    fn add_video_device(&self, index: u32) {
+        ensure_video_harvester(&self.tx_entropy, &self.running, &self.state, index);
+        
+        let mut lock = self.state.lock();
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] VIDEO: Added camera {} (VIDEO_{})", ts, index, index);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    fn remove_video_device(&self, index: u32) {
+        let mut lock = self.state.lock();
+        lock.video_devices.retain(|&i| i != index);
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] VIDEO: Removed camera {}", ts, index);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    /// Optional harvesters compiled into this build (Cargo features; the default build has none
+    /// and their start functions are no-ops)
+    fn compiled_features(&self) -> Vec<&'static str> {
+        [
+            ("bluetooth", cfg!(feature = "bluetooth")),
+            ("sdr", cfg!(feature = "sdr")),
+            ("token", cfg!(feature = "token")),
+            ("screen", cfg!(feature = "screen")),
+            ("usb", cfg!(feature = "usb")),
+            ("gamepad", cfg!(feature = "gamepad")),
+        ]
+        .into_iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| name)
+        .collect()
+    }
+
+    fn list_video_devices(&self) -> PyResult<String> {
+        use nokhwa::utils::ApiBackend;
+        
+        let cameras = nokhwa::query(ApiBackend::Auto)
+            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+        let active = self.state.lock().video_devices.clone();
+        
+        let devices: Vec<serde_json::Value> = cameras.iter()
+            .map(|info| {
+                let index = info.index().as_index().ok();
+                serde_json::json!({
+                    "index": index,
+                    "name": info.human_name(),
+                    "description": info.description(),
+                    "active": index.map(|i| active.contains(&i)).unwrap_or(false),
+                })
+            })
+            .collect();
+        
+        Ok(serde_json::Value::Array(devices).to_string())
+    }
+
+    #[pyo3(signature = (roi=None))]
+    fn set_video_roi(&self, roi: Option<(u32, u32, u32, u32)>) {
+        let mut lock = self.state.lock();
+        lock.video_roi = roi;
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = match roi {
+            Some((x, y, w, h)) => format!("[{}] VIDEO: ROI set to {}x{} at ({}, {})", ts, w, h, x, y),
+            None => format!("[{}] VIDEO: ROI cleared (full frame)", ts),
+        };
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    fn set_video_dark_mode(&self, enabled: bool) {
+        let mut lock = self.state.lock();
+        lock.video_dark_mode = enabled;
+        
+        let status = if enabled { "ENABLED" } else { "DISABLED" };
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] VIDEO: Dark-frame mode -> {}", ts, status);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    fn set_extractor(&self, name: String) -> PyResult<()> {
+        let extractor = Extractor::from_name(&name)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("Unknown extractor: {}", name)))?;
+        
+        let mut lock = self.state.lock();
+        let conditioner = &lock.fortuna.conditioner;
+        Conditioner::validate_params(extractor, conditioner.input_bytes, conditioner.output_bytes)
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        lock.fortuna.conditioner.extractor = extractor;
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] EXTRACTION: Conditioner -> {}", ts, extractor.name());
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(())
+    }
+
+    /// Adaptive extraction sizes each source's raw input by its measured min-entropy
+    /// (2 x output up to 4 x the configured input) instead of a fixed byte count
+    fn set_adaptive_extraction(&self, enabled: bool) {
+        let mut lock = self.state.lock();
+        lock.fortuna.conditioner.adaptive = enabled;
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] EXTRACTION: Ratio -> {}", ts, lock.fortuna.conditioner.ratio_label());
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    /// Raw bytes per extraction and bytes out; SHA-256/SHA3-256/BLAKE3/HMAC cap at 32, SHA-512 at 64
+    fn set_extraction_params(&self, input_bytes: usize, output_bytes: usize) -> PyResult<()> {
+        let mut lock = self.state.lock();
+        Conditioner::validate_params(lock.fortuna.conditioner.extractor, input_bytes, output_bytes)
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        lock.fortuna.conditioner.input_bytes = input_bytes;
+        lock.fortuna.conditioner.output_bytes = output_bytes;
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] EXTRACTION: Ratio -> {}", ts, lock.fortuna.conditioner.ratio_label());
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(())
+    }
+
+    /// mode: "von_neumann", "xor_fold" (with `rounds`), or "none" to remove
+    #[pyo3(signature = (source, mode, rounds=1))]
+    fn set_source_debias(&self, source: String, mode: String, rounds: u8) -> PyResult<()> {
+        let stage = match mode.to_lowercase().replace('-', "_").as_str() {
+            "von_neumann" | "vn" => Some(Debias::VonNeumann),
+            "xor_fold" | "xor" => Some(Debias::XorFold(rounds.clamp(1, 4))),
+            "none" | "" => None,
+            _ => return Err(pyo3::exceptions::PyValueError::new_err(format!("Unknown debias mode: {}", mode))),
+        };
+        
+        let source = source.to_uppercase();
+        let mut lock = self.state.lock();
+        match stage {
+            Some(s) => { lock.debias_sources.insert(source.clone(), s); }
+            None => { lock.debias_sources.remove(&source); }
+        }
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] DEBIAS: {} -> {}", ts, source, mode.to_uppercase());
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(())
+    }
+
+    /// generator: "ctr" (AES-256 CTR-DRBG) or "hmac" (SHA-256 HMAC-DRBG); re-instantiates the consumer
+    fn set_consumer_generator(&self, consumer: String, generator: String) -> PyResult<()> {
+        let kind = DrbgKind::from_name(&generator)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("Unknown generator: {}", generator)))?;
+        
+        let mut lock = self.state.lock();
+        let mut entry = OutputConsumer::new(kind);
+        if let Some(old) = lock.output_consumers.remove(&consumer) {
+            entry.prediction_resistance = old.prediction_resistance;
+            entry.quota = old.quota;
+        }
+        lock.output_consumers.insert(consumer.clone(), entry);
+        refresh_output_consumers(&mut lock);
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] DRBG: {} -> {}", ts, consumer, generator.to_uppercase());
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(())
+    }
+
+    /// Output limits for `consumer`: bytes per second, and bytes per pool reseed (refilled each
+    /// time fresh entropy reseeds the pool). Requests over either limit fail. None lifts a limit.
+    #[pyo3(signature = (consumer, bytes_per_sec=None, bytes_per_reseed=None))]
+    fn set_consumer_quota(&self, consumer: String, bytes_per_sec: Option<u64>, bytes_per_reseed: Option<u64>) -> PyResult<()> {
+        if bytes_per_sec == Some(0) || bytes_per_reseed == Some(0) {
+            return Err(pyo3::exceptions::PyValueError::new_err("quotas must be positive (None for unlimited)"));
+        }
+        let mut lock = self.state.lock();
+        let quota = &mut lock.output_consumers.entry(consumer.clone())
+            .or_insert_with(|| OutputConsumer::new(DrbgKind::Ctr))
+            .quota;
+        quota.bytes_per_sec = bytes_per_sec;
+        quota.bytes_per_reseed = bytes_per_reseed;
+        
+        let limit = |l: Option<u64>| l.map_or("unlimited".to_string(), |l| l.to_string());
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] DRBG: {} quota {} B/s, {} B/reseed", ts, consumer, limit(bytes_per_sec), limit(bytes_per_reseed));
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(())
+    }
+
+    /// With prediction resistance on, the consumer's generator reseeds from the pool before every request
+    fn set_prediction_resistance(&self, consumer: String, enabled: bool) {
+        let mut lock = self.state.lock();
+        lock.output_consumers.entry(consumer.clone())
+            .or_insert_with(|| OutputConsumer::new(DrbgKind::Ctr))
+            .prediction_resistance = enabled;
+        
+        let status = if enabled { "ON" } else { "OFF" };
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] DRBG: {} prediction resistance -> {}", ts, consumer, status);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    /// One-off prediction-resistance request: reseed the consumer's generator from the pool now
+    fn reseed_consumer(&self, consumer: String) -> PyResult<()> {
+        let mut lock = self.state.lock();
+        if force_reseed_consumers(&mut lock, Some(&consumer), "requested") == 0 {
+            return Err(pyo3::exceptions::PyRuntimeError::new_err(format!("No seeded generator for {}", consumer)));
+        }
+        Ok(())
+    }
+
+    /// Reseeds every instantiated output generator from the pool now; returns how many
+    fn force_reseed(&self) -> usize {
+        let mut lock = self.state.lock();
+        let count = force_reseed_consumers(&mut lock, None, "forced");
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] DRBG: Forced reseed of {} generators", ts, count);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        count
+    }
+
+    /// Reseed triggers for output generators; None disables a trigger. Any enabled one fires a reseed.
+    #[pyo3(signature = (max_age_secs=Some(DRBG_RESEED_SECS), max_output_bytes=None, min_credit_bits=None))]
+    fn set_reseed_policy(&self, max_age_secs: Option<u64>, max_output_bytes: Option<u64>, min_credit_bits: Option<f64>) -> PyResult<()> {
+        if max_age_secs == Some(0) || max_output_bytes == Some(0) || min_credit_bits.is_some_and(|b| b <= 0.0) {
+            return Err(pyo3::exceptions::PyValueError::new_err("Reseed triggers must be positive (use None to disable)"));
+        }
+        
+        let mut lock = self.state.lock();
+        lock.reseed_policy = ReseedPolicy { max_age_secs, max_output_bytes, min_credit_bits };
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!(
+            "[{}] DRBG: Reseed policy -> age {:?} s, output {:?} B, credits {:?} bits",
+            ts, max_age_secs, max_output_bytes, min_credit_bits
+        );
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(())
+    }
+
+    /// Assessed entropy credit for `source` in bits per byte (0..=8); still capped at half its measured min-entropy
+    fn set_entropy_credit(&self, source: String, bits_per_byte: f64) -> PyResult<()> {
+        if !(0.0..=8.0).contains(&bits_per_byte) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("bits_per_byte must be 0..=8, got {}", bits_per_byte)));
+        }
+        let source = source.to_uppercase();
+        self.state.lock().ledger.credit_rates.insert(source, bits_per_byte);
+        Ok(())
+    }
+
+    /// route: "fast" (output DRBGs), "slow" (key minting) or "both" (default)
+    fn set_source_route(&self, source: String, route: String) -> PyResult<()> {
+        let parsed = PoolRoute::from_name(&route)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("Unknown route: {}", route)))?;
+        
+        let source = source.to_uppercase();
+        let mut lock = self.state.lock();
+        if parsed == PoolRoute::Both {
+            lock.pool_routes.remove(&source);
+        } else {
+            lock.pool_routes.insert(source.clone(), parsed);
+        }
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] ROUTE: {} -> {} pool", ts, source, parsed.name());
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(())
+    }
+
+    /// Per-source RCT cutoff (consecutive repeats), APT cutoff (share of the window the reference
+    /// sample may fill) and APT window (SP 800-90B uses 512 for byte samples, 1024 for bits).
+    /// Must stay within the harvester-side gross limits (RCT 32, APT 0.80).
+    fn set_health_params(&self, source: String, rct_cutoff: usize, apt_cutoff: f64, window: usize) -> PyResult<()> {
+        if !(2..=GROSS_RCT_CUTOFF).contains(&rct_cutoff) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("rct_cutoff must be 2..={}", GROSS_RCT_CUTOFF)));
+        }
+        if !(apt_cutoff > 0.0 && apt_cutoff <= GROSS_APT_CUTOFF) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("apt_cutoff must be in (0, {}]", GROSS_APT_CUTOFF)));
+        }
+        if !(16..=4096).contains(&window) {
+            return Err(pyo3::exceptions::PyValueError::new_err("window must be 16..=4096 samples"));
+        }
+        
+        let source = source.to_uppercase();
+        let mut lock = self.state.lock();
+        let current = lock.health_params.get(&source).copied().unwrap_or_else(|| HealthParams::default_for(&source));
+        let params = HealthParams { rct_cutoff, apt_cutoff, window, ..current };
+        lock.health_params.insert(source.clone(), params);
+        // The running APT window was sized for the old parameters
+        if let Some(health) = lock.source_health.get_mut(&source) {
+            health.apt = AdaptiveProportion::default();
+        }
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!(
+            "[{}] HEALTH: {} -> RCT {}, APT {}/{}", ts, source, rct_cutoff, params.apt_cutoff_count(), window
+        );
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(())
+    }
+
+    /// Registers `callback(source, test, statistic, severity)`, invoked from the mixer thread on
+    /// health test failures, state transitions and low total entropy rate
+    fn on_health_event(&self, py: Python<'_>, callback: Py<PyAny>) -> PyResult<()> {
+        if !callback.bind(py).is_callable() {
+            return Err(pyo3::exceptions::PyTypeError::new_err("callback must be callable"));
+        }
+        self.state.lock().health_callbacks.push(callback);
+        Ok(())
+    }
+
+    /// Fires an ENTROPY_RATE health event when credited entropy drops below `min_bits_per_sec`
+    /// (measured over 10 s windows); None disables it
+    #[pyo3(signature = (min_bits_per_sec=None))]
+    fn set_entropy_rate_alarm(&self, min_bits_per_sec: Option<f64>) -> PyResult<()> {
+        if let Some(rate) = min_bits_per_sec {
+            if !rate.is_finite() || rate < 0.0 {
+                return Err(pyo3::exceptions::PyValueError::new_err("min_bits_per_sec must be >= 0"));
+            }
+        }
+        self.state.lock().entropy_rate_alarm = min_bits_per_sec;
+        Ok(())
+    }
+
+    /// Output and minting raise EntropyFailureError while credited pool entropy is below this
+    /// (0 disables the floor; quarantine of every active source always triggers failure mode)
+    fn set_entropy_floor(&self, bits: f64) -> PyResult<()> {
+        if !(0.0..=LEDGER_CAPACITY_BITS).contains(&bits) {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                format!("bits must be 0-{}", LEDGER_CAPACITY_BITS)));
+        }
+        self.state.lock().entropy_floor_bits = bits;
+        Ok(())
+    }
+
+    /// Research mode: samples failing health checks (harvester filter, startup, continuous
+    /// tests, quarantine) are still mixed in, with zero entropy credit, and counted as tainted.
+    /// Local sources only: P2P and mobile input is still dropped when it fails.
+    fn set_research_mode(&self, enabled: bool) {
+        let mut lock = self.state.lock();
+        lock.research_mode = enabled;
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] HEALTH: Research mode {}", ts, if enabled { "ON (failing samples tainted, uncredited)" } else { "OFF" });
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    /// Enables the chi-square test for `source` with this alarm threshold (255 degrees of freedom;
+    /// 330.5 is p = 0.001 for uniform bytes). Off by default except for P2P peers; `None` disables it.
+    #[pyo3(signature = (source, threshold=None))]
+    fn set_chi_square_threshold(&self, source: String, threshold: Option<f64>) -> PyResult<()> {
+        if threshold.is_some_and(|t| !t.is_finite() || t <= 0.0) {
+            return Err(pyo3::exceptions::PyValueError::new_err("threshold must be positive"));
+        }
+        let source = source.to_uppercase();
+        let mut lock = self.state.lock();
+        let current = lock.health_params.get(&source).copied().unwrap_or_else(|| HealthParams::default_for(&source));
+        if threshold.is_none() {
+            if let Some(metrics) = lock.source_metrics.get_mut(&source) {
+                metrics.chi_square = 0.0;
+                metrics.chi_square_alarm = false;
+            }
+        }
+        lock.health_params.insert(source, HealthParams { chi_square_threshold: threshold, ..current });
+        Ok(())
+    }
+
+    fn set_audio_mode(&self, mode: String) -> PyResult<()> {
+        let audio_mode = match mode.to_lowercase().as_str() {
+            "normal" => AudioMode::Normal,
+            "ultrasonic" => AudioMode::Ultrasonic,
+            _ => return Err(pyo3::exceptions::PyValueError::new_err(format!("Unknown audio mode: {}", mode))),
+        };
+        
+        let mut lock = self.state.lock();
+        lock.audio_mode = audio_mode;
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] AUDIO: Mode -> {}", ts, mode.to_uppercase());
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(())
+    }
+
+    fn list_audio_devices(&self) -> PyResult<String> {
+        use cpal::traits::{DeviceTrait, HostTrait};
+        
+        let host = cpal::default_host();
+        let default_name = host.default_input_device().and_then(|d| d.name().ok());
+        let inputs = host.input_devices()
+            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+        let active = self.state.lock().audio_devices.clone();
+        
+        let devices: Vec<serde_json::Value> = inputs
+            .filter_map(|d| d.name().ok())
+            .map(|name| {
+                serde_json::json!({
+                    "name": name,
+                    "default": default_name.as_ref() == Some(&name),
+                    "active": active.contains(&name),
+                    "source": audio_source_name(&name),
+                })
+            })
+            .collect();
+        
+        Ok(serde_json::Value::Array(devices).to_string())
+    }
+
+    /// Replace the captured inputs with a single device ("" = host default)
+    fn set_audio_device(&self, name: String) {
+        self.state.lock().audio_devices.retain(|d| d == &name);
+        ensure_audio_harvester(&self.tx_entropy, &self.running, &self.state, &name);
+        
+        let mut lock = self.state.lock();
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] AUDIO: Capturing {}", ts, audio_source_name(&name));
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    /// Capture an additional input alongside the current ones
+    fn add_audio_device(&self, name: String) {
+        ensure_audio_harvester(&self.tx_entropy, &self.running, &self.state, &name);
+        
+        let mut lock = self.state.lock();
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] AUDIO: Added {}", ts, audio_source_name(&name));
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    fn remove_audio_device(&self, name: String) {
+        let mut lock = self.state.lock();
+        lock.audio_devices.retain(|d| d != &name);
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] AUDIO: Removed {}", ts, audio_source_name(&name));
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    fn set_ntp_servers(&self, servers: Vec<String>) {
+        let mut lock = self.state.lock();
+        lock.ntp_servers = servers;
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] CLOCK: {} NTP server(s) configured", ts, lock.ntp_servers.len());
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    fn set_fs_watch_paths(&self, paths: Vec<String>) {
+        let mut lock = self.state.lock();
+        lock.fs_watch_paths = paths;
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] FS: Watching {} path(s)", ts, lock.fs_watch_paths.len());
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+    }
+
+    /// (Re-)registering a device resets its replay counter and lifts any quarantine
+    fn register_mobile_device(&self, device_id: String, key_hex: String) -> PyResult<()> {
+        let key = hex::decode(&key_hex)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("Invalid key hex: {}", e)))?;
+        if key.len() < 16 {
+            return Err(pyo3::exceptions::PyValueError::new_err("Attestation key must be at least 16 bytes"));
+        }
+        
+        let mut lock = self.state.lock();
+        lock.mobile_devices.insert(device_id.clone(), MobileDevice {
+            key,
+            last_counter: 0,
+            accepted: 0,
+            rejected: 0,
+            consecutive_failures: 0,
+            quarantined: false,
+        });
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] MOBILE: Registered {} as {}", ts, device_id, mobile_source_name(&device_id));
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(())
+    }
+
+    fn remove_mobile_device(&self, device_id: String) {
+        let mut lock = self.state.lock();
+        if lock.mobile_devices.remove(&device_id).is_some() {
+            let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+            let msg = format!("[{}] MOBILE: Removed {}", ts, device_id);
+            if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+            lock.logs.push_back(msg);
+        }
+    }
+
+    /// Names are upper-cased and always carry the PY_ prefix (added if missing), so a plugin can
+    /// never report under a native harvester's name. Returns the name to submit under.
+    fn register_source(&self, name: String) -> PyResult<String> {
+        let name = plugin_source_name(&name);
+        if name == "PY_" {
+            return Err(pyo3::exceptions::PyValueError::new_err("Source name must not be empty"));
+        }
+        
+        let mut lock = self.state.lock();
+        if lock.custom_sources.insert(name.clone()) {
+            let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+            let msg = format!("[{}] PLUGIN: Registered source {}", ts, name);
+            if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+            lock.logs.push_back(msg);
+        }
+        Ok(name)
+    }
+
+    fn unregister_source(&self, name: String) {
+        let name = plugin_source_name(&name);
+        let mut lock = self.state.lock();
+        if lock.custom_sources.remove(&name) {
+            let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+            let msg = format!("[{}] PLUGIN: Unregistered source {}", ts, name);
+            if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+            lock.logs.push_back(msg);
+        }
+    }
+
+    /// Returns false if the sample failed health checks or the mixer queue was full
+    fn submit_entropy(&self, name: String, data: &[u8]) -> PyResult<bool> {
+        let name = plugin_source_name(&name);
+        if !self.state.lock().custom_sources.contains(&name) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("Source {} is not registered", name)));
+        }
+        
+        if data.is_empty() || !admits_sample(&self.state, data) {
+            return Ok(false);
+        }
+        Ok(self.tx_entropy.try_send((name, data.to_vec())).is_ok())
+    }
+
+    /// `n` random bytes from the PYTHON output consumer's DRBG, reseeded from the pool per the
+    /// reseed policy; debits n * 8 bits from the entropy ledger
+    fn get_random_bytes<'py>(&self, py: Python<'py>, n: usize) -> PyResult<Bound<'py, pyo3::types::PyBytes>> {
+        if !(1..=1 << 20).contains(&n) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("n must be 1..=1048576, got {}", n)));
+        }
+        
+        let mut out = python_output(&self.state, n)?;
+        let bytes = pyo3::types::PyBytes::new(py, &out);
+        out.zeroize();
+        Ok(bytes)
+    }
+
+    /// /dev/random-style variant of get_random_bytes: waits (GIL released, Ctrl-C honoured) until
+    /// the ledger holds `min_pool_entropy_bits` of credited entropy (default n * 8, capped at the
+    /// pool's 256), then reseeds the PYTHON consumer from the pool and generates. Raises
+    /// TimeoutError after `timeout` seconds; None waits indefinitely.
+    #[pyo3(signature = (n, min_pool_entropy_bits=None, timeout=None))]
+    fn get_random_bytes_blocking<'py>(&self, py: Python<'py>, n: usize, min_pool_entropy_bits: Option<f64>, timeout: Option<f64>) -> PyResult<Bound<'py, pyo3::types::PyBytes>> {
+        if !(1..=1 << 20).contains(&n) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("n must be 1..=1048576, got {}", n)));
+        }
+        let min_bits = min_pool_entropy_bits.unwrap_or((n * 8) as f64).min(LEDGER_CAPACITY_BITS);
+        if !min_bits.is_finite() || min_bits <= 0.0 {
+            return Err(pyo3::exceptions::PyValueError::new_err("min_pool_entropy_bits must be positive"));
+        }
+        if timeout.is_some_and(|t| !t.is_finite() || t < 0.0) {
+            return Err(pyo3::exceptions::PyValueError::new_err("timeout must be non-negative"));
+        }
+        let deadline = timeout.map(|t| Instant::now() + Duration::from_secs_f64(t));
+        
+        loop {
+            let state = self.state.clone();
+            let ready = py.allow_threads(move || {
+                // Short waits between signal checks
+                let slice_end = Instant::now() + Duration::from_millis(100);
+                loop {
+                    if state.lock().ledger.available_bits >= min_bits { return true; }
+                    if Instant::now() >= slice_end { return false; }
+                    thread::sleep(Duration::from_millis(10));
+                }
+            });
+            
+            if ready {
+                let mut lock = self.state.lock();
+                // Credit may have been spent by another consumer between the wait and the lock
+                if lock.ledger.available_bits >= min_bits {
+                    check_output_allowed(&lock)?;
+                    force_reseed_consumers(&mut lock, Some("PYTHON"), "blocking");
+                    let mut out = consumer_generate(&mut lock, "PYTHON", n)
+                        .map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+                    drop(lock);
+                    let bytes = pyo3::types::PyBytes::new(py, &out);
+                    out.zeroize();
+                    return Ok(bytes);
+                }
+            }
+            if deadline.is_some_and(|d| Instant::now() >= d) {
+                return Err(pyo3::exceptions::PyTimeoutError::new_err(format!(
+                    "pool entropy stayed below {} bits", min_bits)));
+            }
+            py.check_signals()?;
+        }
+    }
+
+    /// Userspace entropy daemon mode: every 5 s, 64 bytes from the KERNEL consumer go to
+    /// /dev/random via RNDADDENTROPY, credited at `bits_per_byte` (0..=8, and never more than the
+    /// ledger holds). Linux only, needs CAP_SYS_ADMIN; None stops feeding.
+    #[pyo3(signature = (bits_per_byte=None))]
+    fn feed_kernel(&self, bits_per_byte: Option<f64>) -> PyResult<()> {
+        if let Some(bits) = bits_per_byte {
+            if !cfg!(target_os = "linux") {
+                return Err(pyo3::exceptions::PyRuntimeError::new_err("Kernel feeding needs Linux"));
+            }
+            if !bits.is_finite() || !(0.0..=8.0).contains(&bits) {
+                return Err(pyo3::exceptions::PyValueError::new_err("bits_per_byte must be 0..=8"));
+            }
+        }
+        
+        let mut lock = self.state.lock();
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = match bits_per_byte {
+            Some(bits) => {
+                match lock.kernel_feed.as_mut() {
+                    Some(feed) => feed.bits_per_byte = bits,
+                    None => lock.kernel_feed = Some(KernelFeed { bits_per_byte: bits, bytes_fed: 0, bits_credited: 0, last_error: None }),
+                }
+                format!("[{}] KERNEL: Feeding /dev/random at {:.2} bits/byte", ts, bits)
+            }
+            None => {
+                lock.kernel_feed = None;
+                format!("[{}] KERNEL: Feeding stopped", ts)
+            }
+        };
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(())
+    }
+
+    /// Serves the EGD (entropy gathering daemon) protocol on a Unix socket at `path`, from the
+    /// EGD output consumer, with per-client accounting in get_metrics. Replaces a running server.
+    /// Connected clients are dropped when it stops.
+    fn start_egd_server(&self, path: String) -> PyResult<()> {
+        start_egd_server(path.clone(), self.tx_entropy.clone(), self.state.clone(), self.running.clone())
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(format!("{}: {}", path, e)))?;
+        
+        let mut lock = self.state.lock();
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] EGD: Listening on {}", ts, path);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(())
+    }
+
+    /// Closes the EGD socket and removes it
+    fn stop_egd_server(&self) {
+        let mut lock = self.state.lock();
+        if let Some(server) = lock.egd_server.take() {
+            server.stop.store(true, Ordering::Relaxed);
+            let _ = fs::remove_file(&server.path);
+            let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+            let msg = format!("[{}] EGD: Closed {}", ts, server.path);
+            if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+            lock.logs.push_back(msg);
+        }
+    }
+
+    /// Local randomness service on a Unix socket for non-Python processes: length-prefixed
+    /// "N bytes" and "status" requests, served from the SOCKET consumer. Peers are admitted by
+    /// uid (SO_PEERCRED): root, our own uid, and `allowed_uids`. Replaces a running service.
+    #[pyo3(signature = (path="/run/chaosmagnet.sock".to_string(), allowed_uids=Vec::new()))]
+    fn start_socket_service(&self, path: String, allowed_uids: Vec<u32>) -> PyResult<()> {
+        start_socket_service(path.clone(), allowed_uids.clone(), self.state.clone(), self.running.clone())
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(format!("{}: {}", path, e)))?;
+        
+        let mut lock = self.state.lock();
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] SOCKET: Listening on {} ({} extra uids)", ts, path, allowed_uids.len());
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(())
+    }
+
+    /// Closes the socket service and removes its socket
+    fn stop_socket_service(&self) {
+        let mut lock = self.state.lock();
+        if let Some(service) = lock.socket_service.take() {
+            service.stop.store(true, Ordering::Relaxed);
+            let _ = fs::remove_file(&service.path);
+            let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+            let msg = format!("[{}] SOCKET: Closed {}", ts, service.path);
+            if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+            lock.logs.push_back(msg);
+        }
+    }
+
+    /// Writes `num_bytes` of FILE-consumer output to `path` (one-time pads, test corpora for
+    /// statistical suites), with the GIL released; debits the ledger like any other output
+    fn write_random_file(&self, py: Python<'_>, path: String, num_bytes: u64) -> PyResult<()> {
+        if num_bytes == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err("num_bytes must be positive"));
+        }
+        check_output_allowed(&self.state.lock())?;
+        let state = self.state.clone();
+        py.allow_threads(|| write_random_file(&state, &path, num_bytes))
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(format!("{}: {}", path, e)))?;
+        
+        let mut lock = self.state.lock();
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] FILE: Wrote {} bytes to {}", ts, num_bytes, path);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(())
+    }
+
+    /// Streams FILE-consumer output into `path` until stop_file_sink, rotating to `path.1`..
+    /// `path.<keep_files>` every `rotate_at_bytes`, so disk use stays bounded. `rate_limit` in
+    /// bytes/s, None for unthrottled. Replaces a running sink.
+    #[pyo3(signature = (path, rotate_at_bytes, keep_files=4, rate_limit=None))]
+    fn start_file_sink(&self, path: String, rotate_at_bytes: u64, keep_files: u32, rate_limit: Option<u64>) -> PyResult<()> {
+        if rotate_at_bytes == 0 || rate_limit == Some(0) {
+            return Err(pyo3::exceptions::PyValueError::new_err("rotate_at_bytes and rate_limit must be positive"));
+        }
+        start_file_sink(FileSink {
+            path: path.clone(),
+            rotate_at_bytes,
+            keep_files,
+            rate_limit,
+            bytes_written: 0,
+            rotations: 0,
+            stop: Arc::new(AtomicBool::new(false)),
+        }, self.state.clone(), self.running.clone());
+        
+        let mut lock = self.state.lock();
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] FILE: Sink {} (rotate at {} bytes, keep {})", ts, path, rotate_at_bytes, keep_files);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(())
+    }
+
+    /// Stops the file sink; returns the bytes it wrote
+    fn stop_file_sink(&self) -> PyResult<u64> {
+        let mut lock = self.state.lock();
+        let Some(sink) = lock.file_sink.take() else {
+            return Err(pyo3::exceptions::PyValueError::new_err("no file sink running"));
+        };
+        sink.stop.store(true, Ordering::Relaxed);
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] FILE: Sink {} stopped after {} bytes", ts, sink.path, sink.bytes_written);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(sink.bytes_written)
+    }
+
+    /// Continuously writes FIFO-consumer output to the named pipe at `path` (created if missing),
+    /// so `dd`, dieharder and the like can read it like a device file. `rate_limit` in bytes/s,
+    /// None for as fast as the reader drains it.
+    #[pyo3(signature = (path, rate_limit=None))]
+    fn start_fifo_output(&self, path: String, rate_limit: Option<u64>) -> PyResult<()> {
+        if rate_limit == Some(0) {
+            return Err(pyo3::exceptions::PyValueError::new_err("rate_limit must be positive"));
+        }
+        start_fifo_output(path.clone(), rate_limit, self.state.clone(), self.running.clone())
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(format!("{}: {}", path, e)))?;
+        
+        let mut lock = self.state.lock();
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = match rate_limit {
+            Some(rate) => format!("[{}] FIFO: Writing to {} at {} B/s", ts, path, rate),
+            None => format!("[{}] FIFO: Writing to {}", ts, path),
+        };
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(())
+    }
+
+    /// Stops writing to the pipe at `path`; the pipe itself is left in place
+    fn stop_fifo_output(&self, path: String) -> PyResult<()> {
+        let mut lock = self.state.lock();
+        let Some(output) = lock.fifo_outputs.remove(&path) else {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("no FIFO output running for {}", path)));
+        };
+        output.stop.store(true, Ordering::Relaxed);
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] FIFO: Stopped {} after {} bytes", ts, path, output.bytes_written);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(())
+    }
+
+    /// Fills a writable buffer (bytearray, memoryview, numpy array...) in place from the PYTHON
+    /// consumer, with the GIL released; returns the number of bytes written. The buffer must be
+    /// C-contiguous; its raw bytes are filled regardless of item type.
+    fn fill_buffer(&self, py: Python<'_>, buf: &Bound<'_, PyAny>) -> PyResult<usize> {
+        let view = pyo3::types::PyMemoryView::from(buf)?.call_method1("cast", ("B",))?;
+        let buffer = pyo3::buffer::PyBuffer::<u8>::get(&view)?;
+        if buffer.readonly() {
+            return Err(pyo3::exceptions::PyValueError::new_err("buffer is read-only"));
+        }
+        let len = buffer.len_bytes();
+        let addr = buffer.buf_ptr() as usize;
+        let state = self.state.clone();
+        
+        py.allow_threads(move || {
+            // SAFETY: `buffer` keeps the export alive and writable until after this closure, and
+            // the cast to "B" guarantees `len` contiguous bytes starting at `addr`
+            let out = unsafe { std::slice::from_raw_parts_mut(addr as *mut u8, len) };
+            // Per-MiB so the mixer isn't locked out for the whole fill
+            for chunk in out.chunks_mut(1 << 20) {
+                let mut lock = state.lock();
+                check_output_allowed(&lock)?;
+                consumer_fill(&mut lock, "PYTHON", chunk).map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+            }
+            Ok::<(), PyErr>(())
+        })?;
+        drop(buffer);
+        Ok(len)
+    }
+
+    /// Uniform 64-bit integer from the PYTHON consumer
+    fn rand_u64(&self) -> PyResult<u64> {
+        python_u64(&self.state)
+    }
+
+    /// Uniform integer in lo..hi (hi exclusive, like range()), by rejection sampling
+    fn rand_range(&self, lo: i64, hi: i64) -> PyResult<i64> {
+        if hi <= lo {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("empty range {}..{}", lo, hi)));
+        }
+        let span = (hi as i128 - lo as i128) as u64;
+        let offset = python_below(&self.state, span)?;
+        Ok((lo as i128 + offset as i128) as i64)
+    }
+
+    /// Uniform float in [0, 1) with 53 random bits, every value a multiple of 2^-53
+    fn rand_float(&self) -> PyResult<f64> {
+        Ok((python_u64(&self.state)? >> 11) as f64 / (1u64 << 53) as f64)
+    }
+
+    /// Random (version 4) UUID string
+    fn uuid4(&self) -> PyResult<String> {
+        Ok(python_uuids(&self.state, 4, 1)?.remove(0))
+    }
+
+    /// `count` (1..=100000) version 4 UUIDs from a single DRBG draw
+    fn uuid4_batch(&self, count: usize) -> PyResult<Vec<String>> {
+        if !(1..=100_000).contains(&count) {
+            return Err(pyo3::exceptions::PyValueError::new_err("count must be 1..=100000"));
+        }
+        python_uuids(&self.state, 4, count)
+    }
+
+    /// Time-ordered (version 7) UUID string; monotonic across calls
+    fn uuid7(&self) -> PyResult<String> {
+        Ok(python_uuids(&self.state, 7, 1)?.remove(0))
+    }
+
+    /// `count` (1..=100000) version 7 UUIDs, in ascending order
+    fn uuid7_batch(&self, count: usize) -> PyResult<Vec<String>> {
+        if !(1..=100_000).contains(&count) {
+            return Err(pyo3::exceptions::PyValueError::new_err("count must be 1..=100000"));
+        }
+        python_uuids(&self.state, 7, count)
+    }
+
+    /// Password of `length` characters drawn uniformly from `charset` ("alnum", "alpha",
+    /// "digits", "hex", "printable", or a literal string of characters); returns it with its
+    /// entropy in bits
+    #[pyo3(signature = (length, charset="alnum"))]
+    fn generate_password(&self, length: usize, charset: &str) -> PyResult<(String, f64)> {
+        if !(1..=1024).contains(&length) {
+            return Err(pyo3::exceptions::PyValueError::new_err("length must be 1..=1024"));
+        }
+        let chars = password_charset(charset);
+        if chars.len() < 2 {
+            return Err(pyo3::exceptions::PyValueError::new_err("charset needs at least 2 distinct characters"));
+        }
+        let mut password = String::with_capacity(length);
+        for _ in 0..length {
+            password.push(chars[python_below(&self.state, chars.len() as u64)? as usize]);
+        }
+        Ok((password, length as f64 * (chars.len() as f64).log2()))
+    }
+
+    /// Passphrase of `words` words drawn uniformly from `wordlist` (an EFF list name under
+    /// wordlists/, or a path) joined by `separator`; returns it with its entropy in bits
+    #[pyo3(signature = (words, wordlist="eff_large", separator=" "))]
+    fn generate_passphrase(&self, words: usize, wordlist: &str, separator: &str) -> PyResult<(String, f64)> {
+        if !(1..=64).contains(&words) {
+            return Err(pyo3::exceptions::PyValueError::new_err("words must be 1..=64"));
+        }
+        let list = load_wordlist(wordlist)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(format!("wordlist {}: {}", wordlist, e)))?;
+        if list.len() < 2 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("wordlist {} has fewer than 2 words", wordlist)));
+        }
+        let mut chosen = Vec::with_capacity(words);
+        for _ in 0..words {
+            chosen.push(list[python_below(&self.state, list.len() as u64)? as usize].as_str());
+        }
+        Ok((chosen.join(separator), words as f64 * (list.len() as f64).log2()))
+    }
+
+    /// `count` rolls of a `sides`-sided die (uniform by rejection) with a SHA-256 commitment to the
+    /// DRBG transcript behind them, from which the rolls can be re-derived and checked against the
+    /// commitment. Returns (rolls, commitment hex, transcript hex).
+    #[pyo3(signature = (sides=6, count=1))]
+    fn roll_dice(&self, sides: u32, count: usize) -> PyResult<(Vec<u32>, String, String)> {
+        if sides < 2 || !(1..=10_000).contains(&count) {
+            return Err(pyo3::exceptions::PyValueError::new_err("sides must be >= 2 and count 1..=10000"));
+        }
+        let (rolls, commitment, transcript) = dice_rolls(&self.state, sides, count)?;
+        Ok((rolls, hex::encode(commitment), hex::encode(transcript)))
+    }
+
+    /// Diceware passphrase of `words` words: d6 rolls looked up in a diceware list ("eff_large",
+    /// "eff_short2", or a path). Returns (passphrase, dice codes, commitment hex, transcript hex),
+    /// the commitment covering every roll as in roll_dice.
+    #[pyo3(signature = (words, wordlist="eff_large", separator=" "))]
+    fn diceware(&self, words: usize, wordlist: &str, separator: &str) -> PyResult<(String, Vec<String>, String, String)> {
+        if !(1..=64).contains(&words) {
+            return Err(pyo3::exceptions::PyValueError::new_err("words must be 1..=64"));
+        }
+        let (codes, dice) = load_diceware_list(wordlist)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("wordlist {}: {}", wordlist, e)))?;
+        let (rolls, commitment, transcript) = dice_rolls(&self.state, 6, words * dice)?;
+        
+        let rolled: Vec<String> = rolls.chunks(dice)
+            .map(|r| r.iter().map(|d| char::from(b'0' + *d as u8)).collect())
+            .collect();
+        let passphrase = rolled.iter()
+            .map(|code| codes.get(code).map_or("", String::as_str))
+            .collect::<Vec<_>>()
+            .join(separator);
+        Ok((passphrase, rolled, hex::encode(commitment), hex::encode(transcript)))
+    }
+
+    /// Shuffles `list` in place (Fisher-Yates over the DRBG), for lottery draws and randomized
+    /// trials; the GIL is released while drawing for large lists
+    fn shuffle(&self, py: Python<'_>, list: &Bound<'_, pyo3::types::PyList>) -> PyResult<()> {
+        // Snapshot first: other threads may resize the list while the GIL is released
+        let mut items: Vec<Bound<'_, PyAny>> = list.iter().collect();
+        let n = items.len();
+        if n < 2 {
+            return Ok(());
+        }
+        let targets = if n >= SHUFFLE_GIL_RELEASE_LEN {
+            let state = self.state.clone();
+            py.allow_threads(move || fisher_yates_targets(&state, n, n - 1))?
+        } else {
+            fisher_yates_targets(&self.state, n, n - 1)?
+        };
+        
+        for (step, j) in targets.into_iter().enumerate() {
+            items.swap(n - 1 - step, j);
+        }
+        for (i, item) in items.into_iter().enumerate() {
+            list.set_item(i, item)?;
+        }
+        Ok(())
+    }
+
+    /// `k` distinct elements of `population`, uniformly chosen and in selection order (partial
+    /// Fisher-Yates over the DRBG)
+    fn sample(&self, py: Python<'_>, population: Vec<Py<PyAny>>, k: usize) -> PyResult<Vec<Py<PyAny>>> {
+        let n = population.len();
+        if k > n {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("sample of {} from a population of {}", k, n)));
+        }
+        let targets = if k >= SHUFFLE_GIL_RELEASE_LEN {
+            let state = self.state.clone();
+            py.allow_threads(move || fisher_yates_targets(&state, n, k))?
+        } else {
+            fisher_yates_targets(&self.state, n, k)?
+        };
+        
+        let mut items = population;
+        for (step, j) in targets.into_iter().enumerate() {
+            items.swap(n - 1 - step, j);
+        }
+        let mut chosen = items.split_off(n - k);
+        chosen.reverse();
+        Ok(chosen)
+    }
+
+    /// A ChaosStream with its own DRBG (`generator`: "ctr" or "hmac"), reseeded from the pool per
+    /// the reseed policy, so several consumers can draw concurrently. Iterating yields
+    /// `chunk_size`-byte blocks.
+    #[pyo3(signature = (chunk_size=32, generator="ctr"))]
+    fn get_stream(&self, chunk_size: usize, generator: &str) -> PyResult<ChaosStream> {
+        if !(1..=1 << 20).contains(&chunk_size) {
+            return Err(pyo3::exceptions::PyValueError::new_err("chunk_size must be 1..=1048576"));
+        }
+        let kind = DrbgKind::from_name(generator)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("Unknown generator: {}", generator)))?;
+        
+        let mut lock = self.state.lock();
+        lock.streams_opened += 1;
+        let name = format!("STREAM_{}", lock.streams_opened);
+        lock.output_consumers.insert(name.clone(), OutputConsumer::new(kind));
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] DRBG: Opened {} ({})", ts, name, generator.to_uppercase());
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        
+        Ok(ChaosStream {
+            state: self.state.clone(),
+            name,
+            chunk_size,
+        })
+    }
+
+    /// `num_bytes` (8..=4096) right after a SHAKE256 pass over everything the source pools have
+    /// buffered, instead of waiting for the next scheduled extraction. The SHAKE output is mixed
+    /// into the pool and the bytes come from the XOF consumer reseeded on it, so quotas and the
+    /// ledger debit apply as for any output.
+    fn extract_xof<'py>(&self, py: Python<'py>, num_bytes: usize) -> PyResult<Bound<'py, pyo3::types::PyBytes>> {
+        if !(8..=4096).contains(&num_bytes) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("num_bytes must be 8..=4096, got {}", num_bytes)));
+        }
+        
+        let mut lock = self.state.lock();
+        check_output_allowed(&lock)?;
+        let (mut fresh, h_in) = lock.fortuna.extract_xof(32)
+            .map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        lock.accumulator.mix(&[b"CHAOS_MAGNET_XOF_RESEED", &fresh]);
+        fresh.zeroize();
+        if !lock.output_consumers.contains_key("XOF") {
+            lock.output_consumers.insert("XOF".to_string(), OutputConsumer::new(DrbgKind::Ctr));
+        }
+        force_reseed_consumers(&mut lock, Some("XOF"), "xof");
+        let mut out = consumer_generate(&mut lock, "XOF", num_bytes)
+            .map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] XOF: {} bytes after a SHAKE256 reseed (h_in {:.0} bits)", ts, num_bytes, h_in);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        drop(lock);
+        
+        let bytes = pyo3::types::PyBytes::new(py, &out);
+        out.zeroize();
+        Ok(bytes)
+    }
+
+    /// Folds caller-provided material (HSM output, dice rolls, seed ceremonies) into the main and
+    /// slow pools. The entropy credit is whatever the caller claims, up to 8 bits per byte.
+    #[pyo3(signature = (data, claimed_entropy_bits=0.0, provenance=None))]
+    fn inject_seed(&self, data: &[u8], claimed_entropy_bits: f64, provenance: Option<String>) -> PyResult<()> {
+        if data.is_empty() || data.len() > 65536 {
+            return Err(pyo3::exceptions::PyValueError::new_err("Seed must be 1..=65536 bytes"));
+        }
+        let max_bits = data.len() as f64 * 8.0;
+        if !(0.0..=max_bits).contains(&claimed_entropy_bits) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "claimed_entropy_bits must be 0..={} for {} bytes", max_bits, data.len()
+            )));
+        }
+        let provenance = provenance.unwrap_or_else(|| "UNSPECIFIED".to_string());
+        let source = format!("INJECT:{}", provenance);
+        
+        let mut lock = self.state.lock();
+        lock.accumulator.mix(&[b"CHAOS_MAGNET_SEED_INJECT", &(data.len() as u64).to_le_bytes(), data]);
+        lock.slow_pool.add_raw_bytes(&source, data, claimed_entropy_bits);
+        lock.ledger.credit_claimed(&source, claimed_entropy_bits);
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!(
+            "[{}] INJECT: {} bytes from {}, {:.0} bits claimed",
+            ts, data.len(), provenance, claimed_entropy_bits
+        );
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(())
+    }
+
+    /// Per-sample whitening ahead of pooling: XOR-fold `fold_rounds` times (0 = off), then
+    /// SHA-256 over samples of 64+ bytes if `hash` is set
+    #[pyo3(signature = (fold_rounds=0, hash=false))]
+    fn set_whitener(&self, fold_rounds: u8, hash: bool) -> PyResult<()> {
+        if fold_rounds > 4 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("fold_rounds must be 0..=4, got {}", fold_rounds)));
+        }
+        
+        let mut lock = self.state.lock();
+        lock.whitener.fold_rounds = fold_rounds;
+        lock.whitener.hash = hash;
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] WHITENER: fold x{}, hash {}", ts, fold_rounds, if hash { "ON" } else { "OFF" });
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(())
+    }
+
+    /// Known-answer and avalanche tests for the active conditioner; returns a JSON report
+    fn run_extractor_selftest(&self) -> PyResult<String> {
+        let conditioner = self.state.lock().fortuna.conditioner.clone();
+        
+        let known_answers = extractor_known_answers(conditioner.extractor);
+        let kat_passed = known_answers.iter().all(|(_, ok)| *ok);
+        let (mean, lo, hi) = extractor_avalanche(&conditioner, SELFTEST_AVALANCHE_TRIALS);
+        let avalanche_passed = (0.5 - mean).abs() <= SELFTEST_AVALANCHE_TOLERANCE;
+        let passed = kat_passed && avalanche_passed;
+        
+        let report = serde_json::json!({
+            "extractor": conditioner.extractor.name(),
+            "passed": passed,
+            "known_answer": known_answers.iter()
+                .map(|(name, ok)| serde_json::json!({"test": name, "passed": ok}))
+                .collect::<Vec<_>>(),
+            "avalanche": {
+                "trials": SELFTEST_AVALANCHE_TRIALS,
+                "mean_flip_fraction": mean,
+                "min_flip_fraction": lo,
+                "max_flip_fraction": hi,
+                "passed": avalanche_passed,
             },
-            Err(e) => {
-                eprintln!("P2P: Failed to bind to {}: {}", addr, e);
-                return;
+        });
+        
+        let mut lock = self.state.lock();
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!(
+            "[{}] SELFTEST: {} {} (KAT {}, avalanche {:.3})",
+            ts, conditioner.extractor.name(), if passed { "PASS" } else { "FAIL" },
+            if kat_passed { "ok" } else { "FAILED" }, mean
+        );
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        
+        Ok(report.to_string())
+    }
+
+    /// FIPS 140-2 monobit/poker/runs/long-run battery over the last `kilobytes` of raw data from
+    /// `source`, split into 20,000-bit blocks; returns a JSON report
+    #[pyo3(signature = (source, kilobytes=16))]
+    fn run_health_battery(&self, source: String, kilobytes: usize) -> PyResult<String> {
+        if !(3..=BATTERY_HISTORY_BYTES / 1024).contains(&kilobytes) {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                format!("kilobytes must be 3-{}", BATTERY_HISTORY_BYTES / 1024)));
+        }
+        let source = source.to_uppercase();
+        let raw: Vec<u8> = {
+            let lock = self.state.lock();
+            let Some(health) = lock.source_health.get(&source) else {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!("no data from {}", source)));
+            };
+            let take = health.history.len().min(kilobytes * 1024);
+            health.history.iter().skip(health.history.len() - take).copied().collect()
+        };
+        if raw.len() < FIPS_BLOCK_BYTES {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "{} has {} bytes buffered, need {}", source, raw.len(), FIPS_BLOCK_BYTES)));
+        }
+        
+        // Most recent whole blocks only
+        let skip = raw.len() % FIPS_BLOCK_BYTES;
+        let results: Vec<FipsBlockResult> = raw[skip..].chunks(FIPS_BLOCK_BYTES).map(fips_block).collect();
+        let count = |f: fn(&FipsBlockResult) -> bool| results.iter().filter(|r| f(r)).count();
+        let (monobit, poker, runs, long_run) = (
+            count(FipsBlockResult::monobit_passed),
+            count(FipsBlockResult::poker_passed),
+            count(FipsBlockResult::runs_passed),
+            count(FipsBlockResult::long_run_passed),
+        );
+        let blocks = results.len();
+        let passed = monobit == blocks && poker == blocks && runs == blocks && long_run == blocks;
+        
+        let report = serde_json::json!({
+            "source": source,
+            "bytes_tested": blocks * FIPS_BLOCK_BYTES,
+            "blocks": blocks,
+            "passed": passed,
+            "summary": {
+                "monobit": {"passed_blocks": monobit, "failed_blocks": blocks - monobit},
+                "poker": {"passed_blocks": poker, "failed_blocks": blocks - poker},
+                "runs": {"passed_blocks": runs, "failed_blocks": blocks - runs},
+                "long_run": {"passed_blocks": long_run, "failed_blocks": blocks - long_run},
+            },
+            "bounds": {
+                "monobit": [FIPS_MONOBIT_BOUNDS.0, FIPS_MONOBIT_BOUNDS.1],
+                "poker": [FIPS_POKER_BOUNDS.0, FIPS_POKER_BOUNDS.1],
+                "runs": FIPS_RUNS_BOUNDS.iter().map(|&(lo, hi)| [lo, hi]).collect::<Vec<_>>(),
+                "long_run_max": FIPS_LONG_RUN - 1,
+            },
+            "block_results": results.iter().map(|r| serde_json::json!({
+                "monobit": {"ones": r.ones, "passed": r.monobit_passed()},
+                "poker": {"statistic": r.poker, "passed": r.poker_passed()},
+                "runs": {"zeros": r.runs[0], "ones": r.runs[1], "passed": r.runs_passed()},
+                "long_run": {"longest": r.longest_run, "passed": r.long_run_passed()},
+            })).collect::<Vec<_>>(),
+        });
+        
+        let mut lock = self.state.lock();
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!(
+            "[{}] BATTERY: {} {} over {} blocks (monobit {}/{}, poker {}/{}, runs {}/{}, long-run {}/{})",
+            ts, source, if passed { "PASS" } else { "FAIL" }, blocks,
+            monobit, blocks, poker, blocks, runs, blocks, long_run, blocks
+        );
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        
+        Ok(report.to_string())
+    }
+
+    /// Writes the next `sample_count` raw samples from `source` (before debiasing, conditioning
+    /// or health gating) to `path` in the 8-bit binary format NIST's SP 800-90B tools read.
+    /// Replaces any capture already running for the source.
+    fn start_raw_capture(&self, source: String, path: String, sample_count: usize) -> PyResult<()> {
+        if !(1..=100_000_000).contains(&sample_count) {
+            return Err(pyo3::exceptions::PyValueError::new_err("sample_count must be 1-100000000"));
+        }
+        let file = fs::File::create(&path)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(format!("{}: {}", path, e)))?;
+        
+        let source = source.to_uppercase();
+        let mut lock = self.state.lock();
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] CAPTURE: {} -> {} ({} samples)", ts, source, path, sample_count);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        lock.raw_captures.insert(source, RawCapture {
+            writer: std::io::BufWriter::new(file),
+            path,
+            requested: sample_count,
+            written: 0,
+        });
+        Ok(())
+    }
+
+    /// Ends a running capture early, keeping what was written; returns the sample count
+    fn stop_raw_capture(&self, source: String) -> PyResult<usize> {
+        use std::io::Write;
+        let source = source.to_uppercase();
+        let mut lock = self.state.lock();
+        let Some(mut capture) = lock.raw_captures.remove(&source) else {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("no capture running for {}", source)));
+        };
+        capture.writer.flush()
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(format!("{}: {}", capture.path, e)))?;
+        Ok(capture.written)
+    }
+
+    /// SP 800-90B permutation testing over the last `kilobytes` of raw data from `source`. The
+    /// verdict selects the source's estimator track (IID: MCV only). Returns a JSON report.
+    /// IID data settles after a few dozen shuffles; non-IID data runs all 10,000 (seconds).
+    #[pyo3(signature = (source, kilobytes=4))]
+    fn run_iid_test(&self, py: Python<'_>, source: String, kilobytes: usize) -> PyResult<String> {
+        if !(1..=BATTERY_HISTORY_BYTES / 1024).contains(&kilobytes) {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                format!("kilobytes must be 1-{}", BATTERY_HISTORY_BYTES / 1024)));
+        }
+        let source = source.to_uppercase();
+        let raw: Vec<u8> = {
+            let lock = self.state.lock();
+            let Some(health) = lock.source_health.get(&source) else {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!("no data from {}", source)));
+            };
+            if health.history.len() < kilobytes * 1024 {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "{} has {} bytes buffered, need {}", source, health.history.len(), kilobytes * 1024)));
+            }
+            health.history.iter().skip(health.history.len() - kilobytes * 1024).copied().collect()
+        };
+        
+        // Thousands of permutations with a compression statistic each: seconds of work
+        let results = py.allow_threads(|| iid_permutation_test(&raw));
+        let iid = results.iter().all(|r| r.4);
+        
+        let report = serde_json::json!({
+            "source": source,
+            "samples": raw.len(),
+            "iid": iid,
+            "estimator_track": if iid { "IID" } else { "NON_IID" },
+            "statistics": results.iter().map(|(name, original, greater, equal, passed)| serde_json::json!({
+                "test": name,
+                "statistic": original,
+                "permutations_greater": greater,
+                "permutations_equal": equal,
+                "passed": passed,
+            })).collect::<Vec<_>>(),
+        });
+        
+        let mut lock = self.state.lock();
+        lock.source_metrics.entry(source.clone()).or_default().iid = Some(iid);
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!(
+            "[{}] IID: {} {} ({}/{} statistics passed)",
+            ts, source, if iid { "IID" } else { "NON-IID" },
+            results.iter().filter(|r| r.4).count(), results.len()
+        );
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        
+        Ok(report.to_string())
+    }
+
+    /// Mints a KEM keypair (`kem`: "kyber512", "kyber768" or "kyber1024", i.e. ML-KEM-512/768/1024)
+    /// with a commitment to the slow pool state, signs it (see vault_bundle_message), and saves
+    /// the bundle to keys/
+    #[pyo3(signature = (requester=None, kem="kyber768"))]
+    fn mint_pqc_bundle(&self, requester: Option<String>, kem: &str) -> PyResult<String> {
+        let requester = requester.unwrap_or_else(|| "LOCAL".to_string());
+        let kem = KemKind::from_name(kem)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("Unknown KEM: {}", kem)))?;
+        let mut lock = self.state.lock();
+        
+        if !lock.pqc_active {
+            return Ok("Error: PQC Engine Offline".to_string());
+        }
+        if lock.slow_pool.reseeds == 0 {
+            return Ok("Error: Slow pool not seeded yet".to_string());
+        }
+        check_output_allowed(&lock)?;
+        if let Err(e) = lock.vault.check_writable() {
+            return Ok(format!("Error: {}", e));
+        }
+        
+        let (kyber_pk, kyber_sk) = kem.keypair();
+        
+        let mut context_hasher = Sha3_256::new();
+        context_hasher.update(lock.slow_pool.state);
+        context_hasher.update(&kyber_pk);
+        let context = context_hasher.finalize();
+        lock.slow_pool.ratchet();
+        
+        let timestamp = get_timestamp();
+
+        let mut bundle = serde_json::json!({
+            "type": "COBRA_PQC_BUNDLE",
+            "requester": requester,
+            "timestamp": timestamp,
+            "accumulated_true_bits": lock.ledger.available_bits,
+            "conditioning": lock.fortuna.conditioner.extractor.name(),
+            "sp800_90b_h_out_bits": lock.fortuna.last_claim_bits,
+            "slow_pool_reseeds": lock.slow_pool.reseeds,
+            "kem": kem.name(),
+            "kyber_pk": hex::encode(&kyber_pk),
+            "kyber_sk": hex::encode(&kyber_sk),
+            "pool_commitment": hex::encode(context),
+        });
+        sign_vault_bundle(&lock, &mut bundle).map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        lock.ledger.debit(LEDGER_MINT_DEBIT_BITS);
+
+        let filename = format!("keys/key_{}_{}.json", timestamp, hex::encode(&kyber_pk[0..4]));
+        if let Err(e) = save_vault_bundle(&mut lock, &filename, bundle, &["kyber_sk"]) {
+            return Ok(format!("Error: {}", e));
+        }
+
+        Ok(format!("Generated {}", filename))
+    }
+
+    /// Hybrid bundle: an X25519 + ML-KEM (`kem`) key-exchange pair and an Ed25519 + Falcon
+    /// (`signer`) signing pair in one file, all four public keys bound under a single identity
+    /// signature. The classical secrets are drawn from the KEYS consumer.
+    #[pyo3(signature = (requester=None, kem="kyber768", signer="falcon512"))]
+    fn mint_hybrid_bundle(&self, requester: Option<String>, kem: &str, signer: &str) -> PyResult<String> {
+        let requester = requester.unwrap_or_else(|| "LOCAL".to_string());
+        let kem = KemKind::from_name(kem)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("Unknown KEM: {}", kem)))?;
+        let signer = FalconKind::from_name(signer)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("Unknown signer: {}", signer)))?;
+        let mut lock = self.state.lock();
+        
+        if !lock.pqc_active {
+            return Ok("Error: PQC Engine Offline".to_string());
+        }
+        if lock.slow_pool.reseeds == 0 {
+            return Ok("Error: Slow pool not seeded yet".to_string());
+        }
+        check_output_allowed(&lock)?;
+        if let Err(e) = lock.vault.check_writable() {
+            return Ok(format!("Error: {}", e));
+        }
+        
+        let mut seeds = consumer_generate(&mut lock, "KEYS", 64)
+            .map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        let mut x25519_seed = [0u8; 32];
+        let mut ed25519_seed = [0u8; 32];
+        x25519_seed.copy_from_slice(&seeds[..32]);
+        ed25519_seed.copy_from_slice(&seeds[32..]);
+        seeds.zeroize();
+        let x25519_sk = X25519Secret::from(x25519_seed);
+        let x25519_pk = X25519Public::from(&x25519_sk);
+        let ed25519_sk = Ed25519SigningKey::from_bytes(&ed25519_seed);
+        let ed25519_pk = ed25519_sk.verifying_key();
+        x25519_seed.zeroize();
+        ed25519_seed.zeroize();
+        
+        let (kem_pk, kem_sk) = kem.keypair();
+        let (sig_pk, sig_sk) = signer.keypair();
+        
+        let mut context_hasher = Sha3_256::new();
+        context_hasher.update(lock.slow_pool.state);
+        context_hasher.update(b"COBRA_HYBRID_BUNDLE");
+        context_hasher.update(x25519_pk.as_bytes());
+        context_hasher.update(&kem_pk);
+        context_hasher.update(ed25519_pk.as_bytes());
+        context_hasher.update(&sig_pk);
+        let context = context_hasher.finalize();
+        lock.slow_pool.ratchet();
+        
+        let timestamp = get_timestamp();
+        
+        let mut bundle = serde_json::json!({
+            "type": "COBRA_HYBRID_BUNDLE",
+            "requester": requester,
+            "timestamp": timestamp,
+            "accumulated_true_bits": lock.ledger.available_bits,
+            "conditioning": lock.fortuna.conditioner.extractor.name(),
+            "sp800_90b_h_out_bits": lock.fortuna.last_claim_bits,
+            "slow_pool_reseeds": lock.slow_pool.reseeds,
+            "kex": format!("x25519+{}", kem.name()),
+            "x25519_pk": hex::encode(x25519_pk.as_bytes()),
+            "x25519_sk": hex::encode(x25519_sk.as_bytes()),
+            "kem": kem.name(),
+            "kyber_pk": hex::encode(&kem_pk),
+            "kyber_sk": hex::encode(&kem_sk),
+            "sig": format!("ed25519+{}", signer.name()),
+            "ed25519_pk": hex::encode(ed25519_pk.as_bytes()),
+            "ed25519_sk": hex::encode(ed25519_sk.to_bytes()),
+            "signer": signer.name(),
+            "signer_pk": hex::encode(&sig_pk),
+            "signer_sk": hex::encode(&sig_sk),
+            "pool_commitment": hex::encode(context),
+        });
+        sign_vault_bundle(&lock, &mut bundle).map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        lock.ledger.debit(LEDGER_MINT_DEBIT_BITS);
+        
+        let filename = format!("keys/hybrid_{}_{}.json", timestamp, hex::encode(&x25519_pk.as_bytes()[0..4]));
+        if let Err(e) = save_vault_bundle(&mut lock, &filename, bundle, &["x25519_sk", "kyber_sk", "ed25519_sk", "signer_sk"]) {
+            return Ok(format!("Error: {}", e));
+        }
+        
+        Ok(format!("Generated {}", filename))
+    }
+
+    /// Classical keypair for teams not yet on PQC: `algorithm` "ed25519" or "p256" (ECDSA
+    /// P-256, SEC1 uncompressed public key). The secret comes from the KEYS consumer and the
+    /// bundle is signed and saved like the PQC ones.
+    #[pyo3(signature = (algorithm="ed25519", requester=None))]
+    fn mint_classical_keypair(&self, algorithm: &str, requester: Option<String>) -> PyResult<String> {
+        let requester = requester.unwrap_or_else(|| "LOCAL".to_string());
+        let algorithm = match algorithm.to_lowercase().replace(['-', '_'], "").as_str() {
+            "ed25519" => "ed25519",
+            "p256" | "secp256r1" | "prime256v1" | "ecdsap256" => "p256",
+            _ => return Err(pyo3::exceptions::PyValueError::new_err(format!("Unknown classical algorithm: {}", algorithm))),
+        };
+        let mut lock = self.state.lock();
+        
+        if !lock.pqc_active {
+            return Ok("Error: PQC Engine Offline".to_string());
+        }
+        if lock.slow_pool.reseeds == 0 {
+            return Ok("Error: Slow pool not seeded yet".to_string());
+        }
+        check_output_allowed(&lock)?;
+        if let Err(e) = lock.vault.check_writable() {
+            return Ok(format!("Error: {}", e));
+        }
+        
+        let (public_key, secret_key) = loop {
+            let mut seed = consumer_generate(&mut lock, "KEYS", 32)
+                .map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+            let keypair = if algorithm == "ed25519" {
+                let mut bytes = [0u8; 32];
+                bytes.copy_from_slice(&seed);
+                let sk = Ed25519SigningKey::from_bytes(&bytes);
+                bytes.zeroize();
+                Some((sk.verifying_key().as_bytes().to_vec(), sk.to_bytes().to_vec()))
+            } else {
+                // Scalars >= n are rejected and redrawn (probability ~2^-32)
+                P256SecretKey::from_slice(&seed).ok()
+                    .map(|sk| (sk.public_key().to_sec1_bytes().to_vec(), sk.to_bytes().to_vec()))
+            };
+            seed.zeroize();
+            if let Some(keypair) = keypair {
+                break keypair;
             }
         };
         
-        // Set non-blocking for graceful shutdown
-        listener.set_nonblocking(true).ok();
+        let mut context_hasher = Sha3_256::new();
+        context_hasher.update(lock.slow_pool.state);
+        context_hasher.update(&public_key);
+        let context = context_hasher.finalize();
+        lock.slow_pool.ratchet();
+        
+        let timestamp = get_timestamp();
+        
+        let mut bundle = serde_json::json!({
+            "type": "COBRA_CLASSICAL_BUNDLE",
+            "requester": requester,
+            "timestamp": timestamp,
+            "accumulated_true_bits": lock.ledger.available_bits,
+            "conditioning": lock.fortuna.conditioner.extractor.name(),
+            "sp800_90b_h_out_bits": lock.fortuna.last_claim_bits,
+            "slow_pool_reseeds": lock.slow_pool.reseeds,
+            "algorithm": algorithm,
+            "public_key": hex::encode(&public_key),
+            "secret_key": hex::encode(&secret_key),
+            "pool_commitment": hex::encode(context),
+        });
+        sign_vault_bundle(&lock, &mut bundle).map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        lock.ledger.debit(LEDGER_MINT_DEBIT_BITS);
+        
+        let filename = format!("keys/classical_{}_{}_{}.json", algorithm, timestamp, hex::encode(&public_key[0..4]));
+        if let Err(e) = save_vault_bundle(&mut lock, &filename, bundle, &["secret_key"]) {
+            return Ok(format!("Error: {}", e));
+        }
+        
+        Ok(format!("Generated {}", filename))
+    }
+
+    /// `n` (1..=4096) random bytes from the TOKEN consumer, signed with this engine's Falcon
+    /// identity over (bytes, timestamp, sequence, SHA3-256 commitment to the pool state), so
+    /// downstream systems can check where the randomness came from. Returns the token as JSON.
+    fn mint_entropy_token(&self, n: usize) -> PyResult<String> {
+        if !(1..=4096).contains(&n) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("n must be 1..=4096, got {}", n)));
+        }
+        let mut lock = self.state.lock();
+        if !lock.pqc_active {
+            return Err(pyo3::exceptions::PyRuntimeError::new_err("PQC engine offline"));
+        }
+        check_output_allowed(&lock)?;
+        
+        let mut commit_hasher = Sha3_256::new();
+        commit_hasher.update(b"CHAOS_MAGNET_POOL_COMMIT");
+        commit_hasher.update(lock.accumulator.pool);
+        let commitment = commit_hasher.finalize();
+        let mut bytes = consumer_generate(&mut lock, "TOKEN", n)
+            .map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        lock.entropy_tokens_issued += 1;
+        let sequence = lock.entropy_tokens_issued;
+        let timestamp = get_timestamp();
+        
+        let signature = lock.falcon_kind.sign(&lock.falcon_sk, &entropy_token_message(sequence, timestamp, &commitment, &bytes))
+            .map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        
+        let token = serde_json::json!({
+            "type": "COBRA_ENTROPY_TOKEN",
+            "bytes": hex::encode(&bytes),
+            "timestamp": timestamp,
+            "sequence": sequence,
+            "pool_commitment": hex::encode(commitment),
+            "falcon_sig": hex::encode(&signature),
+            "falcon_signer": lock.falcon_kind.name(),
+            "falcon_signer_pk": hex::encode(&lock.falcon_pk),
+        });
+        bytes.zeroize();
+        Ok(token.to_string())
+    }
+
+    /// `n` (1..=1024) independent keys of `length` bytes (16..=1024) from one 32-byte extraction
+    /// from the KEYS consumer, expanded with HKDF-SHA256 using info "<context>/<i>" per key. One
+    /// trip through the engine lock instead of `n`.
+    #[pyo3(signature = (n, length=32, context="".to_string()))]
+    fn derive_keys<'py>(&self, py: Python<'py>, n: usize, length: usize, context: String) -> PyResult<Vec<Bound<'py, pyo3::types::PyBytes>>> {
+        if !(1..=1024).contains(&n) || !(16..=1024).contains(&length) {
+            return Err(pyo3::exceptions::PyValueError::new_err("n must be 1..=1024 and length 16..=1024"));
+        }
+        let mut ikm = {
+            let mut lock = self.state.lock();
+            check_output_allowed(&lock)?;
+            consumer_generate(&mut lock, "KEYS", 32).map_err(pyo3::exceptions::PyRuntimeError::new_err)?
+        };
+        
+        let keys = (0..n)
+            .map(|i| {
+                let mut key = hkdf_sha256(b"CHAOS_MAGNET_DERIVE_KEYS", &ikm, format!("{}/{}", context, i).as_bytes(), length);
+                let bytes = pyo3::types::PyBytes::new(py, &key);
+                key.zeroize();
+                bytes
+            })
+            .collect();
+        ikm.zeroize();
+        Ok(keys)
+    }
+
+    /// A `bits`-bit seed (64..=4096, whole bytes) for an external PRNG, from the SEED consumer,
+    /// with an attestation record of its provenance: label, time, each source's share of credited
+    /// entropy, the ledger balance and every source's health state. The record's compact JSON
+    /// (keys sorted, as emitted) is signed with the engine's Falcon identity.
+    #[pyo3(signature = (bits=256, label="".to_string()))]
+    fn export_seed(&self, bits: usize, label: String) -> PyResult<String> {
+        if !(64..=4096).contains(&bits) || bits % 8 != 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err("bits must be 64..=4096 and a multiple of 8"));
+        }
+        let mut lock = self.state.lock();
+        if !lock.pqc_active {
+            return Err(pyo3::exceptions::PyRuntimeError::new_err("PQC engine offline"));
+        }
+        check_output_allowed(&lock)?;
+        
+        let available_bits = lock.ledger.available_bits;
+        let mut seed = consumer_generate(&mut lock, "SEED", bits / 8)
+            .map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        let total_credited: f64 = lock.ledger.credited_by_source.values().sum();
+        let source_mix: HashMap<&String, f64> = lock.ledger.credited_by_source.iter()
+            .map(|(name, credited)| (name, if total_credited > 0.0 { credited / total_credited } else { 0.0 }))
+            .collect();
+        let health: HashMap<&String, &str> = lock.source_health.iter()
+            .map(|(name, h)| (name, h.state.name()))
+            .collect();
         
-        while running.load(Ordering::Relaxed) {
-            match listener.accept() {
-                Ok((mut stream, addr)) => {
-                    // Check if P2P is still active
-                    if !state.lock().p2p_config.active {
-                        continue;
-                    }
-                    
-                    let tx_clone = tx.clone();
-                    let state_clone = state.clone();
-                    
-                    thread::spawn(move || {
-                        let mut buffer = String::new();
-                        if stream.read_to_string(&mut buffer).is_ok() {
-                            // Parse HTTP request (simple POST body extraction)
-                            if let Some(body_start) = buffer.find("\r\n\r\n") {
-                                let body = &buffer[body_start + 4..];
-                                
-                                // Parse JSON payload
-                                if let Ok(json) = serde_json::from_str::<serde_json::Value>(body) {
-                                    if let Some(payload_hex) = json["payload_hex"].as_str() {
-                                        if let Ok(entropy_bytes) = hex::decode(payload_hex) {
-                                            // Health check
-                                            if passes_health_checks(&entropy_bytes) {
-                                                // Add to processing queue
-                                                let source = format!("P2P_{}", addr.ip());
-                                                let _ = tx_clone.try_send((source, entropy_bytes));
-                                                
-                                                // Update P2P stats
-                                                let mut lock = state_clone.lock();
-                                                lock.p2p_config.received_count += 1;
-                                                
-                                                // HTTP response
-                                                let response = "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nOK";
-                                                let _ = stream.write_all(response.as_bytes());
-                                                return;
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        
-                        // Error response
-                        let response = "HTTP/1.1 400 Bad Request\r\nContent-Length: 5\r\n\r\nERROR";
-                        let _ = stream.write_all(response.as_bytes());
-                    });
-                },
-                Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                    // No connection, sleep briefly
-                    thread::sleep(Duration::from_millis(100));
-                },
-                Err(_) => {
-                    // Other error, continue
-                    thread::sleep(Duration::from_millis(100));
+        let record = serde_json::json!({
+            "type": "COBRA_SEED_ATTESTATION",
+            "label": label,
+            "bits": bits,
+            "seed_sha256": hex::encode(Sha256::digest(&seed)),
+            "timestamp": get_timestamp(),
+            "pool_reseeds": lock.fortuna.reseed_count,
+            "credited_entropy_bits": available_bits,
+            "total_credited_bits": lock.ledger.total_credited_bits,
+            "source_mix": source_mix,
+            "source_health": health,
+            "research_mode": lock.research_mode,
+        });
+        let signature = lock.falcon_kind.sign(&lock.falcon_sk, record.to_string().as_bytes())
+            .map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] SEED: Exported {}-bit seed{}", ts, bits,
+            if label.is_empty() { String::new() } else { format!(" for {}", label) });
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        
+        let export = serde_json::json!({
+            "seed": hex::encode(&seed),
+            "attestation": record,
+            "falcon_sig": hex::encode(&signature),
+            "falcon_signer": lock.falcon_kind.name(),
+            "falcon_signer_pk": hex::encode(&lock.falcon_pk),
+        });
+        seed.zeroize();
+        Ok(export.to_string())
+    }
+
+    /// Writes `bytes` (32..=1048576) of BLOCK-consumer output to `path` as a sequence-numbered,
+    /// Falcon-signed entropy block for carrying to an air-gapped machine; returns its sequence
+    /// number. The verifier needs this engine's signer key (`falcon_signer_pk` in bundles).
+    fn export_entropy_block(&self, bytes: usize, path: String) -> PyResult<u64> {
+        if !(32..=1 << 20).contains(&bytes) {
+            return Err(pyo3::exceptions::PyValueError::new_err("bytes must be 32..=1048576"));
+        }
+        let mut lock = self.state.lock();
+        if !lock.pqc_active {
+            return Err(pyo3::exceptions::PyRuntimeError::new_err("PQC engine offline"));
+        }
+        check_output_allowed(&lock)?;
+        let mut data = consumer_generate(&mut lock, "BLOCK", bytes)
+            .map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        lock.entropy_blocks_exported += 1;
+        let sequence = lock.entropy_blocks_exported;
+        
+        let mut block = Vec::with_capacity(ENTROPY_BLOCK_HEADER_LEN + bytes + lock.falcon_kind.signature_bytes());
+        block.extend_from_slice(ENTROPY_BLOCK_MAGIC);
+        block.extend_from_slice(&sequence.to_be_bytes());
+        block.extend_from_slice(&get_timestamp().to_be_bytes());
+        block.extend_from_slice(&(bytes as u32).to_be_bytes());
+        block.extend_from_slice(&data);
+        data.zeroize();
+        let signature = lock.falcon_kind.sign(&lock.falcon_sk, &block)
+            .map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        block.extend_from_slice(&signature);
+        
+        let result = fs::write(&path, &block);
+        block.zeroize();
+        result.map_err(|e| pyo3::exceptions::PyIOError::new_err(format!("{}: {}", path, e)))?;
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] BLOCK: #{} ({} bytes) -> {}", ts, sequence, bytes, path);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(sequence)
+    }
+
+    /// Verifies an export_entropy_block file against `signer_pk` (hex) and returns (data,
+    /// sequence, timestamp). With `mix`, the data is also fed to this engine's pool as source
+    /// IMPORT, going through health checks and credit like any other source; a block whose
+    /// sequence is not above the last one imported from that signer (this session) is rejected
+    /// as a replay.
+    #[pyo3(signature = (path, signer_pk, mix=true))]
+    fn verify_entropy_block<'py>(&self, py: Python<'py>, path: String, signer_pk: &str, mix: bool) -> PyResult<(Bound<'py, pyo3::types::PyBytes>, u64, u64)> {
+        let signer = hex::decode(signer_pk)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("signer_pk: {}", e)))?;
+        let (mut data, sequence, timestamp) = read_entropy_block(&path, &signer)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("{}: {}", path, e)))?;
+        
+        if mix {
+            let mut lock = self.state.lock();
+            let signer_hex = hex::encode(&signer);
+            if let Some(&last) = lock.blocks_imported.get(&signer_hex) {
+                if sequence <= last {
+                    data.zeroize();
+                    return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                        "{}: block #{} already imported (last from this signer: #{})", path, sequence, last
+                    )));
                 }
             }
+            if self.tx_entropy.try_send(("IMPORT".to_string(), data.clone())).is_err() {
+                data.zeroize();
+                return Err(pyo3::exceptions::PyRuntimeError::new_err("Entropy channel full, block not imported; retry"));
+            }
+            lock.blocks_imported.insert(signer_hex, sequence);
+            let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+            let msg = format!("[{}] BLOCK: Imported #{} ({} bytes) from {}", ts, sequence, data.len(), path);
+            if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+            lock.logs.push_back(msg);
         }
-    });
-}
-
-// ═══════════════════════════════════════════════════════════════════════════
-// MIXER THREAD (WITH P2P SUPPORT)
-// ═══════════════════════════════════════════════════════════════════════════
+        let bytes = pyo3::types::PyBytes::new(py, &data);
+        data.zeroize();
+        Ok((bytes, sequence, timestamp))
+    }
 
-fn start_mixer_thread(
-    rx: Receiver<(String, Vec<u8>)>,
-    state: Arc<Mutex<SharedState>>,
-    running: Arc<AtomicBool>
-) {
-    thread::spawn(move || {
-        let client = reqwest::blocking::Client::builder()
-            .timeout(Duration::from_millis(500))
-            .build()
-            .unwrap_or_else(|_| reqwest::blocking::Client::new());
+    /// Checks a mint_entropy_token token's signature against its `falcon_signer_pk`, or against
+    /// `signer_pk` (hex) when given, which is what a verifier pinning an identity should pass
+    #[staticmethod]
+    #[pyo3(signature = (token, signer_pk=None))]
+    fn verify_entropy_token(token: &str, signer_pk: Option<&str>) -> PyResult<bool> {
+        let token: serde_json::Value = serde_json::from_str(token)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("token is not JSON: {}", e)))?;
+        let hex_field = |name: &str| -> PyResult<Vec<u8>> {
+            token[name].as_str()
+                .and_then(|s| hex::decode(s).ok())
+                .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("token field {} missing or not hex", name)))
+        };
+        let (Some(timestamp), Some(sequence)) = (token["timestamp"].as_u64(), token["sequence"].as_u64()) else {
+            return Err(pyo3::exceptions::PyValueError::new_err("token timestamp/sequence missing"));
+        };
+        let signer = match signer_pk {
+            Some(pk) => hex::decode(pk).map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("signer_pk: {}", e)))?,
+            None => hex_field("falcon_signer_pk")?,
+        };
         
-        let mut last_net_time = 0u64;
+        let message = entropy_token_message(sequence, timestamp, &hex_field("pool_commitment")?, &hex_field("bytes")?);
+        let Some(kind) = FalconKind::from_public_key(&signer) else {
+            return Ok(false);
+        };
+        Ok(kind.verify(&signer, &message, &hex_field("falcon_sig")?))
+    }
+
+    /// Local randomness beacon: a Falcon-signed 512-bit pulse every `interval_secs`, each chaining
+    /// the previous pulse's output value. With `http_port`, pulses are also served read-only at
+    /// /beacon/last, /beacon/pulse/<index> and /beacon/time/<unix secs>. Calling it on a running
+    /// beacon stops the old threads first and continues the same chain with the new settings.
+    #[pyo3(signature = (interval_secs=60, http_port=None))]
+    fn start_beacon(&self, py: Python<'_>, interval_secs: u64, http_port: Option<u16>) -> PyResult<()> {
+        if !(1..=86_400).contains(&interval_secs) {
+            return Err(pyo3::exceptions::PyValueError::new_err("interval_secs must be 1..=86400"));
+        }
         
-        while running.load(Ordering::Relaxed) {
-            let (source, data) = match rx.recv_timeout(Duration::from_secs(1)) {
-                Ok(d) => d,
-                Err(_) => continue,
-            };
-            
-            // Measure RAW entropy
-            let raw_shannon = shannon_entropy(&data);
-            let raw_min = min_entropy(&data);
-            let entropy_contribution_bits = (raw_min * data.len() as f64).min(data.len() as f64 * 8.0);
-            
-            let mut lock = state.lock();
-            
-            // Feed to extraction pool
-            let extracted_opt = lock.extraction_pool.add_raw_bytes(&data);
-            
-            // Update source metrics
-            let metrics = lock.source_metrics.entry(source.clone()).or_default();
-            metrics.samples += 1;
-            metrics.raw_shannon = raw_shannon;
-            metrics.min_entropy = raw_min;
-            metrics.total_bits_contributed += entropy_contribution_bits;
-            metrics.avg_raw_entropy = if metrics.samples == 1 {
-                raw_shannon
-            } else {
-                metrics.avg_raw_entropy * 0.95 + raw_shannon * 0.05
-            };
-            
-            lock.estimated_true_entropy_bits += entropy_contribution_bits;
-            
-            // Update history
-            if lock.history_raw_entropy.len() >= HISTORY_LEN {
-                lock.history_raw_entropy.pop_front();
+        // The old listener must release its port before the new one binds it
+        let old = self.state.lock().beacon.take();
+        let (pulses, next_index, last_output) = match old {
+            Some(mut old) => {
+                old.stop.store(true, Ordering::Relaxed);
+                py.allow_threads(|| old.threads.drain(..).for_each(|t| { let _ = t.join(); }));
+                (old.pulses, old.next_index, old.last_output)
             }
-            lock.history_raw_entropy.push_back(raw_min);
-            
-            // Process extracted entropy
-            if let Some(extracted) = extracted_opt {
-                let extracted_shannon = shannon_entropy(&extracted);
-                
-                if lock.history_whitened_entropy.len() >= HISTORY_LEN {
-                    lock.history_whitened_entropy.pop_front();
-                }
-                lock.history_whitened_entropy.push_back(extracted_shannon);
-                
-                // Mix into pool
-                let mut pool_hasher = Sha3_256::new();
-                pool_hasher.update(&lock.pool);
-                pool_hasher.update(source.as_bytes());
-                pool_hasher.update(&extracted);
-                lock.pool = pool_hasher.finalize().into();
-                
-                // Update display pool
-                for &b in extracted.iter() {
-                    if lock.display_pool.len() >= POOL_SIZE {
-                        lock.display_pool.pop_front();
-                    }
-                    lock.display_pool.push_back(b);
-                }
-                
-                lock.total_bytes += extracted.len();
-                lock.sequence_id += 1;
-                
-                // Log extraction
-                let ts = chrono::Local::now().format("%H:%M:%S").to_string();
-                let msg = format!(
-                    "[{}] EXTRACT #{} | 200→32 bytes | Quality:{:.2} | Source:{}",
-                    ts, lock.extraction_pool.extractions_count, extracted_shannon, source
-                );
-                if lock.logs.len() >= 20 { lock.logs.pop_front(); }
-                lock.logs.push_back(msg);
-            
-                // AUTO-MINT (every 10 extractions if quality is good)
-                if lock.extraction_pool.extractions_count % 10 == 0
-                    && raw_min > AUTO_MINT_THRESHOLD
-                    && lock.pqc_active 
-                {
-                    let ts = chrono::Local::now().format("%H:%M:%S").to_string();
-                    let msg = format!(
-                        "[{}] AUTO-MINT: Quality={:.2}, Minting keypair...", 
-                        ts, raw_min
-                    );
-                    if lock.logs.len() >= 20 { lock.logs.pop_front(); }
-                    lock.logs.push_back(msg);
-                    
-                    let (kyber_pk, kyber_sk) = kyber512::keypair();
-                    
-                    let mut context_hasher = Sha3_256::new();
-                    context_hasher.update(&lock.pool);
-                    context_hasher.update(kyber_pk.as_bytes());
-                    let context = context_hasher.finalize();
-                    
-                    if let Ok(falcon_secret) = falcon512::SecretKey::from_bytes(&lock.falcon_sk) {
-                        let signature = falcon512::detached_sign(&context, &falcon_secret);
-                        let timestamp = get_timestamp();
-                        
-                        let bundle = serde_json::json!({
-                            "type": "COBRA_PQC_BUNDLE",
-                            "requester": "RUST_AUTO",
-                            "timestamp": timestamp,
-                            "raw_min_entropy": raw_min,
-                            "accumulated_true_bits": lock.estimated_true_entropy_bits,
-                            "kyber_pk": hex::encode(kyber_pk.as_bytes()),
-                            "kyber_sk": hex::encode(kyber_sk.as_bytes()),
-                            "falcon_sig": hex::encode(signature.as_bytes()),
-                            "falcon_signer_pk": hex::encode(&lock.falcon_pk),
+            None => (VecDeque::with_capacity(BEACON_MAX_PULSES), 0, [0u8; 64]),
+        };
+        
+        let stop = Arc::new(AtomicBool::new(false));
+        let mut threads = Vec::with_capacity(2);
+        if let Some(port) = http_port {
+            match start_beacon_http(port, stop.clone(), self.state.clone(), self.running.clone()) {
+                Ok(handle) => threads.push(handle),
+                Err(e) => {
+                    // Keep the chain so a retry on another port continues it
+                    if next_index > 0 {
+                        let mut lock = self.state.lock();
+                        lock.beacon = Some(Beacon {
+                            interval_secs,
+                            http_port: None,
+                            pulses,
+                            next_index,
+                            last_output,
+                            stop: Arc::new(AtomicBool::new(true)),
+                            threads: Vec::new(),
                         });
-                        
-                        let filename = format!("keys/key_{}_{}.json", timestamp, hex::encode(&kyber_pk.as_bytes()[0..4]));
-                        if let Ok(file) = fs::File::create(&filename) {
-                            let _ = serde_json::to_writer_pretty(file, &bundle);
-                            
-                            let ts = chrono::Local::now().format("%H:%M:%S").to_string();
-                            let msg = format!("[{}] VAULT: Saved {}", ts, filename);
-                            if lock.logs.len() >= 20 { lock.logs.pop_front(); }
-                            lock.logs.push_back(msg);
-                        }
-                    }
-                }
-                
-                // Network uplink
-                let now = get_timestamp();
-                if lock.net_mode && now > last_net_time {
-                    last_net_time = now;
-                    
-                    let target = lock.uplink_url.clone();
-                    let seq = lock.sequence_id;
-                    let source_clone = source.clone();
-                    let c = client.clone();
-                    
-                    let payload_hex = hex::encode(&extracted[..]);
-                    let payload_size = extracted.len();
-                    
-                    let digest = {
-                        let mut hasher = Sha3_256::new();
-                        hasher.update(&data);
-                        hex::encode(hasher.finalize())
-                    };
-                
-                    let ts_epoch = SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .unwrap()
-                        .as_secs_f64();
-                    
-                    let raw_min_copy = raw_min;
-                    let raw_shannon_copy = raw_shannon;
-                    
-                    thread::spawn(move || {
-                        let _ = c.post(&target)
-                            .json(&serde_json::json!({
-                                "node": "chaos_magnet",
-                                "seq": seq,
-                                "timestamp": get_timestamp(),
-                                "ts_epoch": ts_epoch,
-                                "entropy_estimate_raw_shannon": raw_shannon_copy,
-                                "entropy_estimate_raw_min": raw_min_copy,
-                                "health": "OK",
-                                "source": source_clone,
-                                "metrics": {"size": payload_size},
-                                "payload_hex": payload_hex,
-                                "digest": digest
-                            }))
-                            .send();
-                    });
-                }
-                
-                // P2P distribution (send to all peers)
-                if lock.p2p_config.active && !lock.p2p_config.peers.is_empty() {
-                    let peers = lock.p2p_config.peers.clone();
-                    let payload_hex = hex::encode(&extracted[..]);
-                    let seq = lock.sequence_id;
-                    let c = client.clone();
-                    
-                    thread::spawn(move || {
-                        for peer in peers {
-                            let url = format!("http://{}/ingest", peer);
-                            let _ = c.post(&url)
-                                .json(&serde_json::json!({
-                                    "node": "chaos_magnet_p2p",
-                                    "seq": seq,
-                                    "timestamp": get_timestamp(),
-                                    "payload_hex": payload_hex,
-                                }))
-                                .send();
-                        }
-                    });
+                    }
+                    return Err(pyo3::exceptions::PyIOError::new_err(format!("port {}: {}", port, e)));
                 }
             }
         }
-    });
-}
+        threads.push(start_beacon_thread(interval_secs, stop.clone(), self.state.clone(), self.running.clone()));
+        
+        let mut lock = self.state.lock();
+        lock.beacon = Some(Beacon {
+            interval_secs,
+            http_port,
+            pulses,
+            next_index,
+            last_output,
+            stop,
+            threads,
+        });
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = match http_port {
+            Some(port) => format!("[{}] BEACON: Pulsing every {}s, HTTP on port {}", ts, interval_secs, port),
+            None => format!("[{}] BEACON: Pulsing every {}s", ts, interval_secs),
+        };
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        Ok(())
+    }
 
-// ═══════════════════════════════════════════════════════════════════════════
-// PYTHON CLASS
-// ═══════════════════════════════════════════════════════════════════════════
+    fn stop_beacon(&self, py: Python<'_>) {
+        let mut lock = self.state.lock();
+        if let Some(mut beacon) = lock.beacon.take() {
+            beacon.stop.store(true, Ordering::Relaxed);
+            let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+            let msg = format!("[{}] BEACON: Stopped after {} pulses", ts, beacon.next_index);
+            if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+            lock.logs.push_back(msg);
+            drop(lock);
+            py.allow_threads(|| beacon.threads.drain(..).for_each(|t| { let _ = t.join(); }));
+        }
+    }
 
-#[pymethods]
-impl ChaosEngine {
-    #[new]
-    fn new() -> Self {
-        let (tx, rx) = bounded(1000);
-        let _ = fs::create_dir_all("keys");
+    /// Beacon pulse `index` as JSON (the latest when None); None if it was never emitted or has
+    /// aged out
+    #[pyo3(signature = (index=None))]
+    fn get_pulse(&self, index: Option<u64>) -> Option<String> {
+        let lock = self.state.lock();
+        let beacon = lock.beacon.as_ref()?;
+        let pulse = match index {
+            Some(i) => beacon.pulse(i),
+            None => beacon.pulses.back(),
+        };
+        pulse.map(|p| p.to_json().to_string())
+    }
+
+    /// The latest beacon pulse emitted at or before `timestamp` (Unix seconds), as JSON
+    fn get_pulse_at(&self, timestamp: u64) -> Option<String> {
+        let lock = self.state.lock();
+        lock.beacon.as_ref()?.pulse_at(timestamp).map(|p| p.to_json().to_string())
+    }
+
+    /// Mints a signature keypair (`algorithm`: "dilithium2"/"dilithium3"/"dilithium5", i.e.
+    /// ML-DSA-44/65/87, "falcon512"/"falcon1024", or "sphincs128s"/"sphincs192s"/"sphincs256s",
+    /// i.e. SLH-DSA-SHA2 small variants) into the vault, in the same signed bundle
+    /// format as mint_pqc_bundle
+    #[pyo3(signature = (algorithm="dilithium3", requester=None))]
+    fn mint_signature_keypair(&self, algorithm: &str, requester: Option<String>) -> PyResult<String> {
+        let requester = requester.unwrap_or_else(|| "LOCAL".to_string());
+        let kind = SignatureKeyKind::from_name(algorithm)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("Unknown signature algorithm: {}", algorithm)))?;
+        let mut lock = self.state.lock();
         
-        let (pk, sk) = falcon512::keypair();
-        let pqc_active = true;
+        if !lock.pqc_active {
+            return Ok("Error: PQC Engine Offline".to_string());
+        }
+        if lock.slow_pool.reseeds == 0 {
+            return Ok("Error: Slow pool not seeded yet".to_string());
+        }
+        check_output_allowed(&lock)?;
+        if let Err(e) = lock.vault.check_writable() {
+            return Ok(format!("Error: {}", e));
+        }
         
-        let mut display_pool = VecDeque::with_capacity(POOL_SIZE);
-        display_pool.extend(vec![0u8; POOL_SIZE]);
+        let (public_key, secret_key) = kind.keypair();
         
-        let state = Arc::new(Mutex::new(SharedState {
-            extraction_pool: EntropyExtractionPool::new(),
-            pool: [0u8; 32],
-            display_pool,
-            history_raw_entropy: VecDeque::from(vec![0.0; HISTORY_LEN]),
-            history_whitened_entropy: VecDeque::from(vec![0.0; HISTORY_LEN]),
-            source_metrics: HashMap::new(),
-            estimated_true_entropy_bits: 0.0,
-            logs: VecDeque::from(vec!["ENGINE: Rust Core v3.3 (P2P Enabled)".to_string()]),
-            total_bytes: 0,
-            net_mode: true,
-            uplink_url: "http://192.168.1.19:8000/ingest".to_string(),
-            sequence_id: 0,
-            falcon_pk: pk.as_bytes().to_vec(),
-            falcon_sk: sk.as_bytes().to_vec(),
-            pqc_active,
-            harvester_states: HarvesterStates::default(),
-            p2p_config: P2PConfig::default(),
-        }));
+        let mut context_hasher = Sha3_256::new();
+        context_hasher.update(lock.slow_pool.state);
+        context_hasher.update(&public_key);
+        let context = context_hasher.finalize();
+        lock.slow_pool.ratchet();
         
-        {
-            let mut lock = state.lock();
-            let ts = chrono::Local::now().format("%H:%M:%S").to_string();
-            lock.logs.push_back(format!("[{}] IDENTITY: Falcon-512 Session Key Generated", ts));
-            lock.logs.push_back(format!("[{}] EXTRACTION: 200→32 byte compression", ts));
+        let timestamp = get_timestamp();
+        
+        let mut bundle = serde_json::json!({
+            "type": "COBRA_PQC_SIG_BUNDLE",
+            "requester": requester,
+            "timestamp": timestamp,
+            "accumulated_true_bits": lock.ledger.available_bits,
+            "conditioning": lock.fortuna.conditioner.extractor.name(),
+            "sp800_90b_h_out_bits": lock.fortuna.last_claim_bits,
+            "slow_pool_reseeds": lock.slow_pool.reseeds,
+            "algorithm": kind.name(),
+            "public_key": hex::encode(&public_key),
+            "secret_key": hex::encode(&secret_key),
+            "pool_commitment": hex::encode(context),
+        });
+        sign_vault_bundle(&lock, &mut bundle).map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        lock.ledger.debit(LEDGER_MINT_DEBIT_BITS);
+        
+        let filename = format!("keys/sigkey_{}_{}_{}.json", kind.name(), timestamp, hex::encode(&public_key[0..4]));
+        if let Err(e) = save_vault_bundle(&mut lock, &filename, bundle, &["secret_key"]) {
+            return Ok(format!("Error: {}", e));
         }
-
-        let running = Arc::new(AtomicBool::new(true));
         
-        start_mixer_thread(rx, state.clone(), running.clone());
-        start_p2p_server(tx.clone(), state.clone(), running.clone());
-        start_trng_harvester(tx.clone(), running.clone(), state.clone());
-        start_audio_harvester(tx.clone(), running.clone(), state.clone());
-        start_system_harvester(tx.clone(), running.clone(), state.clone());
-        start_mouse_harvester(tx.clone(), running.clone(), state.clone());
-        start_video_harvester(tx.clone(), running.clone(), state.clone());
-
-        ChaosEngine { state, running, tx_entropy: tx }
+        Ok(format!("Generated {}", filename))
     }
 
-    fn toggle_harvester(&self, name: String, active: bool) {
+    /// Initialises the key vault: derives its key from `passphrase` with Argon2id under a fresh
+    /// salt and writes keys/vault.json. Minted secrets are sealed from then on. Changing the
+    /// passphrase of an existing vault is not supported.
+    fn set_vault_passphrase(&self, py: Python<'_>, passphrase: String) -> PyResult<()> {
+        if passphrase.is_empty() {
+            return Err(pyo3::exceptions::PyValueError::new_err("Vault passphrase must not be empty"));
+        }
+        if std::path::Path::new(VAULT_META_PATH).exists() {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("Vault passphrase already set ({}); use unlock_vault()", VAULT_META_PATH)));
+        }
+        
+        let mut salt = [0u8; 16];
+        rand::rngs::OsRng.fill_bytes(&mut salt);
+        let mut key = py.allow_threads(|| vault_derive_key(&passphrase, &salt, VAULT_ARGON2_M_COST_KIB, VAULT_ARGON2_T_COST, VAULT_ARGON2_P_COST))
+            .map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        let check = vault_seal(&key, VAULT_CHECK, VAULT_META_PATH.as_bytes())
+            .map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        let meta = serde_json::json!({
+            "kdf": "argon2id",
+            "m_cost_kib": VAULT_ARGON2_M_COST_KIB,
+            "t_cost": VAULT_ARGON2_T_COST,
+            "p_cost": VAULT_ARGON2_P_COST,
+            "salt": hex::encode(salt),
+            "check": check,
+        });
+        let contents = serde_json::to_vec_pretty(&meta)
+            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+        let written = write_vault_file(VAULT_META_PATH, &contents);
+        
         let mut lock = self.state.lock();
-        match name.to_uppercase().as_str() {
-            "TRNG" | "HARDWARE/TRNG" => lock.harvester_states.trng = active,
-            "AUDIO" | "AUDIO (MIC)" => lock.harvester_states.audio = active,
-            "SYS" | "SYSTEM" | "SYSTEM/CPU" => lock.harvester_states.system = active,
-            "MOUSE" | "HID (MOUSE)" => lock.harvester_states.mouse = active,
-            "VIDEO" | "VIDEO (CAM)" => lock.harvester_states.video = active,
-            _ => {}
+        if let Err(e) = written {
+            key.zeroize();
+            return Err(pyo3::exceptions::PyIOError::new_err(format!("{}: {}", VAULT_META_PATH, e)));
         }
+        lock.vault.lock();
+        lock.vault.key = Some(key);
+        key.zeroize();
         
-        let status = if active { "Active" } else { "Inactive" };
         let ts = chrono::Local::now().format("%H:%M:%S").to_string();
-        let msg = format!("[{}] Toggle: {} -> {}", ts, name, status);
+        let msg = format!("[{}] VAULT: Passphrase set, vault unlocked", ts);
         if lock.logs.len() >= 20 { lock.logs.pop_front(); }
         lock.logs.push_back(msg);
+        Ok(())
     }
 
-    fn toggle_uplink(&self, active: bool) {
+    /// Derives the vault key from `passphrase` and checks it against keys/vault.json; raises
+    /// ValueError on a wrong passphrase
+    fn unlock_vault(&self, py: Python<'_>, passphrase: String) -> PyResult<()> {
+        let contents = fs::read(VAULT_META_PATH)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(format!("No vault at {} ({}); call set_vault_passphrase() first", VAULT_META_PATH, e)))?;
+        let meta: serde_json::Value = serde_json::from_slice(&contents)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("{}: {}", VAULT_META_PATH, e)))?;
+        let param = |name: &str| meta.get(name).and_then(|v| v.as_u64()).and_then(|v| u32::try_from(v).ok());
+        let (Some(m_cost), Some(t_cost), Some(p_cost), Some(salt)) = (
+            param("m_cost_kib"),
+            param("t_cost"),
+            param("p_cost"),
+            meta.get("salt").and_then(|v| v.as_str()).and_then(|h| hex::decode(h).ok()),
+        ) else {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("{}: missing KDF parameters", VAULT_META_PATH)));
+        };
+        
+        let mut key = py.allow_threads(|| vault_derive_key(&passphrase, &salt, m_cost, t_cost, p_cost))
+            .map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        let check = vault_open(&key, &meta["check"], VAULT_META_PATH.as_bytes());
+        if !check.is_ok_and(|c| c == VAULT_CHECK) {
+            key.zeroize();
+            return Err(pyo3::exceptions::PyValueError::new_err("Wrong vault passphrase"));
+        }
+        
         let mut lock = self.state.lock();
-        lock.net_mode = active;
+        lock.vault.lock();
+        lock.vault.key = Some(key);
+        key.zeroize();
         
-        let status = if active { "ENABLED" } else { "PAUSED" };
         let ts = chrono::Local::now().format("%H:%M:%S").to_string();
-        let msg = format!("[{}] Network Uplink -> {}", ts, status);
+        let msg = format!("[{}] VAULT: Unlocked", ts);
         if lock.logs.len() >= 20 { lock.logs.pop_front(); }
         lock.logs.push_back(msg);
+        Ok(())
     }
 
-    fn toggle_p2p(&self, active: bool) {
+    /// Wipes the vault key from memory; minting then fails until unlock_vault()
+    fn lock_vault(&self) {
         let mut lock = self.state.lock();
-        lock.p2p_config.active = active;
+        lock.vault.lock();
         
-        let status = if active { "ENABLED" } else { "PAUSED" };
         let ts = chrono::Local::now().format("%H:%M:%S").to_string();
-        let msg = format!("[{}] P2P Mode -> {}", ts, status);
+        let msg = format!("[{}] VAULT: Locked", ts);
         if lock.logs.len() >= 20 { lock.logs.pop_front(); }
         lock.logs.push_back(msg);
     }
 
-    fn set_p2p_port(&self, port: u16) {
+    /// Explicit opt-in to writing secret keys as plaintext hex while the vault is locked
+    fn set_vault_plaintext_export(&self, enabled: bool) {
         let mut lock = self.state.lock();
-        lock.p2p_config.listen_port = port;
+        lock.vault.allow_plaintext = enabled;
         
         let ts = chrono::Local::now().format("%H:%M:%S").to_string();
-        let msg = format!("[{}] P2P: Listen port set to {}", ts, port);
+        let msg = if enabled {
+            format!("[{}] VAULT: WARNING plaintext secret export enabled", ts)
+        } else {
+            format!("[{}] VAULT: Plaintext secret export disabled", ts)
+        };
         if lock.logs.len() >= 20 { lock.logs.pop_front(); }
         lock.logs.push_back(msg);
     }
 
-    fn add_peer(&self, peer_addr: String) {
+    /// Keeps the Falcon identity across restarts, sealed in the vault at `path`: loads it from
+    /// there when the file exists (replacing this session's key), otherwise saves the current
+    /// one. Needs the vault unlocked. Returns the identity public key (hex).
+    #[pyo3(signature = (path="keys/identity.json".to_string()))]
+    fn enable_identity_persistence(&self, path: String) -> PyResult<String> {
         let mut lock = self.state.lock();
-        if !lock.p2p_config.peers.contains(&peer_addr) {
-            lock.p2p_config.peers.push(peer_addr.clone());
-            
-            let ts = chrono::Local::now().format("%H:%M:%S").to_string();
-            let msg = format!("[{}] P2P: Added peer {}", ts, peer_addr);
-            if lock.logs.len() >= 20 { lock.logs.pop_front(); }
-            lock.logs.push_back(msg);
+        if lock.vault.key.is_none() {
+            return Err(pyo3::exceptions::PyRuntimeError::new_err("Identity persistence needs an unlocked vault"));
         }
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = if std::path::Path::new(&path).exists() {
+            let (kind, pk, sk) = read_identity_file(&lock.vault, &path)
+                .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("{}: {}", path, e)))?;
+            lock.falcon_sk.zeroize();
+            lock.falcon_kind = kind;
+            lock.falcon_pk = pk;
+            lock.falcon_sk = sk;
+            format!("[{}] IDENTITY: Loaded persistent {} key {} from {}", ts, kind.name(), hex::encode(&lock.falcon_pk[..8]), path)
+        } else {
+            write_identity_file(&lock.vault, &path, lock.falcon_kind, &lock.falcon_pk, &lock.falcon_sk)
+                .map_err(|e| pyo3::exceptions::PyIOError::new_err(format!("{}: {}", path, e)))?;
+            format!("[{}] IDENTITY: Session key persisted to {}", ts, path)
+        };
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        
+        lock.identity_path = Some(path);
+        Ok(hex::encode(&lock.falcon_pk))
     }
 
-    #[pyo3(signature = (requester=None))]
-    fn mint_pqc_bundle(&self, requester: Option<String>) -> PyResult<String> {
-        let requester = requester.unwrap_or_else(|| "LOCAL".to_string());
+    /// Hex public key of the current Falcon identity
+    fn get_identity_pubkey(&self) -> String {
+        hex::encode(&self.state.lock().falcon_pk)
+    }
+
+    /// Encapsulates to a peer's ML-KEM public key (hex; Kyber-512/768/1024 inferred from its
+    /// length, e.g. `kyber_pk` from another node's export_public). Returns (shared_secret,
+    /// ciphertext); send the ciphertext to the peer for kem_decapsulate.
+    fn kem_encapsulate<'py>(&self, py: Python<'py>, peer_pk: &str) -> PyResult<(Bound<'py, pyo3::types::PyBytes>, Bound<'py, pyo3::types::PyBytes>)> {
+        let pk = hex::decode(peer_pk).map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("peer_pk: {}", e)))?;
+        let kem = KemKind::from_public_key(&pk)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("peer_pk is not an ML-KEM public key ({} bytes)", pk.len())))?;
+        let (mut shared_secret, ciphertext) = kem.encapsulate(&pk).map_err(pyo3::exceptions::PyValueError::new_err)?;
+        let result = (pyo3::types::PyBytes::new(py, &shared_secret), pyo3::types::PyBytes::new(py, &ciphertext));
+        shared_secret.zeroize();
+        Ok(result)
+    }
+
+    /// Shared secret for `ciphertext` using the vault key `key_id` (a KEM or hybrid bundle);
+    /// the secret key is unsealed in memory only and never returned
+    fn kem_decapsulate<'py>(&self, py: Python<'py>, key_id: &str, ciphertext: &[u8]) -> PyResult<Bound<'py, pyo3::types::PyBytes>> {
+        read_stored_key(key_id).map_err(pyo3::exceptions::PyValueError::new_err)?;
+        let path = vault_key_path(key_id).map_err(pyo3::exceptions::PyValueError::new_err)?;
         let mut lock = self.state.lock();
+        let mut bundle = read_vault_bundle(&lock.vault, &path).map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        let Some(serde_json::Value::String(mut sk_hex)) = bundle.as_object_mut().and_then(|fields| fields.remove("kyber_sk")) else {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("{} has no ML-KEM secret key", key_id)));
+        };
+        let sk = hex::decode(&sk_hex);
+        sk_hex.zeroize();
+        let mut sk = sk.map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("{}: kyber_sk: {}", key_id, e)))?;
+        
+        let kem = bundle["kem"].as_str().and_then(KemKind::from_name).or_else(|| KemKind::from_secret_key(&sk));
+        let shared_secret = kem
+            .ok_or_else(|| format!("{}: unknown ML-KEM parameter set", key_id))
+            .and_then(|kem| kem.decapsulate(&sk, ciphertext));
+        sk.zeroize();
+        let mut shared_secret = shared_secret.map_err(pyo3::exceptions::PyValueError::new_err)?;
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("[{}] VAULT: Decapsulated with {}", ts, key_id);
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        drop(lock);
         
+        let result = pyo3::types::PyBytes::new(py, &shared_secret);
+        shared_secret.zeroize();
+        Ok(result)
+    }
+
+    /// Falcon detached signature over `data` with the engine identity (domain-separated from
+    /// the engine's own signatures); check it with verify() and get_identity_pubkey()
+    fn sign<'py>(&self, py: Python<'py>, data: &[u8]) -> PyResult<Bound<'py, pyo3::types::PyBytes>> {
+        let message = user_data_message(data);
+        let signature = {
+            let lock = self.state.lock();
+            if !lock.pqc_active {
+                return Err(pyo3::exceptions::PyRuntimeError::new_err("PQC engine offline"));
+            }
+            lock.falcon_kind.sign(&lock.falcon_sk, &message).map_err(pyo3::exceptions::PyRuntimeError::new_err)?
+        };
+        Ok(pyo3::types::PyBytes::new(py, &signature))
+    }
+
+    /// Checks a sign() signature; `signer_pk` (hex) defaults to this engine's current identity.
+    /// Falcon-512 vs -1024 is inferred from the key length.
+    #[pyo3(signature = (data, signature, signer_pk=None))]
+    fn verify(&self, data: &[u8], signature: &[u8], signer_pk: Option<&str>) -> PyResult<bool> {
+        let signer = match signer_pk {
+            Some(pk) => hex::decode(pk).map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("signer_pk: {}", e)))?,
+            None => self.state.lock().falcon_pk.clone(),
+        };
+        let Some(kind) = FalconKind::from_public_key(&signer) else {
+            return Ok(false);
+        };
+        Ok(kind.verify(&signer, &user_data_message(data), signature))
+    }
+
+    /// Replaces the Falcon identity (same scheme unless `signer` is given). The rotation record
+    /// is signed by both the outgoing and the incoming key, appended to
+    /// keys/identity_rotations.jsonl, and returned as JSON. With persistence on, the new key
+    /// is saved first, so a locked vault refuses the rotation.
+    #[pyo3(signature = (signer=None))]
+    fn rotate_identity(&self, signer: Option<&str>) -> PyResult<String> {
+        let mut lock = self.state.lock();
         if !lock.pqc_active {
-            return Ok("Error: PQC Engine Offline".to_string());
+            return Err(pyo3::exceptions::PyRuntimeError::new_err("PQC engine offline"));
         }
+        let new_kind = match signer {
+            Some(name) => FalconKind::from_name(name)
+                .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("Unknown signer: {} (falcon512 or falcon1024)", name)))?,
+            None => lock.falcon_kind,
+        };
+        let (new_pk, mut new_sk) = new_kind.keypair();
         
-        let (kyber_pk, kyber_sk) = kyber512::keypair();
+        let record = serde_json::json!({
+            "type": "COBRA_IDENTITY_ROTATION",
+            "timestamp": get_timestamp(),
+            "old_signer": lock.falcon_kind.name(),
+            "old_public_key": hex::encode(&lock.falcon_pk),
+            "new_signer": new_kind.name(),
+            "new_public_key": hex::encode(&new_pk),
+        });
+        let message = record.to_string();
+        let signatures = lock.falcon_kind.sign(&lock.falcon_sk, message.as_bytes())
+            .and_then(|old_sig| new_kind.sign(&new_sk, message.as_bytes()).map(|new_sig| (old_sig, new_sig)));
+        let (old_sig, new_sig) = match signatures {
+            Ok(sigs) => sigs,
+            Err(e) => {
+                new_sk.zeroize();
+                return Err(pyo3::exceptions::PyRuntimeError::new_err(e));
+            }
+        };
         
-        let mut context_hasher = Sha3_256::new();
-        context_hasher.update(&lock.pool);
-        context_hasher.update(kyber_pk.as_bytes());
-        let context = context_hasher.finalize();
+        if let Some(path) = lock.identity_path.as_deref() {
+            if let Err(e) = write_identity_file(&lock.vault, path, new_kind, &new_pk, &new_sk) {
+                new_sk.zeroize();
+                return Err(pyo3::exceptions::PyRuntimeError::new_err(format!("Rotation aborted: {}", e)));
+            }
+        }
         
-        let falcon_secret = falcon512::SecretKey::from_bytes(&lock.falcon_sk)
-            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
-        let signature = falcon512::detached_sign(&context, &falcon_secret);
-        let timestamp = get_timestamp();
-
-        let bundle = serde_json::json!({
-            "type": "COBRA_PQC_BUNDLE",
-            "requester": requester,
-            "timestamp": timestamp,
-            "accumulated_true_bits": lock.estimated_true_entropy_bits,
-            "kyber_pk": hex::encode(kyber_pk.as_bytes()),
-            "kyber_sk": hex::encode(kyber_sk.as_bytes()),
-            "falcon_sig": hex::encode(signature.as_bytes()),
-            "falcon_signer_pk": hex::encode(&lock.falcon_pk),
+        let event = serde_json::json!({
+            "record": record,
+            "old_sig": hex::encode(&old_sig),
+            "new_sig": hex::encode(&new_sig),
         });
+        let appended = fs::OpenOptions::new().create(true).append(true).open(IDENTITY_ROTATION_LOG)
+            .and_then(|mut file| std::io::Write::write_all(&mut file, format!("{}\n", event).as_bytes()));
+        
+        lock.falcon_sk.zeroize();
+        lock.falcon_kind = new_kind;
+        lock.falcon_pk = new_pk;
+        lock.falcon_sk = new_sk;
+        
+        let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+        let msg = match appended {
+            Ok(()) => format!("[{}] IDENTITY: Rotated to {} key {} (cross-signed)", ts, new_kind.name(), hex::encode(&lock.falcon_pk[..8])),
+            Err(e) => format!("[{}] IDENTITY: Rotated to {} key {}, rotation log write failed: {}", ts, new_kind.name(), hex::encode(&lock.falcon_pk[..8]), e),
+        };
+        if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+        lock.logs.push_back(msg);
+        
+        Ok(event.to_string())
+    }
 
-        let filename = format!("keys/key_{}_{}.json", timestamp, hex::encode(&kyber_pk.as_bytes()[0..4]));
-        if let Ok(file) = fs::File::create(&filename) {
-            let _ = serde_json::to_writer_pretty(file, &bundle);
-        }
+    /// Metadata for every minted bundle in keys/ as a JSON array, oldest first: id (file stem),
+    /// type, algorithm, timestamp, requester, entropy at mint, whether secrets are sealed, and
+    /// whether the identity signature checks out. Never reads secrets.
+    fn list_keys(&self) -> PyResult<String> {
+        let entries = fs::read_dir(VAULT_DIR)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(format!("{}: {}", VAULT_DIR, e)))?;
+        let mut keys: Vec<serde_json::Value> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                let id = path.file_stem()?.to_str()?.to_string();
+                if path.extension()? != "json" {
+                    return None;
+                }
+                read_stored_key(&id).ok().map(|bundle| vault_key_metadata(&id, &bundle))
+            })
+            .collect();
+        keys.sort_by_key(|k| (k["timestamp"].as_u64().unwrap_or(0), k["id"].as_str().unwrap_or("").to_string()));
+        Ok(serde_json::Value::Array(keys).to_string())
+    }
+
+    /// Full bundle for `id` as JSON with secrets unsealed (needs the vault unlocked if sealed),
+    /// plus its list_keys metadata under "metadata"
+    fn get_key(&self, id: &str) -> PyResult<String> {
+        let stored = read_stored_key(id).map_err(pyo3::exceptions::PyValueError::new_err)?;
+        let path = vault_key_path(id).map_err(pyo3::exceptions::PyValueError::new_err)?;
+        let mut bundle = {
+            let lock = self.state.lock();
+            read_vault_bundle(&lock.vault, &path).map_err(pyo3::exceptions::PyRuntimeError::new_err)?
+        };
+        bundle["metadata"] = vault_key_metadata(id, &stored);
+        Ok(bundle.to_string())
+    }
 
+    /// Removes keys/<id>.json
+    fn delete_key(&self, id: &str) -> PyResult<()> {
+        read_stored_key(id).map_err(pyo3::exceptions::PyValueError::new_err)?;
+        let path = vault_key_path(id).map_err(pyo3::exceptions::PyValueError::new_err)?;
+        fs::remove_file(&path).map_err(|e| pyo3::exceptions::PyIOError::new_err(format!("{}: {}", path, e)))?;
+        
+        let mut lock = self.state.lock();
         let ts = chrono::Local::now().format("%H:%M:%S").to_string();
-        let msg = format!("[{}] VAULT: Saved {}", ts, filename);
+        let msg = format!("[{}] VAULT: Deleted {}", ts, path);
         if lock.logs.len() >= 20 { lock.logs.pop_front(); }
         lock.logs.push_back(msg);
+        Ok(())
+    }
 
-        Ok(format!("Generated {}", filename))
+    /// Bundle for `id` as JSON with all secret material (sealed or not) stripped, safe to share
+    fn export_public(&self, id: &str) -> PyResult<String> {
+        let mut bundle = read_stored_key(id).map_err(pyo3::exceptions::PyValueError::new_err)?;
+        if let Some(fields) = bundle.as_object_mut() {
+            fields.remove("vault");
+            for field in VAULT_SECRET_FIELDS {
+                fields.remove(*field);
+            }
+        }
+        Ok(bundle.to_string())
     }
 
     fn set_network_target(&self, ip: String) {
@@ -960,38 +9808,262 @@ impl ChaosEngine {
                     "avg_entropy": m.avg_raw_entropy,
                     "samples": m.samples,
                     "total_bits": m.total_bits_contributed,
+                    "chi_square": m.chi_square,
+                    "chi_square_alarm": m.chi_square_alarm,
+                    "lag_min_entropy": m.lag_min_entropy,
+                    "lag_period": m.lag_period,
+                    "markov_min_entropy": m.markov_min_entropy,
+                    "collision_min_entropy": m.collision_min_entropy,
+                    "compression_min_entropy": m.compression_min_entropy,
+                    "window_mcv_min_entropy": m.mcv_min_entropy,
+                    "assessed_min_entropy": m.assessed_min_entropy(),
+                    "iid": m.iid,
+                    "tainted": m.tainted,
+                    "tainted_samples": m.tainted_samples,
+                    "credited_bits": lock.ledger.credited_by_source.get(name).copied().unwrap_or(0.0),
+                }))
+            })
+            .collect();
+        
+        let startup_tests: HashMap<String, serde_json::Value> = lock.source_health.iter()
+            .map(|(name, h)| {
+                (name.clone(), serde_json::json!({
+                    "status": h.startup_status(),
+                    "buffered_samples": h.startup_buffer.len(),
+                    "required_samples": STARTUP_TEST_SAMPLES,
+                    "failures": h.startup_failures,
+                    "continuous_failures": h.continuous_failures,
+                }))
+            })
+            .collect();
+        
+        let source_states: HashMap<String, serde_json::Value> = lock.source_health.iter()
+            .map(|(name, h)| {
+                (name.clone(), serde_json::json!({
+                    "state": h.state.name(),
+                    "consecutive_failures": h.consecutive_failures,
+                    "continuous_failures": h.continuous_failures,
+                    "startup_failures": h.startup_failures,
+                    "credited": h.state == HealthState::Healthy,
+                    "checks_passed": h.checks_passed,
+                    "checks_failed": h.continuous_failures,
+                    "bytes_rejected": h.bytes_rejected,
+                    "rct_last_run": h.rct_last_run,
+                    "rct_max_run": h.rct_max_run,
+                    "apt_last_proportion": h.apt_last_proportion,
+                    "apt_max_proportion": h.apt_max_proportion,
+                    "retest_attempts": h.retest_attempts,
+                    "next_retest_secs": h.next_retest.map(|at| at.saturating_duration_since(Instant::now()).as_secs()),
+                }))
+            })
+            .collect();
+        
+        let mobile_devices: HashMap<String, serde_json::Value> = lock.mobile_devices.iter()
+            .map(|(id, d)| {
+                (id.clone(), serde_json::json!({
+                    "source": mobile_source_name(id),
+                    "accepted": d.accepted,
+                    "rejected": d.rejected,
+                    "quarantined": d.quarantined,
+                }))
+            })
+            .collect();
+        
+        let last_reseed = lock.output_consumers.values()
+            .filter(|c| c.drbg.is_some())
+            .max_by_key(|c| c.last_reseed_at);
+        let output_consumers: HashMap<String, serde_json::Value> = lock.output_consumers.iter()
+            .map(|(name, c)| {
+                (name.clone(), serde_json::json!({
+                    "generator": c.drbg.as_ref().map(|d| d.name()),
+                    "reseeds": c.drbg.as_ref().map(|d| d.reseeds()),
+                    "reseed_counter": c.drbg.as_ref().map(|d| d.reseed_counter()),
+                    "prediction_resistance": c.prediction_resistance,
+                    "bytes_generated": c.bytes_generated,
+                    "quota": {
+                        "bytes_per_sec": c.quota.bytes_per_sec,
+                        "bytes_per_reseed": c.quota.bytes_per_reseed,
+                        "bytes_this_reseed": c.quota.reseed_bytes,
+                        "denied_requests": c.quota.denied_requests,
+                    },
+                    "last_reseed_time": c.last_reseed_at,
+                    "last_reseed_reason": c.last_reseed_reason,
                 }))
             })
             .collect();
         
         let metrics = serde_json::json!({
-            "pool_hex": hex::encode(lock.pool).to_uppercase(),
+            "pool_view": hex::encode(lock.accumulator.view()).to_uppercase(),
             "total_bytes": lock.total_bytes,
+            "ratchet_count": lock.accumulator.ratchets,
+            "whitener": {
+                "fold_rounds": lock.whitener.fold_rounds,
+                "hash": lock.whitener.hash,
+                "samples": lock.whitener.samples,
+                "hashed_samples": lock.whitener.hashed_samples,
+                "bytes_in": lock.whitener.bytes_in,
+                "bytes_out": lock.whitener.bytes_out,
+            },
+            "accumulator": {
+                "mixes": lock.accumulator.mixes,
+                "bytes_mixed": lock.accumulator.bytes_mixed,
+                "ratchets": lock.accumulator.ratchets,
+            },
             "current_entropy": current_raw,
             "current_raw_entropy": current_raw,
             "current_whitened_entropy": current_whitened,
-            "estimated_true_bits": lock.estimated_true_entropy_bits,
+            "pool_entropy_available_bits": lock.ledger.available_bits,
+            "ledger_credited_bits": lock.ledger.total_credited_bits,
+            "ledger_debited_bits": lock.ledger.total_debited_bits,
             
             // NEW: Extraction pool metrics
-            "extraction_pool_fill": lock.extraction_pool.fill_percentage(),
-            "extraction_pool_accumulated": lock.extraction_pool.accumulated_bytes(),
-            "extractions_count": lock.extraction_pool.extractions_count,
-            "total_raw_consumed": lock.extraction_pool.total_raw_consumed,
-            "total_extracted_bytes": lock.extraction_pool.total_extracted_bytes,
+            "extraction_pool_fill": lock.fortuna.pool_fill(0),
+            "extraction_pool_accumulated": lock.fortuna.accumulated_bytes(),
+            "extractions_count": lock.fortuna.reseed_count,
+            "extractor": lock.fortuna.conditioner.extractor.name(),
+            "vetted_conditioning": lock.fortuna.conditioner.extractor.vetted_width().is_some(),
+            "claimed_h_out_bits": lock.fortuna.last_claim_bits,
+            "total_claimed_bits": lock.fortuna.total_claimed_bits,
+            "total_raw_consumed": lock.fortuna.total_raw_consumed(),
+            "total_extracted_bytes": lock.fortuna.total_extracted_bytes(),
+            "xof_extractions": lock.fortuna.xof_extractions,
+            "fortuna_pool_fill": (0..FORTUNA_POOLS).map(|i| lock.fortuna.pool_fill(i)).collect::<Vec<_>>(),
+            "source_pool_fill": lock.fortuna.source_pool_fill(),
+            "extraction_input_bytes": lock.fortuna.conditioner.input_bytes,
+            "extraction_output_bytes": lock.fortuna.conditioner.output_bytes,
+            "extraction_ratio": lock.fortuna.conditioner.input_bytes as f64 / lock.fortuna.conditioner.output_bytes as f64,
+            "adaptive_extraction": lock.fortuna.conditioner.adaptive,
+            "slow_pool": {
+                "reseeds": lock.slow_pool.reseeds,
+                "fill": lock.slow_pool.fill_percentage(),
+                "pending_bits": lock.slow_pool.pending_bits,
+                "pending_bytes": lock.slow_pool.pending_bytes,
+                "reseed_bits": SLOW_POOL_RESEED_BITS,
+                "last_reseed_sources": lock.slow_pool.last_contributors,
+            },
+            "pool_routes": lock.pool_routes.iter().map(|(k, v)| (k.clone(), v.name())).collect::<HashMap<_, _>>(),
+            "effective_extraction_ratio": lock.fortuna.last_effective_ratio,
             
             "source_quality": source_quality,
+            "startup_tests": startup_tests,
+            "source_states": source_states,
+            "failure_mode": entropy_failure(&lock),
+            "raw_captures": lock.raw_captures.iter()
+                .map(|(name, c)| (name.clone(), serde_json::json!({
+                    "path": c.path,
+                    "written": c.written,
+                    "requested": c.requested,
+                })))
+                .collect::<HashMap<_, _>>(),
+            "entropy_floor_bits": lock.entropy_floor_bits,
+            "egd": lock.egd_server.as_ref().map(|s| serde_json::json!({
+                "path": s.path,
+                "clients": s.clients.iter()
+                    .map(|(name, c)| (name.clone(), serde_json::json!({
+                        "connected": c.connected,
+                        "connections": c.connections,
+                        "requests": c.requests,
+                        "bytes_served": c.bytes_served,
+                        "bytes_received": c.bytes_received,
+                    })))
+                    .collect::<HashMap<_, _>>(),
+            })),
+            "socket_service": lock.socket_service.as_ref().map(|s| serde_json::json!({
+                "path": s.path,
+                "allowed_uids": s.allowed_uids,
+                "clients": s.clients.iter()
+                    .map(|(uid, c)| (uid.to_string(), serde_json::json!({
+                        "requests": c.requests,
+                        "bytes_served": c.bytes_served,
+                        "denied": c.denied,
+                    })))
+                    .collect::<HashMap<_, _>>(),
+            })),
+            "beacon": lock.beacon.as_ref().map(|b| serde_json::json!({
+                "interval_secs": b.interval_secs,
+                "http_port": b.http_port,
+                "running": !b.stop.load(Ordering::Relaxed),
+                "pulses_emitted": b.next_index,
+                "pulses_retained": b.pulses.len(),
+                "last_output_value": hex::encode(b.last_output),
+            })),
+            "file_sink": lock.file_sink.as_ref().map(|s| serde_json::json!({
+                "path": s.path,
+                "rotate_at_bytes": s.rotate_at_bytes,
+                "keep_files": s.keep_files,
+                "rate_limit": s.rate_limit,
+                "bytes_written": s.bytes_written,
+                "rotations": s.rotations,
+            })),
+            "fifo_outputs": lock.fifo_outputs.iter()
+                .map(|(path, f)| (path.clone(), serde_json::json!({
+                    "rate_limit": f.rate_limit,
+                    "bytes_written": f.bytes_written,
+                    "readers": f.readers,
+                })))
+                .collect::<HashMap<_, _>>(),
+            "kernel_feed": lock.kernel_feed.as_ref().map(|f| serde_json::json!({
+                "bits_per_byte": f.bits_per_byte,
+                "bytes_fed": f.bytes_fed,
+                "bits_credited": f.bits_credited,
+                "last_error": f.last_error,
+            })),
+            "health_params": lock.health_params.iter()
+                .map(|(name, p)| (name.clone(), serde_json::json!({
+                    "rct_cutoff": p.rct_cutoff,
+                    "apt_cutoff": p.apt_cutoff,
+                    "window": p.window,
+                    "apt_cutoff_count": p.apt_cutoff_count(),
+                    "chi_square_threshold": p.chi_square_threshold,
+                })))
+                .collect::<HashMap<_, _>>(),
             "history": lock.history_raw_entropy.iter().collect::<Vec<_>>(),
             "history_raw": lock.history_raw_entropy.iter().collect::<Vec<_>>(),
             "history_whitened": lock.history_whitened_entropy.iter().collect::<Vec<_>>(),
             "logs": lock.logs.iter().collect::<Vec<_>>(),
             "net_mode": lock.net_mode,
             "pqc_ready": lock.pqc_active,
+            "pqc_identity": {
+                "signer": lock.falcon_kind.name(),
+                "public_key": hex::encode(&lock.falcon_pk),
+            },
+            "vault": {
+                "initialized": std::path::Path::new(VAULT_META_PATH).exists(),
+                "unlocked": lock.vault.key.is_some(),
+                "plaintext_export": lock.vault.allow_plaintext,
+                "bundles_sealed": lock.vault.bundles_sealed,
+            },
+            "output_consumers": output_consumers,
+            "reseed_policy": {
+                "max_age_secs": lock.reseed_policy.max_age_secs,
+                "max_output_bytes": lock.reseed_policy.max_output_bytes,
+                "min_credit_bits": lock.reseed_policy.min_credit_bits,
+            },
+            "last_reseed_time": last_reseed.map(|c| c.last_reseed_at),
+            "last_reseed_reason": last_reseed.map(|c| c.last_reseed_reason),
             
             // NEW: P2P metrics
             "p2p_active": lock.p2p_config.active,
             "p2p_port": lock.p2p_config.listen_port,
             "p2p_peer_count": lock.p2p_config.peers.len(),
             "p2p_received_count": lock.p2p_config.received_count,
+            "p2p_peer_health": lock.p2p_config.peer_health.iter()
+                .map(|(ip, p)| (ip.clone(), serde_json::json!({
+                    "accepted": p.accepted,
+                    "rejected": p.rejected,
+                    "consecutive_failures": p.consecutive_failures,
+                    "banned": p.banned,
+                })))
+                .collect::<HashMap<_, _>>(),
+            
+            "video_devices": lock.video_devices,
+            "audio_devices": lock.audio_devices,
+            "video_roi": lock.video_roi,
+            "video_dark_mode": lock.video_dark_mode,
+            "mobile_devices": mobile_devices,
+            "custom_sources": lock.custom_sources.iter().collect::<Vec<_>>(),
+            "debiased_sources": lock.debias_sources.keys().collect::<Vec<_>>(),
+            "audio_mode": if lock.audio_mode == AudioMode::Ultrasonic { "ultrasonic" } else { "normal" },
         });
         
         Ok(metrics.to_string())
@@ -999,11 +10071,143 @@ impl ChaosEngine {
     
     fn shutdown(&self) {
         self.running.store(false, Ordering::Relaxed);
+        
+        let mut lock = self.state.lock();
+        if let Some(path) = lock.seed_file.clone() {
+            let ts = chrono::Local::now().format("%H:%M:%S").to_string();
+            let msg = match write_seed_file(&path, &lock.accumulator.pool) {
+                Ok(()) => format!("[{}] SEED: Saved {}", ts, path),
+                Err(e) => format!("[{}] SEED: Could not save {}: {}", ts, path, e),
+            };
+            lock.accumulator.ratchet();
+            if lock.logs.len() >= 20 { lock.logs.pop_front(); }
+            lock.logs.push_back(msg);
+        }
+    }
+}
+
+impl Drop for ChaosStream {
+    fn drop(&mut self) {
+        self.state.lock().output_consumers.remove(&self.name);
+    }
+}
+
+#[pymethods]
+impl ChaosStream {
+    /// `n` bytes (1..=1048576) from this stream's generator
+    fn read<'py>(&self, py: Python<'py>, n: usize) -> PyResult<Bound<'py, pyo3::types::PyBytes>> {
+        if !(1..=1 << 20).contains(&n) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("n must be 1..=1048576, got {}", n)));
+        }
+        let mut out = vec![0u8; n];
+        py.allow_threads(|| {
+            let mut lock = self.state.lock();
+            check_output_allowed(&lock)?;
+            consumer_fill(&mut lock, &self.name, &mut out).map_err(pyo3::exceptions::PyRuntimeError::new_err)
+        })?;
+        let bytes = pyo3::types::PyBytes::new(py, &out);
+        out.zeroize();
+        Ok(bytes)
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Never exhausted: each step yields `chunk_size` bytes
+    fn __next__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, pyo3::types::PyBytes>> {
+        self.read(py, self.chunk_size)
+    }
+
+    #[getter]
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    #[getter]
+    fn bytes_generated(&self) -> u64 {
+        self.state.lock().output_consumers.get(&self.name).map_or(0, |c| c.bytes_generated)
     }
 }
 
 #[pymodule]
-fn chaos_magnet_core(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
+fn chaos_magnet_core(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<ChaosEngine>()?;
+    m.add_class::<ChaosStream>()?;
+    m.add("EntropyFailureError", py.get_type::<EntropyFailureError>())?;
     Ok(())
-}
\ No newline at end of file
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // NIST CAVP drbgvectors_no_reseed, CTR_DRBG.rsp [AES-256 no df], COUNT = 0: instantiate,
+    // generate twice, compare the second output
+    #[test]
+    fn ctr_drbg_known_answer() {
+        let entropy = hex::decode("df5d73faa468649edda33b5cca79b0b05600419ccb7a879ddfec9db32ee494e5531b51de16a30f769262474c73bec010").unwrap();
+        let expected = hex::decode("d1c07cd95af8a7f11012c84ce48bb8cb87189e99d40fccb1771c619bdf82ab2280b1dc2f2581f39164f7ac0c510494b3a43c41b7db17514c87b107ae793e01c5").unwrap();
+        
+        let mut drbg = CtrDrbg::instantiate(&entropy, &[]);
+        let mut out = [0u8; 64];
+        drbg.generate(&mut out, &[]).unwrap();
+        drbg.generate(&mut out, &[]).unwrap();
+        assert_eq!(out.to_vec(), expected);
+    }
+
+    // NIST CAVP drbgvectors_no_reseed, HMAC_DRBG.rsp [SHA-256], COUNT = 0; the nonce is
+    // appended to the entropy input as in 800-90A instantiate
+    #[test]
+    fn hmac_drbg_known_answer() {
+        let mut seed = hex::decode("ca851911349384bffe89de1cbdc46e6831e44d34a4fb935ee285dd14b71a7488").unwrap();
+        seed.extend(hex::decode("659ba96c601dc69fc902940805ec0ca8").unwrap());
+        let expected = hex::decode("e528e9abf2dece54d47c7e75e5fe302149f817ea9fb4bee6f4199697d04d5b89d54fbb978a15b5c443c9ec21036d2460b6f73ebad0dc2aba6e624abf07745bc107694bb7547bb0995f70de25d6b29e2d3011bb19d27676c07162c8b5ccde0668961df86803482cb37ed6d5c0bb8d50cf1f50d476aa0458bdaba806f48be9dcb8").unwrap();
+        
+        let mut drbg = HmacDrbg::instantiate(&seed, &[]);
+        let mut out = [0u8; 128];
+        drbg.generate(&mut out, &[]).unwrap();
+        drbg.generate(&mut out, &[]).unwrap();
+        assert_eq!(out.to_vec(), expected);
+    }
+
+    #[test]
+    fn adaptive_proportion_carries_within_a_window_only() {
+        let mut apt = AdaptiveProportion::default();
+        
+        // Counts carry across submissions inside one window
+        assert!(!apt.feed(&[9, 9], 16, 4).1);
+        assert_eq!(apt.feed(&[9, 9], 16, 4), (4, true));
+        
+        // A failed window is closed: the next sample starts a fresh one
+        assert_eq!(apt.feed(&[9, 1, 2, 3], 16, 4), (1, false));
+        
+        // A full window resets the count, so three hits either side of the boundary never fail
+        let mut apt = AdaptiveProportion::default();
+        let mut first = vec![5, 5, 5];
+        first.extend(10..23);
+        assert_eq!(apt.feed(&first, 16, 4), (3, false));
+        assert_eq!(apt.feed(&[5, 5, 5], 16, 4), (3, false));
+    }
+
+    #[test]
+    fn vault_seal_round_trip_and_aad_binding() {
+        let key = [7u8; 32];
+        let sealed = vault_seal(&key, b"identity secret key", b"identity.json").unwrap();
+        assert_eq!(vault_open(&key, &sealed, b"identity.json").unwrap(), b"identity secret key");
+        
+        // Same ciphertext under different associated data, or a different key, must not open
+        assert!(vault_open(&key, &sealed, b"other.json").is_err());
+        assert!(vault_open(&[8u8; 32], &sealed, b"identity.json").is_err());
+        
+        let mut tampered = sealed.clone();
+        let mut ciphertext = hex::decode(sealed["ciphertext"].as_str().unwrap()).unwrap();
+        ciphertext[0] ^= 1;
+        tampered["ciphertext"] = serde_json::Value::String(hex::encode(ciphertext));
+        assert!(vault_open(&key, &tampered, b"identity.json").is_err());
+    }
+}